@@ -0,0 +1,129 @@
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::const_sort;
+
+/// Runtime conveniences returning sorted copies of slices as [`Vec`]s.
+///
+/// The methods sort with the same algorithms and accept the same comparators as the const
+/// functions of this crate, so a comparator written for a const item can be reused at runtime
+/// without adapting it to the `std` APIs. They are not `const` since they allocate.
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// use const_sort::SliceSortVecExt;
+///
+/// const fn by_len(a: &&str, b: &&str) -> core::cmp::Ordering {
+///   if a.len() < b.len() {
+///     core::cmp::Ordering::Less
+///   } else if a.len() > b.len() {
+///     core::cmp::Ordering::Greater
+///   } else {
+///     core::cmp::Ordering::Equal
+///   }
+/// }
+///
+/// let words = ["ccc", "a", "bb", "dd"];
+/// assert_eq!(words.to_sorted_vec_by(by_len)[0], "a");
+/// assert_eq!(words.argsort_vec_by(by_len), [1, 2, 3, 0]);
+/// assert_eq!(words.sorted_dedup_vec_by(by_len).len(), 3);
+/// ```
+pub trait SliceSortVecExt<T> {
+  /// Returns a sorted copy of the slice.
+  ///
+  /// Like [`ConstSliceSortExt::const_sort_unstable`](crate::ConstSliceSortExt::const_sort_unstable),
+  /// this might not preserve the order of equal elements.
+  #[must_use]
+  fn to_sorted_vec(&self) -> Vec<T>
+  where
+    T: Clone + Ord;
+  /// Returns a copy of the slice sorted with the comparator function `compare`.
+  ///
+  /// This might not preserve the order of equal elements.
+  #[must_use]
+  fn to_sorted_vec_by<F>(&self, compare: F) -> Vec<T>
+  where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering;
+  /// Returns a sorted copy of the slice without duplicates.
+  #[must_use]
+  fn sorted_dedup_vec(&self) -> Vec<T>
+  where
+    T: Clone + Ord;
+  /// Returns a copy of the slice sorted with the comparator function `compare`, keeping one of
+  /// every group of elements that compare equal.
+  #[must_use]
+  fn sorted_dedup_vec_by<F>(&self, compare: F) -> Vec<T>
+  where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering;
+  /// Returns the permutation of indices that sorts the slice.
+  ///
+  /// Position `i` of the result holds the index of the `i`-th smallest element. Indices of equal
+  /// elements keep their original relative order.
+  #[must_use]
+  fn argsort_vec(&self) -> Vec<usize>
+  where
+    T: Ord;
+  /// Returns the permutation of indices that sorts the slice with the comparator function
+  /// `compare`.
+  ///
+  /// Indices of elements that compare equal keep their original relative order.
+  #[must_use]
+  fn argsort_vec_by<F>(&self, compare: F) -> Vec<usize>
+  where
+    F: FnMut(&T, &T) -> Ordering;
+}
+
+impl<T> SliceSortVecExt<T> for [T] {
+  fn to_sorted_vec(&self) -> Vec<T>
+  where
+    T: Clone + Ord,
+  {
+    self.to_sorted_vec_by(Ord::cmp)
+  }
+  fn to_sorted_vec_by<F>(&self, mut compare: F) -> Vec<T>
+  where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+  {
+    let mut v = self.to_vec();
+    const_sort::const_quicksort(&mut v, |a, b| compare(a, b) == Ordering::Less);
+    v
+  }
+  fn sorted_dedup_vec(&self) -> Vec<T>
+  where
+    T: Clone + Ord,
+  {
+    self.sorted_dedup_vec_by(Ord::cmp)
+  }
+  fn sorted_dedup_vec_by<F>(&self, mut compare: F) -> Vec<T>
+  where
+    T: Clone,
+    F: FnMut(&T, &T) -> Ordering,
+  {
+    let mut v = self.to_sorted_vec_by(&mut compare);
+    v.dedup_by(|a, b| compare(a, b) == Ordering::Equal);
+    v
+  }
+  fn argsort_vec(&self) -> Vec<usize>
+  where
+    T: Ord,
+  {
+    self.argsort_vec_by(Ord::cmp)
+  }
+  fn argsort_vec_by<F>(&self, mut compare: F) -> Vec<usize>
+  where
+    F: FnMut(&T, &T) -> Ordering,
+  {
+    let mut indices: Vec<usize> = (0..self.len()).collect();
+    const_sort::const_quicksort(&mut indices, |&a, &b| match compare(&self[a], &self[b]) {
+      Ordering::Equal => a < b,
+      ordering => ordering == Ordering::Less,
+    });
+    indices
+  }
+}
@@ -0,0 +1,53 @@
+//! Versioned re-exports of the public API.
+//!
+//! The const trait syntax this crate is built on is still unstable, and adapting to a new nightly
+//! may require renaming or restructuring items at the crate root. Every `api::vN` module keeps
+//! re-exporting the items of its version under the same names across such changes, so downstream
+//! code that imports from it only has to change when it opts into a new version.
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(const_mut_refs)]
+//! #![feature(const_trait_impl)]
+//! // The glob also imports the `const_sort` module, so the crate itself is named with `::`.
+//! use ::const_sort::api::v1::*;
+//!
+//! const V: [u8; 3] = {
+//!   let mut v = [3, 1, 2];
+//!   v.const_sort_unstable();
+//!   v
+//! };
+//! assert_eq!(V, [1, 2, 3]);
+//!
+//! const_table! {
+//!   const LEVELS: [u8; 3] sorted = [64, 0, 128];
+//! }
+//! assert!(LEVELS::IS_SORTED);
+//! ```
+
+/// Version 1 of the public API, i.e. everything exported by the crate root.
+pub mod v1 {
+  // `const_table` names both the module and the `const_table!` macro, so both are re-exported.
+  pub use crate::{
+    const_array, const_sort, const_table, distance_cmp, float_cmp, key_transform, testgen,
+  };
+
+  pub use crate::{
+    const_assert_unique_keys, const_dedup_array, const_priority_sort, const_shrink_to_count,
+  };
+
+  pub use crate::{
+    self_check, ConstChunkedMap, ConstChunkedMapBuilder, ConstIndexMap,
+    ConstInterpolationSearchExt, ConstKey, ConstPermutationExt, ConstSliceSortExt, ConstSortable,
+    ConstSorted, ConstSortedDesc, ConstSortedDrain, ConstSortedMap, ConstSortedSet,
+    ConstSortedSlice, ConstTable1, ConstTable2, ConstTable3, ConstTable4, OrderSummary,
+    SortedCursor, StabilityProbe, WithIndex,
+  };
+
+  #[cfg(feature = "alloc")]
+  pub use crate::SliceSortVecExt;
+
+  #[cfg(feature = "const-closure-compat")]
+  pub use crate::const_closure;
+}
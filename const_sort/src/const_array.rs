@@ -0,0 +1,699 @@
+//! Helpers for building exactly sized arrays in const items.
+//!
+//! Const generics can not yet depend on values computed inside the same const item. Arrays whose
+//! length is only known after processing their input are therefore built in two const
+//! evaluations: the first one computes the length, the second one copies the elements into an
+//! array of that length. The [`const_shrink_to_count!`](crate::const_shrink_to_count) macro wraps
+//! this pattern for any const expression returning an array together with the number of valid
+//! leading elements.
+//!
+//! Unlike an implementation on top of the incomplete `generic_const_exprs` feature, this works
+//! with the const generics of the feature gates this crate already requires.
+
+use core::marker::Destruct;
+use core::mem::MaybeUninit;
+use core::ptr;
+
+use crate::const_sort::const_quicksort;
+use crate::{ConstSliceSortExt, ConstSorted, WithIndex};
+
+/// Removes consecutive repeated elements from `arr`.
+///
+/// If `arr` is sorted, this removes all duplicates. Returns the array, whose first `len` elements
+/// are the retained elements in their original order, together with `len`. The remaining
+/// elements are the removed duplicates in an unspecified order.
+///
+/// This function is *O*(*n*).
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_array::const_sorted_dedup;
+///
+/// const D: ([u8; 6], usize) = const_sorted_dedup([1, 1, 2, 3, 3, 3]);
+/// assert_eq!(D.1, 3);
+/// assert_eq!(D.0[..D.1], [1, 2, 3]);
+/// ```
+#[must_use]
+pub const fn const_sorted_dedup<T, const N: usize>(mut arr: [T; N]) -> ([T; N], usize)
+where
+  T: ~const PartialEq,
+{
+  if N == 0 {
+    return (arr, 0);
+  }
+
+  let mut len = 1;
+  let mut i = 1;
+  while i < N {
+    if arr[i] != arr[len - 1] {
+      arr.swap(i, len);
+      len += 1;
+    }
+    i += 1;
+  }
+  (arr, len)
+}
+
+/// Removes all but the first of consecutive elements with equal keys from `arr`.
+///
+/// If `arr` is sorted by key, this keeps the first occurrence of every key, e.g. the first
+/// definition of every name in a configuration table. Returns the array, whose first `len`
+/// elements are the retained elements in their original order, together with `len`. The
+/// remaining elements are the removed elements in an unspecified order.
+///
+/// This function is *O*(*n*).
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_array::const_dedup_by_key_keep_first;
+///
+/// const fn id(entry: &(u8, &str)) -> u8 {
+///   entry.0
+/// }
+/// const D: ([(u8, &str); 4], usize) =
+///   const_dedup_by_key_keep_first([(1, "a"), (1, "b"), (2, "c"), (2, "d")], id);
+/// assert_eq!(D.0[..D.1], [(1, "a"), (2, "c")]);
+/// ```
+#[must_use]
+pub const fn const_dedup_by_key_keep_first<T, K, F, const N: usize>(
+  mut arr: [T; N],
+  mut key: F,
+) -> ([T; N], usize)
+where
+  F: ~const FnMut(&T) -> K + ~const Destruct,
+  K: ~const PartialEq + ~const Destruct,
+{
+  if N == 0 {
+    return (arr, 0);
+  }
+
+  let mut len = 1;
+  let mut i = 1;
+  while i < N {
+    if key(&arr[i]) != key(&arr[len - 1]) {
+      arr.swap(i, len);
+      len += 1;
+    }
+    i += 1;
+  }
+  (arr, len)
+}
+
+/// Removes all but the last of consecutive elements with equal keys from `arr`.
+///
+/// Like [`const_dedup_by_key_keep_first`], but keeps the last occurrence of every key, e.g. when
+/// later definitions override earlier ones. The retained elements are in their original order.
+///
+/// This function is *O*(*n*).
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_array::const_dedup_by_key_keep_last;
+///
+/// const fn id(entry: &(u8, &str)) -> u8 {
+///   entry.0
+/// }
+/// const D: ([(u8, &str); 4], usize) =
+///   const_dedup_by_key_keep_last([(1, "a"), (1, "b"), (2, "c"), (2, "d")], id);
+/// assert_eq!(D.0[..D.1], [(1, "b"), (2, "d")]);
+/// ```
+#[must_use]
+pub const fn const_dedup_by_key_keep_last<T, K, F, const N: usize>(
+  mut arr: [T; N],
+  mut key: F,
+) -> ([T; N], usize)
+where
+  F: ~const FnMut(&T) -> K + ~const Destruct,
+  K: ~const PartialEq + ~const Destruct,
+{
+  if N == 0 {
+    return (arr, 0);
+  }
+
+  let mut len = 1;
+  let mut i = 1;
+  while i < N {
+    if key(&arr[i]) == key(&arr[len - 1]) {
+      // Replace the retained element, moving it into the removed elements.
+      arr.swap(i, len - 1);
+    } else {
+      arr.swap(i, len);
+      len += 1;
+    }
+    i += 1;
+  }
+  (arr, len)
+}
+
+/// Finds the first item of `items` whose key equals the key of an earlier item.
+///
+/// Returns `(i, j)` with the smallest `j` such that `key(&items[i]) == key(&items[j])` for some
+/// `i < j`, where `i` is the first item with that key. Returns `None` if all keys are unique. The
+/// order of `items` does not matter.
+///
+/// This is what [`const_assert_unique_keys!`](crate::const_assert_unique_keys) checks at compile
+/// time, e.g. to ensure that the ids of registered handlers are unique.
+///
+/// # Current implementation
+///
+/// The keys are copied into a scratch array together with the index of their item, which is
+/// sorted with [`ConstSliceSortExt::const_sort_unstable`]. This is *O*(*N* \* log(*N*)).
+///
+/// [`ConstSliceSortExt::const_sort_unstable`]: crate::ConstSliceSortExt::const_sort_unstable
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_array::const_find_duplicate_key;
+///
+/// const fn id(handler: &(u16, &str)) -> u16 {
+///   handler.0
+/// }
+/// const HANDLERS: [(u16, &str); 4] = [(7, "read"), (2, "write"), (9, "seek"), (2, "sync")];
+/// const DUPLICATE: Option<(usize, usize)> = const_find_duplicate_key(&HANDLERS, id);
+/// assert_eq!(DUPLICATE, Some((1, 3)));
+/// ```
+#[must_use]
+pub const fn const_find_duplicate_key<T, K, F, const N: usize>(
+  items: &[T; N],
+  mut key: F,
+) -> Option<(usize, usize)>
+where
+  F: ~const FnMut(&T) -> K + ~const Destruct,
+  K: ~const PartialOrd + Ord + Copy,
+{
+  let mut keys = [MaybeUninit::<WithIndex<K>>::uninit(); N];
+  let mut i = 0;
+  while i < N {
+    keys[i].write(WithIndex::new(key(&items[i]), i));
+    i += 1;
+  }
+  // SAFETY: All `N` elements were initialized above and `[MaybeUninit<WithIndex<K>>; N]` has the
+  // same layout as `[WithIndex<K>; N]`.
+  let mut keys = unsafe { ptr::read(keys.as_ptr().cast::<[WithIndex<K>; N]>()) };
+  // Equal keys end up next to each other, ordered by the index of their item.
+  keys.const_sort_unstable();
+
+  let mut duplicate: Option<(usize, usize)> = None;
+  let mut i = 1;
+  while i < N {
+    let (first, second) = (&keys[i - 1], &keys[i]);
+    if first.value.lt(&second.value) {
+      i += 1;
+    } else {
+      let is_earlier = match duplicate {
+        Some((_, j)) => second.index < j,
+        None => true,
+      };
+      if is_earlier {
+        duplicate = Some((first.index, second.index));
+      }
+      // Skip the rest of the group, its indices are all greater than `second.index`.
+      while i < N && !keys[i - 1].value.lt(&keys[i].value) {
+        i += 1;
+      }
+    }
+  }
+  duplicate
+}
+
+/// Panics with "duplicate key at index `index`", for [`const_assert_unique_keys!`].
+///
+/// Const panics can only show string slices, so the index is formatted by hand.
+///
+/// [`const_assert_unique_keys!`]: crate::const_assert_unique_keys
+#[doc(hidden)]
+pub const fn panic_duplicate_key(index: usize) -> ! {
+  const PREFIX: &[u8] = b"duplicate key at index ";
+  let mut message = [0; PREFIX.len() + 20];
+  let mut len = 0;
+  while len < PREFIX.len() {
+    message[len] = PREFIX[len];
+    len += 1;
+  }
+  // Write the digits backwards to the end of the buffer, then move them after the prefix.
+  let mut start = message.len();
+  let mut rest = index;
+  loop {
+    start -= 1;
+    message[start] = b"0123456789"[rest % 10];
+    rest /= 10;
+    if rest == 0 {
+      break;
+    }
+  }
+  while start < message.len() {
+    message[len] = message[start];
+    len += 1;
+    start += 1;
+  }
+  match core::str::from_utf8(message.split_at(len).0) {
+    Ok(message) => panic!("{}", message),
+    Err(_) => unreachable!(),
+  }
+}
+
+/// Returns the indices of the elements of `arr` in sorted order, equal elements ordered by index.
+const fn sorted_order<T, const N: usize>(arr: &[T; N]) -> [usize; N]
+where
+  T: ~const PartialOrd + Ord,
+{
+  let mut order = [0; N];
+  let mut i = 0;
+  while i < N {
+    order[i] = i;
+    i += 1;
+  }
+  const_quicksort(&mut order, const |a: &usize, b: &usize| {
+    let (a, b) = (*a, *b);
+    arr[a].lt(&arr[b]) || (!arr[b].lt(&arr[a]) && a < b)
+  });
+  order
+}
+
+/// Calls `f` with every pair of elements of `arr` that are adjacent in sorted order, without
+/// reordering `arr`.
+///
+/// The pairs are visited in ascending order, and the smaller element of each pair is passed
+/// first. Equal elements are visited in the order of their indices. This is the adjacent pair
+/// visitation of a sorted copy, e.g. for checking minimum distances between unsorted table
+/// entries. See [`const_fold_sorted_pairs`] for computing a value from the pairs.
+///
+/// # Current implementation
+///
+/// The indices of the elements are sorted with [`const_quicksort`], so `arr` is neither copied
+/// nor modified. This is *O*(*N* \* log(*N*)).
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_array::const_for_each_sorted_pair;
+///
+/// const fn check_distance(a: &u32, b: &u32) {
+///   assert!(*b - *a >= 10, "slots are less than 10 apart");
+/// }
+/// const SLOTS: [u32; 4] = [40, 10, 75, 20];
+/// const _: () = const_for_each_sorted_pair(&SLOTS, check_distance);
+/// ```
+pub const fn const_for_each_sorted_pair<T, F, const N: usize>(arr: &[T; N], mut f: F)
+where
+  T: ~const PartialOrd + Ord,
+  F: ~const FnMut(&T, &T) + ~const Destruct,
+{
+  let order = sorted_order(arr);
+  let mut i = 1;
+  while i < N {
+    f(&arr[order[i - 1]], &arr[order[i]]);
+    i += 1;
+  }
+}
+
+/// Folds every pair of elements of `arr` that are adjacent in sorted order into an accumulator,
+/// without reordering `arr`.
+///
+/// Starting with `init`, the accumulator is replaced by `f(accumulator, smaller, larger)` for
+/// every pair in the order of [`const_for_each_sorted_pair`], and the final accumulator is
+/// returned.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_array::const_fold_sorted_pairs;
+///
+/// const fn min_gap(min: u32, a: &u32, b: &u32) -> u32 {
+///   if *b - *a < min {
+///     *b - *a
+///   } else {
+///     min
+///   }
+/// }
+/// const SLOTS: [u32; 4] = [40, 10, 75, 20];
+/// const MIN_GAP: u32 = const_fold_sorted_pairs(&SLOTS, u32::MAX, min_gap);
+/// assert_eq!(MIN_GAP, 10);
+/// ```
+#[must_use]
+pub const fn const_fold_sorted_pairs<T, A, F, const N: usize>(arr: &[T; N], init: A, mut f: F) -> A
+where
+  T: ~const PartialOrd + Ord,
+  F: ~const FnMut(A, &T, &T) -> A + ~const Destruct,
+{
+  let order = sorted_order(arr);
+  let mut acc = init;
+  let mut i = 1;
+  while i < N {
+    acc = f(acc, &arr[order[i - 1]], &arr[order[i]]);
+    i += 1;
+  }
+  acc
+}
+
+/// Returns `arr` sorted in ascending order.
+///
+/// This is [`ConstSliceSortExt::const_sort_unstable`] as an expression, so a sorted array or
+/// values derived from it can be used where a block is inconvenient, e.g. in const generic
+/// arguments.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_array::const_sorted;
+///
+/// struct RingBuffer<const CAPACITY: usize>([u8; CAPACITY]);
+///
+/// const MESSAGE_SIZES: [usize; 4] = [24, 64, 8, 16];
+/// // Large enough for the largest message.
+/// type Buffer = RingBuffer<{ const_sorted(MESSAGE_SIZES)[3] }>;
+/// assert_eq!(core::mem::size_of::<Buffer>(), 64);
+/// ```
+#[must_use]
+pub const fn const_sorted<T, const N: usize>(mut arr: [T; N]) -> [T; N]
+where
+  T: ~const PartialOrd + Ord,
+{
+  arr.const_sort_unstable();
+  arr
+}
+
+/// Returns the first `M` elements of `arr` as an array.
+///
+/// # Panics
+///
+/// Panics if `M > N`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_array::const_prefix;
+///
+/// const P: [u8; 2] = const_prefix([4, 5, 6]);
+/// assert_eq!(P, [4, 5]);
+/// ```
+#[must_use]
+pub const fn const_prefix<T, const N: usize, const M: usize>(arr: [T; N]) -> [T; M]
+where
+  T: Copy,
+{
+  assert!(M <= N, "prefix is longer than the array");
+
+  let mut prefix = [MaybeUninit::<T>::uninit(); M];
+  let mut i = 0;
+  while i < M {
+    prefix[i].write(arr[i]);
+    i += 1;
+  }
+  // SAFETY: All `M` elements were initialized above and `[MaybeUninit<T>; M]` has the same
+  // layout as `[T; M]`.
+  unsafe { ptr::read(prefix.as_ptr().cast::<[T; M]>()) }
+}
+
+/// Pads the sorted array `arr` to the power of two length `M` by appending copies of `sentinel`.
+///
+/// The padded table stays sorted, so it can be searched with [`const_binary_search_padded`], whose
+/// loop has a fixed number of iterations that the compiler can turn into branchless code. Pass `N`
+/// as its `len` so the sentinels are never returned as matches.
+///
+/// This function is *O*(*M*).
+///
+/// # Panics
+///
+/// Panics if `M` is not a power of two, `M < N`, `arr` is not sorted or `sentinel` is less than
+/// the largest element of `arr`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_array::{const_binary_search_padded, const_pad_sorted_to_pow2};
+///
+/// const CODES: [u16; 5] = [100, 200, 204, 301, 404];
+/// const PADDED: [u16; 8] = const_pad_sorted_to_pow2(CODES, u16::MAX);
+/// assert_eq!(PADDED, [100, 200, 204, 301, 404, u16::MAX, u16::MAX, u16::MAX]);
+/// assert_eq!(const_binary_search_padded(&PADDED, CODES.len(), &301), Ok(3));
+/// assert_eq!(const_binary_search_padded(&PADDED, CODES.len(), &u16::MAX), Err(5));
+/// ```
+#[must_use]
+pub const fn const_pad_sorted_to_pow2<T, const N: usize, const M: usize>(
+  arr: [T; N],
+  sentinel: T,
+) -> [T; M]
+where
+  T: Copy + ~const PartialOrd + Ord,
+{
+  assert!(
+    M.is_power_of_two() && M >= N,
+    "padded length must be a power of two not less than the array length"
+  );
+  assert!(arr.const_is_sorted(), "array is not sorted");
+  assert!(
+    N == 0 || !sentinel.lt(&arr[N - 1]),
+    "sentinel is less than the largest element"
+  );
+
+  let mut padded = [sentinel; M];
+  let mut i = 0;
+  while i < N {
+    padded[i] = arr[i];
+    i += 1;
+  }
+  padded
+}
+
+/// Binary searches the first `len` elements of the sorted table `padded` for `x`.
+///
+/// `padded` must have a power of two length, e.g. a table built by [`const_pad_sorted_to_pow2`]
+/// with `len` being the length of the unpadded array. The search halves the table a fixed
+/// log2(`padded.len()`) times without an early exit, so every lookup takes the same number of
+/// steps. The elements from `len` on are only compared as the upper bound, never returned.
+///
+/// If `x` is found, [`Ok`] with the index of the first element equal to `x` is returned.
+/// Otherwise [`Err`] with the index where `x` could be inserted into the first `len` elements
+/// while keeping them sorted is returned.
+///
+/// # Errors
+///
+/// Returns the insertion index of `x` as [`Err`] if the first `len` elements do not contain `x`.
+///
+/// # Panics
+///
+/// Panics if the length of `padded` is not a power of two or `len > padded.len()`.
+pub const fn const_binary_search_padded<T>(padded: &[T], len: usize, x: &T) -> Result<usize, usize>
+where
+  T: ~const PartialOrd + Ord,
+{
+  assert!(
+    padded.len().is_power_of_two() && len <= padded.len(),
+    "padded table length must be a power of two not less than len"
+  );
+  // `padded[..base]` is less than `x` and `padded[base + step..]` is not.
+  let mut base = 0;
+  let mut step = padded.len() / 2;
+  while step > 0 {
+    base += usize::from(padded[base + step - 1].lt(x)) * step;
+    step /= 2;
+  }
+  base += usize::from(padded[base].lt(x));
+  if base >= len {
+    Err(len)
+  } else if x.lt(&padded[base]) {
+    Err(base)
+  } else {
+    Ok(base)
+  }
+}
+
+/// Applies the monotonic function `f` to every element of `sorted`, keeping the result sorted
+/// without sorting it again.
+///
+/// `f` must be monotonic, i.e. `a <= b` must imply `f(a) <= f(b)`, like scaling or offsetting
+/// breakpoints. The order of the result is asserted, which costs no more than the mapping itself.
+///
+/// This function is *O*(*N*).
+///
+/// # Panics
+///
+/// Panics if the mapped array is not sorted, i.e. if `f` is not monotonic.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_array::const_map_keys_monotonic;
+/// use const_sort::ConstSorted;
+///
+/// const BREAKPOINTS_MS: ConstSorted<u32, 3> = ConstSorted::new([250, 10, 100]);
+/// const fn to_us(ms: &u32) -> u64 {
+///   *ms as u64 * 1000
+/// }
+/// const BREAKPOINTS_US: ConstSorted<u64, 3> = const_map_keys_monotonic(&BREAKPOINTS_MS, to_us);
+/// assert_eq!(BREAKPOINTS_US.into_inner(), [10_000, 100_000, 250_000]);
+/// ```
+#[must_use]
+pub const fn const_map_keys_monotonic<T, U, F, const N: usize>(
+  sorted: &ConstSorted<T, N>,
+  mut f: F,
+) -> ConstSorted<U, N>
+where
+  U: ~const PartialOrd + Ord + Copy,
+  F: ~const FnMut(&T) -> U + ~const Destruct,
+{
+  let mut mapped = [MaybeUninit::<U>::uninit(); N];
+  let mut i = 0;
+  while i < N {
+    mapped[i].write(f(&sorted.as_slice()[i]));
+    i += 1;
+  }
+  // SAFETY: All `N` elements were initialized above and `[MaybeUninit<U>; N]` has the same
+  // layout as `[U; N]`.
+  let mapped = unsafe { ptr::read(mapped.as_ptr().cast::<[U; N]>()) };
+  assert!(mapped.const_is_sorted(), "f is not monotonic");
+  // SAFETY: `mapped` was checked to be sorted above.
+  unsafe { ConstSorted::from_sorted_unchecked(mapped) }
+}
+
+/// Defines a const array holding the first `count` elements of a const `(array, count)` pair.
+///
+/// The length of the defined array is exactly `count`. The expression is evaluated twice at
+/// compile time: once to compute the length and once to fill the array. Its elements must be
+/// [`Copy`].
+///
+/// Functions that filter or compact arrays in this crate, like
+/// [`const_sorted_dedup`](crate::const_array::const_sorted_dedup), return such a pair.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_shrink_to_count;
+///
+/// const fn evens<const N: usize>(v: [u32; N]) -> ([u32; N], usize) {
+///   let mut out = [0; N];
+///   let mut count = 0;
+///   let mut i = 0;
+///   while i < N {
+///     if v[i] % 2 == 0 {
+///       out[count] = v[i];
+///       count += 1;
+///     }
+///     i += 1;
+///   }
+///   (out, count)
+/// }
+///
+/// const_shrink_to_count! {
+///   const EVENS: [u32] = evens([1, 2, 3, 4, 6, 7]);
+/// }
+/// assert_eq!(EVENS, [2, 4, 6]);
+/// ```
+#[macro_export]
+macro_rules! const_shrink_to_count {
+  ($(#[$attr:meta])* $vis:vis const $name:ident: [$t:ty] = $arr_and_count:expr;) => {
+    $(#[$attr])*
+    $vis const $name: [$t; {
+      const fn count<const N: usize>(arr_and_count: ([$t; N], usize)) -> usize {
+        arr_and_count.1
+      }
+      count($arr_and_count)
+    }] = $crate::const_array::const_prefix($arr_and_count.0);
+  };
+}
+
+/// Defines a const array holding the elements of a sorted array expression without duplicates.
+///
+/// The length of the defined array is exactly the number of unique elements. Like
+/// [`const_shrink_to_count!`](crate::const_shrink_to_count), the expression is evaluated twice at
+/// compile time. It must be sorted, e.g. by a block calling
+/// [`ConstSliceSortExt::const_sort_unstable`], and its elements must be [`Copy`].
+///
+/// [`ConstSliceSortExt::const_sort_unstable`]: crate::ConstSliceSortExt::const_sort_unstable
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::{const_dedup_array, ConstSliceSortExt};
+///
+/// const_dedup_array! {
+///   const OPCODES: [u8] = {
+///     let mut v = [0x90, 0x0f, 0xc3, 0x0f, 0x90];
+///     v.const_sort_unstable();
+///     v
+///   };
+/// }
+/// assert_eq!(OPCODES, [0x0f, 0x90, 0xc3]);
+/// ```
+#[macro_export]
+macro_rules! const_dedup_array {
+  ($(#[$attr:meta])* $vis:vis const $name:ident: [$t:ty] = $arr:expr;) => {
+    $crate::const_shrink_to_count! {
+      $(#[$attr])*
+      $vis const $name: [$t] = $crate::const_array::const_sorted_dedup($arr);
+    }
+  };
+}
+
+/// Fails to compile if two items of a const array have the same key.
+///
+/// `const_assert_unique_keys!(ITEMS, |item| key)` evaluates `key` for every item of the const
+/// array `ITEMS` at compile time and checks that all keys are unique with
+/// [`const_find_duplicate_key`](crate::const_array::const_find_duplicate_key). This guards
+/// registries, e.g. tables of handlers with numeric ids, against accidentally reused keys. The
+/// keys must be [`Copy`] and implement `~const PartialOrd + Ord`.
+///
+/// If a key is repeated, compilation fails with the error "duplicate key at index N", where N is
+/// the index of the first item repeating the key of an earlier item.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_assert_unique_keys;
+///
+/// const HANDLERS: [(u16, &str); 3] = [(7, "read"), (2, "write"), (9, "seek")];
+/// const_assert_unique_keys!(HANDLERS, |handler| handler.0);
+/// ```
+///
+/// ```compile_fail
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_assert_unique_keys;
+///
+/// const HANDLERS: [(u16, &str); 3] = [(7, "read"), (2, "write"), (7, "seek")];
+/// const_assert_unique_keys!(HANDLERS, |handler| handler.0);
+/// ```
+#[macro_export]
+#[allow_internal_unstable(const_closures)]
+macro_rules! const_assert_unique_keys {
+  ($items:expr, |$item:ident| $key:expr $(,)?) => {
+    const _: () = {
+      if let Some((_, duplicate)) =
+        $crate::const_array::const_find_duplicate_key(&$items, const |$item: &_| $key)
+      {
+        $crate::const_array::panic_duplicate_key(duplicate)
+      }
+    };
+  };
+}
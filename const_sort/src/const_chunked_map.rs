@@ -0,0 +1,292 @@
+use core::mem::MaybeUninit;
+use core::{ptr, slice};
+
+use crate::const_sort::{const_kmerge_tree, const_sort_by_external_key};
+use crate::ConstSliceSortExt;
+
+/// A sorted map stored as `M` sorted chunks of `C` keys and values.
+///
+/// The first key of every chunk is kept in a sorted chunk index, so a lookup binary searches the
+/// chunk index for the chunk that may contain the key and then binary searches that chunk. This
+/// is *O*(log(*M*) + log(*C*)).
+///
+/// Sorting a very large table in a single const item can exceed the const evaluation limits.
+/// Maps of any size can instead be assembled with [`ConstChunkedMapBuilder`], which sorts every
+/// chunk in the const item that pushes it.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::ConstChunkedMap;
+///
+/// const PORTS: ConstChunkedMap<u16, &str, 3, 2> = ConstChunkedMap::from_sorted_chunks(
+///   [[22, 25, 53], [80, 443, 8080]],
+///   [["ssh", "smtp", "dns"], ["http", "https", "http-alt"]],
+/// );
+/// const HTTPS: Option<&&str> = PORTS.get(&443);
+/// assert_eq!(HTTPS, Some(&"https"));
+/// assert_eq!(PORTS.get(&21), None);
+/// assert_eq!(PORTS.chunk_index(), &[22, 80]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConstChunkedMap<K, V, const C: usize, const M: usize> {
+  chunk_index: [K; M],
+  keys: [[K; C]; M],
+  values: [[V; C]; M],
+}
+
+impl<K, V, const C: usize, const M: usize> ConstChunkedMap<K, V, C, M> {
+  /// Wraps the chunks of `keys` and the values at the same positions of `values`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `C` is 0 or the keys of all chunks taken one after another are not sorted in
+  /// strictly ascending order.
+  #[must_use]
+  pub const fn from_sorted_chunks(keys: [[K; C]; M], values: [[V; C]; M]) -> Self
+  where
+    K: Copy + ~const PartialOrd + Ord,
+  {
+    assert!(C != 0, "chunk length must not be 0");
+    let mut chunk_index = [MaybeUninit::<K>::uninit(); M];
+    let mut chunk = 0;
+    while chunk < M {
+      let mut i = 0;
+      while i < C {
+        let sorted = if i == 0 {
+          chunk == 0 || keys[chunk - 1][C - 1].lt(&keys[chunk][0])
+        } else {
+          keys[chunk][i - 1].lt(&keys[chunk][i])
+        };
+        assert!(sorted, "chunked map keys are not sorted and unique");
+        i += 1;
+      }
+      chunk_index[chunk].write(keys[chunk][0]);
+      chunk += 1;
+    }
+    Self {
+      // SAFETY: All `M` elements were initialized above and `[MaybeUninit<K>; M]` has the same
+      // layout as `[K; M]`.
+      chunk_index: unsafe { ptr::read(chunk_index.as_ptr().cast::<[K; M]>()) },
+      keys,
+      values,
+    }
+  }
+
+  /// Returns the value of `key`, or `None` if it is not a key of the map.
+  #[must_use]
+  pub const fn get(&self, key: &K) -> Option<&V>
+  where
+    K: ~const PartialOrd + Ord,
+  {
+    // The key can only be in the last chunk whose first key is not greater than it.
+    let chunk = self.chunk_index.const_insertion_index_last(key);
+    if chunk == 0 {
+      return None;
+    }
+    let keys = &self.keys[chunk - 1];
+    let index = keys.const_insertion_index(key);
+    if index < C && !key.lt(&keys[index]) {
+      Some(&self.values[chunk - 1][index])
+    } else {
+      None
+    }
+  }
+
+  /// Returns `true` if `key` is a key of the map.
+  #[must_use]
+  pub const fn contains_key(&self, key: &K) -> bool
+  where
+    K: ~const PartialOrd + Ord,
+  {
+    self.get(key).is_some()
+  }
+
+  /// Returns the first key of every chunk.
+  #[must_use]
+  pub const fn chunk_index(&self) -> &[K; M] {
+    &self.chunk_index
+  }
+
+  /// Returns the sorted keys and the values of chunk `chunk`, or `None` if `chunk >= M`.
+  #[must_use]
+  pub const fn chunk(&self, chunk: usize) -> Option<(&[K; C], &[V; C])> {
+    if chunk < M {
+      Some((&self.keys[chunk], &self.values[chunk]))
+    } else {
+      None
+    }
+  }
+
+  /// Returns the number of keys, i.e. `C * M`.
+  #[must_use]
+  pub const fn len(&self) -> usize {
+    C * M
+  }
+
+  /// Returns `true` if the map has no keys.
+  #[must_use]
+  pub const fn is_empty(&self) -> bool {
+    C * M == 0
+  }
+}
+
+/// Builds a [`ConstChunkedMap`] from `M` chunks of unsorted keys and values.
+///
+/// Every chunk is sorted by [`push_chunk`](Self::push_chunk), so the pushes of a large map can
+/// be spread over several const items that each stay within the const evaluation limits.
+/// [`build`](Self::build) then merges the sorted chunks with [`const_kmerge_tree`], which is
+/// *O*(*C* \* *M* \* log(*M*)).
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::{ConstChunkedMap, ConstChunkedMapBuilder};
+///
+/// type Builder = ConstChunkedMapBuilder<u16, &'static str, 3, 2>;
+///
+/// const FIRST: Builder = Builder::new().push_chunk([443, 22, 8080], ["https", "ssh", "http-alt"]);
+/// const BOTH: Builder = FIRST.push_chunk([80, 53, 25], ["http", "dns", "smtp"]);
+/// const PORTS: ConstChunkedMap<u16, &str, 3, 2> = BOTH.build();
+///
+/// assert_eq!(PORTS.get(&80), Some(&"http"));
+/// assert_eq!(PORTS.chunk_index(), &[22, 80]);
+/// ```
+// The module is private, the builder is only named at the crate root next to `ConstChunkedMap`.
+#[allow(clippy::module_name_repetitions)]
+pub struct ConstChunkedMapBuilder<K, V, const C: usize, const M: usize> {
+  keys: [[MaybeUninit<K>; C]; M],
+  values: [[MaybeUninit<V>; C]; M],
+  // The chunks before this one are initialized and sorted.
+  pushed: usize,
+}
+
+impl<K, V, const C: usize, const M: usize> ConstChunkedMapBuilder<K, V, C, M>
+where
+  K: Copy,
+  V: Copy,
+{
+  /// Creates a builder without any chunks.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `C` is 0.
+  #[must_use]
+  pub const fn new() -> Self {
+    assert!(C != 0, "chunk length must not be 0");
+    Self {
+      keys: [[MaybeUninit::uninit(); C]; M],
+      values: [[MaybeUninit::uninit(); C]; M],
+      pushed: 0,
+    }
+  }
+
+  /// Sorts `keys` together with the values at the same positions of `values` and adds them as
+  /// the next chunk.
+  ///
+  /// # Panics
+  ///
+  /// Panics if all `M` chunks were already pushed.
+  #[must_use]
+  pub const fn push_chunk(mut self, mut keys: [K; C], mut values: [V; C]) -> Self
+  where
+    K: ~const PartialOrd + Ord,
+  {
+    assert!(self.pushed < M, "all chunks were already pushed");
+    const_sort_by_external_key(&mut values, &mut keys);
+    let mut i = 0;
+    while i < C {
+      self.keys[self.pushed][i].write(keys[i]);
+      self.values[self.pushed][i].write(values[i]);
+      i += 1;
+    }
+    self.pushed += 1;
+    self
+  }
+
+  /// Returns the number of chunks pushed so far.
+  #[must_use]
+  pub const fn pushed(&self) -> usize {
+    self.pushed
+  }
+
+  /// Merges the sorted chunks into a [`ConstChunkedMap`].
+  ///
+  /// # Panics
+  ///
+  /// Panics if fewer than `M` chunks were pushed or a key was pushed more than once.
+  #[must_use]
+  pub const fn build(self) -> ConstChunkedMap<K, V, C, M>
+  where
+    K: ~const PartialOrd + Ord,
+  {
+    assert!(self.pushed == M, "not all chunks were pushed");
+    // Every chunk is a shard of the merge, so its keys travel together with their values.
+    let mut pairs = [[MaybeUninit::<(K, V)>::uninit(); C]; M];
+    let mut chunk = 0;
+    while chunk < M {
+      let mut i = 0;
+      while i < C {
+        // SAFETY: All `M` chunks were initialized by `push_chunk`.
+        let pair = unsafe {
+          (
+            self.keys[chunk][i].assume_init(),
+            self.values[chunk][i].assume_init(),
+          )
+        };
+        pairs[chunk][i].write(pair);
+        i += 1;
+      }
+      chunk += 1;
+    }
+    // SAFETY: All `C * M` pairs were initialized above and `[[MaybeUninit<(K, V)>; C]; M]` has the
+    // same layout as `[[(K, V); C]; M]`.
+    let pairs = unsafe { ptr::read(pairs.as_ptr().cast::<[[(K, V); C]; M]>()) };
+    let mut shards: [&[(K, V)]; M] = [&[]; M];
+    let mut chunk = 0;
+    while chunk < M {
+      shards[chunk] = &pairs[chunk];
+      chunk += 1;
+    }
+
+    // The merge overwrites every pair, so it can start from a copy of the unmerged pairs.
+    let mut merged = pairs;
+    // SAFETY: `merged` holds `M` arrays of `C` pairs next to each other, i.e. `C * M` pairs.
+    let out = unsafe { slice::from_raw_parts_mut(merged.as_mut_ptr().cast::<(K, V)>(), C * M) };
+    const_kmerge_tree(&shards, out, const |a: &(K, V), b: &(K, V)| a.0.lt(&b.0));
+
+    let mut merged_keys = [[MaybeUninit::<K>::uninit(); C]; M];
+    let mut merged_values = [[MaybeUninit::<V>::uninit(); C]; M];
+    let mut chunk = 0;
+    while chunk < M {
+      let mut i = 0;
+      while i < C {
+        merged_keys[chunk][i].write(merged[chunk][i].0);
+        merged_values[chunk][i].write(merged[chunk][i].1);
+        i += 1;
+      }
+      chunk += 1;
+    }
+
+    ConstChunkedMap::from_sorted_chunks(
+      // SAFETY: All `C * M` elements were initialized above.
+      unsafe { ptr::read(merged_keys.as_ptr().cast::<[[K; C]; M]>()) },
+      // SAFETY: As above.
+      unsafe { ptr::read(merged_values.as_ptr().cast::<[[V; C]; M]>()) },
+    )
+  }
+}
+
+impl<K, V, const C: usize, const M: usize> const Default for ConstChunkedMapBuilder<K, V, C, M>
+where
+  K: Copy,
+  V: Copy,
+{
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -0,0 +1,77 @@
+//! Closure adapters for stateful comparators and key functions on nightlies without working
+//! `const_closures`.
+//!
+//! [`ConstFnMutClosure`] mirrors the adapter of the same name in the
+//! [`const_closure`](https://crates.io/crates/const_closure) crate, so code written against that
+//! crate only has to change its imports. The adapters of the crate itself implement
+//! `~const FnMut` as well and are accepted by every `*_by` and `*_by_key` function, too.
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(const_mut_refs)]
+//! #![feature(const_trait_impl)]
+//! #![feature(const_cmp)]
+//! use const_sort::const_closure::ConstFnMutClosure;
+//! use const_sort::ConstSliceSortExt;
+//!
+//! const COMPARISONS: usize = {
+//!   const fn cmp(count: &mut usize, (a, b): (&u8, &u8)) -> core::cmp::Ordering {
+//!     *count += 1;
+//!     a.cmp(b)
+//!   }
+//!   let mut count = 0;
+//!   let mut v = [5u8, 4, 1, 3, 2];
+//!   v.const_sort_unstable_by(ConstFnMutClosure::new(&mut count, cmp));
+//!   count
+//! };
+//! assert!(COMPARISONS >= 4);
+//! ```
+
+use core::marker::{Destruct, Tuple};
+
+/// A `~const FnMut` closure made of a mutable reference to some state and a `const fn` receiving
+/// it together with the arguments of the call as a tuple.
+pub struct ConstFnMutClosure<CapturedData, Function> {
+  /// The state passed to every call of `func`.
+  pub data: CapturedData,
+  /// The function called with `data` and the arguments of the call.
+  pub func: Function,
+}
+
+impl<'a, CapturedData: ?Sized, Function> ConstFnMutClosure<&'a mut CapturedData, Function> {
+  /// Creates a closure calling `func` with `data` and the arguments of the call.
+  pub const fn new<ClosureArguments, ClosureReturnValue>(
+    data: &'a mut CapturedData,
+    func: Function,
+  ) -> Self
+  where
+    Function: ~const Fn(&mut CapturedData, ClosureArguments) -> ClosureReturnValue,
+  {
+    Self { data, func }
+  }
+}
+
+impl<'a, CapturedData: ?Sized, ClosureArguments: Tuple, Function, ClosureReturnValue> const
+  FnOnce<ClosureArguments> for ConstFnMutClosure<&'a mut CapturedData, Function>
+where
+  Function:
+    ~const Fn(&mut CapturedData, ClosureArguments) -> ClosureReturnValue + ~const Destruct,
+{
+  type Output = ClosureReturnValue;
+
+  extern "rust-call" fn call_once(mut self, args: ClosureArguments) -> Self::Output {
+    self.call_mut(args)
+  }
+}
+
+impl<'a, CapturedData: ?Sized, ClosureArguments: Tuple, Function, ClosureReturnValue> const
+  FnMut<ClosureArguments> for ConstFnMutClosure<&'a mut CapturedData, Function>
+where
+  Function:
+    ~const Fn(&mut CapturedData, ClosureArguments) -> ClosureReturnValue + ~const Destruct,
+{
+  extern "rust-call" fn call_mut(&mut self, args: ClosureArguments) -> Self::Output {
+    (self.func)(self.data, args)
+  }
+}
@@ -0,0 +1,160 @@
+use crate::const_sort::const_quicksort;
+use crate::ConstSliceSortExt;
+
+/// Defines a table type with a sorted key column and the given value columns.
+macro_rules! column_tables {
+  ($(
+    $(#[$attr:meta])*
+    $name:ident { $($column:ident: $v:ident),+ }
+  )*) => {$(
+    $(#[$attr])*
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct $name<K, const N: usize, $($v),+> {
+      keys: [K; N],
+      $($column: [$v; N],)+
+    }
+
+    impl<K, const N: usize, $($v),+> $name<K, N, $($v),+> {
+      /// Sorts the rows by their keys, keeping every value in the row of its key.
+      ///
+      /// The value at index `i` of every column belongs to `keys[i]`. This is *O*(*N* \*
+      /// log(*N*)).
+      ///
+      /// # Panics
+      ///
+      /// Panics if a key occurs more than once.
+      #[must_use]
+      pub const fn new(mut keys: [K; N], $(mut $column: [$v; N]),+) -> Self
+      where
+        K: ~const PartialOrd + Ord,
+      {
+        let mut order = [0; N];
+        let mut i = 0;
+        while i < N {
+          order[i] = i;
+          i += 1;
+        }
+        const_quicksort(&mut order, const |a: &usize, b: &usize| keys[*a].lt(&keys[*b]));
+        keys.const_apply_permutation(&order);
+        $($column.const_apply_permutation(&order);)+
+
+        let mut i = 1;
+        while i < N {
+          assert!(keys[i - 1].lt(&keys[i]), "table keys are not unique");
+          i += 1;
+        }
+        Self { keys, $($column,)+ }
+      }
+
+      /// Returns the values of the row of `key`, or `None` if no row has the key.
+      ///
+      /// This is a binary search of the key column and therefore *O*(log(*N*)).
+      #[must_use]
+      pub const fn get(&self, key: &K) -> Option<($(&$v,)+)>
+      where
+        K: ~const PartialOrd + Ord,
+      {
+        match self.position(key) {
+          Some(row) => Some(($(&self.$column[row],)+)),
+          None => None,
+        }
+      }
+
+      /// Returns the index of the row of `key`, or `None` if no row has the key.
+      #[must_use]
+      pub const fn position(&self, key: &K) -> Option<usize>
+      where
+        K: ~const PartialOrd + Ord,
+      {
+        match self.keys.const_binary_search(key) {
+          Ok(row) => Some(row),
+          Err(_) => None,
+        }
+      }
+
+      /// Returns the key column, sorted in strictly ascending order.
+      #[must_use]
+      pub const fn keys(&self) -> &[K; N] {
+        &self.keys
+      }
+
+      $(
+        #[doc = concat!("Returns the value column `", stringify!($column), "` in key order.")]
+        #[must_use]
+        pub const fn $column(&self) -> &[$v; N] {
+          &self.$column
+        }
+      )+
+
+      /// Returns the number of rows, i.e. `N`.
+      #[must_use]
+      pub const fn len(&self) -> usize {
+        N
+      }
+
+      /// Returns `true` if the table has no rows.
+      #[must_use]
+      pub const fn is_empty(&self) -> bool {
+        N == 0
+      }
+    }
+  )*};
+}
+
+column_tables! {
+  /// A table with a sorted key column and one value column.
+  ///
+  /// See [`ConstTable2`] for tables with more columns.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstTable1;
+  ///
+  /// // Byte sizes of the unsigned integer types, keyed by their bit width.
+  /// const SIZES: ConstTable1<u32, 3, usize> = ConstTable1::new([64, 8, 16], [8, 1, 2]);
+  /// const U16: Option<(&usize,)> = SIZES.get(&16);
+  /// assert_eq!(U16, Some((&2,)));
+  /// assert_eq!(SIZES.column1(), &[1, 2, 8]);
+  /// ```
+  ConstTable1 { column1: V1 }
+
+  /// A table with a sorted key column and two value columns.
+  ///
+  /// The columns are stored as separate arrays, i.e. as a struct of arrays, and
+  /// [`new`](Self::new) sorts them together by key. Lookups binary search the key column and
+  /// return the values of the matching row, so the index correspondence between the columns never
+  /// has to be managed by hand. [`ConstTable1`], [`ConstTable3`] and [`ConstTable4`] have one,
+  /// three and four value columns.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstTable2;
+  ///
+  /// const STATUS: ConstTable2<u16, 4, &str, bool> = ConstTable2::new(
+  ///   [404, 200, 503, 301],
+  ///   ["Not Found", "OK", "Service Unavailable", "Moved Permanently"],
+  ///   [false, true, false, true],
+  /// );
+  /// const NOT_FOUND: Option<(&&str, &bool)> = STATUS.get(&404);
+  /// assert_eq!(NOT_FOUND, Some((&"Not Found", &false)));
+  /// assert_eq!(STATUS.get(&418), None);
+  /// assert_eq!(STATUS.keys(), &[200, 301, 404, 503]);
+  /// ```
+  ConstTable2 { column1: V1, column2: V2 }
+
+  /// A table with a sorted key column and three value columns.
+  ///
+  /// See [`ConstTable2`].
+  ConstTable3 { column1: V1, column2: V2, column3: V3 }
+
+  /// A table with a sorted key column and four value columns.
+  ///
+  /// See [`ConstTable2`].
+  ConstTable4 { column1: V1, column2: V2, column3: V3, column4: V4 }
+}
@@ -0,0 +1,91 @@
+use crate::ConstSliceSortExt;
+
+/// Maps sparse keys to dense indices by binary searching a sorted array of unique keys.
+///
+/// The dense index of a key is its position in the key array, so a table of `N` values can be
+/// indexed by keys spread over a much larger range, e.g. sparse ids or Unicode code points.
+/// [`dense_index`](Self::dense_index) maps a key to its index and [`key`](Self::key) maps it back.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::ConstIndexMap;
+///
+/// const IDS: ConstIndexMap<u32, 4> = ConstIndexMap::new([3, 80, 443, 8080]);
+/// const NAMES: [&str; 4] = ["dtp", "http", "https", "http-alt"];
+///
+/// const HTTPS: Option<usize> = IDS.dense_index(&443);
+/// assert_eq!(NAMES[HTTPS.unwrap()], "https");
+/// assert_eq!(IDS.dense_index(&22), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConstIndexMap<T, const N: usize>([T; N]);
+
+impl<T, const N: usize> ConstIndexMap<T, N> {
+  /// Wraps the sorted, unique `keys`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `keys` are not sorted in strictly ascending order.
+  #[must_use]
+  pub const fn new(keys: [T; N]) -> Self
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    let mut i = 1;
+    while i < N {
+      assert!(
+        keys[i - 1].lt(&keys[i]),
+        "index map keys are not sorted and unique"
+      );
+      i += 1;
+    }
+    Self(keys)
+  }
+
+  /// Returns the dense index of `key`, or `None` if it is not a key of the map.
+  ///
+  /// This is a binary search and therefore *O*(log(*N*)).
+  #[must_use]
+  pub const fn dense_index(&self, key: &T) -> Option<usize>
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    let index = self.0.const_insertion_index(key);
+    if index < N && !key.lt(&self.0[index]) {
+      Some(index)
+    } else {
+      None
+    }
+  }
+
+  /// Returns the key with the dense index `index`, or `None` if `index >= N`.
+  #[must_use]
+  pub const fn key(&self, index: usize) -> Option<&T> {
+    if index < N {
+      Some(&self.0[index])
+    } else {
+      None
+    }
+  }
+
+  /// Returns the number of keys.
+  #[must_use]
+  pub const fn len(&self) -> usize {
+    N
+  }
+
+  /// Returns `true` if the map has no keys.
+  #[must_use]
+  pub const fn is_empty(&self) -> bool {
+    N == 0
+  }
+
+  /// Returns the sorted keys.
+  #[must_use]
+  pub const fn keys(&self) -> &[T; N] {
+    &self.0
+  }
+}
@@ -0,0 +1,98 @@
+#[const_trait]
+/// Trait for searching sorted slices of unsigned integers in const items.
+pub trait ConstInterpolationSearchExt<T> {
+  /// Interpolation searches this sorted slice for a given element.
+  ///
+  /// Behaves like [`binary_search`]: if the value is found then [`Ok`] is returned, containing
+  /// the index of the matching element. If there are multiple matches, then any one of the
+  /// matches could be returned. If the value is not found then [`Err`] is returned, containing
+  /// the index where a matching element could be inserted while maintaining sorted order.
+  ///
+  /// [`binary_search`]: slice::binary_search
+  ///
+  /// # Errors
+  ///
+  /// Returns the insertion index of `x` as [`Err`] if the slice does not contain `x`.
+  ///
+  /// # Current implementation
+  ///
+  /// Every probe estimates the position of `x` by linear interpolation between the first and the
+  /// last element of the remaining range. On uniformly distributed keys this finds `x` in
+  /// *O*(log(log(*n*))) probes, which often beats a binary search at runtime. After 8 probes the
+  /// remaining range is binary searched, so skewed keys cost at most *O*(log(*n*)).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstInterpolationSearchExt;
+  ///
+  /// const S: [u32; 6] = [10, 20, 30, 40, 50, 60];
+  /// const A: Result<usize, usize> = S.const_interpolation_search(&40);
+  /// assert_eq!(A, Ok(3));
+  /// const B: Result<usize, usize> = S.const_interpolation_search(&45);
+  /// assert_eq!(B, Err(4));
+  /// ```
+  fn const_interpolation_search(&self, x: &T) -> Result<usize, usize>;
+}
+
+/// Maximum number of interpolation probes before falling back to a binary search.
+const MAX_PROBES: usize = 8;
+
+macro_rules! impl_interpolation_search {
+  ($($t:ty),*) => {$(
+    impl const ConstInterpolationSearchExt<$t> for [$t] {
+      fn const_interpolation_search(&self, x: &$t) -> Result<usize, usize> {
+        let x = *x;
+        let mut low = 0;
+        let mut high = self.len();
+
+        let mut probes = 0;
+        while probes < MAX_PROBES && low < high {
+          let (first, last) = (self[low], self[high - 1]);
+          if x < first {
+            return Err(low);
+          }
+          if x > last {
+            return Err(high);
+          }
+          if first == last {
+            // `first <= x <= last`, so every element of the range equals `x`.
+            return Ok(low);
+          }
+
+          // `x - first <= last - first`, so the offset is at most `high - 1 - low`.
+          #[allow(clippy::cast_possible_truncation)]
+          let offset = (u128::from(x - first) * (high - 1 - low) as u128
+            / u128::from(last - first)) as usize;
+          let probe = low + offset;
+          if self[probe] == x {
+            return Ok(probe);
+          }
+          if self[probe] < x {
+            low = probe + 1;
+          } else {
+            high = probe;
+          }
+          probes += 1;
+        }
+
+        while low < high {
+          let mid = low + (high - low) / 2;
+          if self[mid] == x {
+            return Ok(mid);
+          }
+          if self[mid] < x {
+            low = mid + 1;
+          } else {
+            high = mid;
+          }
+        }
+        Err(low)
+      }
+    }
+  )*};
+}
+
+impl_interpolation_search!(u32, u64);
@@ -0,0 +1,50 @@
+#[const_trait]
+/// Trait for types that are ordered by a key, e.g. newtypes around integer indices.
+///
+/// Implement it once for a newtype instead of passing the same key function to every `*_by_key`
+/// call. The functions of [`ConstSliceSortExt`](crate::ConstSliceSortExt) ending in
+/// `_by_const_key` then sort and search slices of the type by its key, and searches take a plain
+/// key instead of a newtype instance.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::{ConstKey, ConstSliceSortExt};
+///
+/// #[derive(Clone, Copy)]
+/// struct Port {
+///   number: u16,
+///   name: &'static str,
+/// }
+///
+/// impl const ConstKey for Port {
+///   type Key = u16;
+///
+///   fn as_ord_key(&self) -> u16 {
+///     self.number
+///   }
+/// }
+///
+/// const PORTS: [Port; 3] = {
+///   let mut ports = [
+///     Port { number: 443, name: "https" },
+///     Port { number: 22, name: "ssh" },
+///     Port { number: 80, name: "http" },
+///   ];
+///   ports.const_sort_unstable_by_const_key();
+///   ports
+/// };
+/// const HTTP: Result<usize, usize> = PORTS.const_search_by_const_key(&80);
+/// assert_eq!(PORTS[HTTP.unwrap()].name, "http");
+/// ```
+pub trait ConstKey {
+  /// The key the type is ordered by.
+  type Key;
+
+  /// Returns the key of `self`.
+  ///
+  /// Two values must compare like their keys.
+  fn as_ord_key(&self) -> Self::Key;
+}
@@ -0,0 +1,159 @@
+#[const_trait]
+/// Trait for validating slices of indices that describe permutations in const items.
+pub trait ConstPermutationExt {
+  /// Checks if the slice contains every index in `0..len()` exactly once.
+  ///
+  /// This is the invariant of index and permutation tables, e.g. de Bruijn bit-trick lookup
+  /// tables or the output of an argsort. If the slice is empty, `true` is returned.
+  ///
+  /// # Current implementation
+  ///
+  /// The slice is scanned once per 4096 possible indices while tracking the indices seen so far
+  /// in a fixed-size bitmap. It does not allocate and is *O*(*n*) for slices of up to 4096
+  /// elements and *O*(*n*^2 / 4096) worst-case for longer ones.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstPermutationExt;
+  ///
+  /// const A: bool = [3usize, 0, 2, 1].const_is_permutation_of_0_to_n();
+  /// assert!(A);
+  /// const B: bool = [0usize, 1, 1, 3].const_is_permutation_of_0_to_n();
+  /// assert!(!B);
+  /// const C: bool = [0usize, 1, 4].const_is_permutation_of_0_to_n();
+  /// assert!(!C);
+  /// ```
+  #[must_use]
+  fn const_is_permutation_of_0_to_n(&self) -> bool;
+
+  /// Decomposes the permutation into its cycles.
+  ///
+  /// The cycle containing index `i` is `i, self[i], self[self[i]], ...` until it returns to `i`.
+  /// The cycles are written to `out` one after another, ordered by their smallest index, and
+  /// every cycle starts with its smallest index, its cycle leader. The length of every cycle is
+  /// written to the same index of `cycle_lens`. Returns the number of cycles.
+  ///
+  /// Applying a cycle of length *k* takes *k* - 1 swaps, so this also describes a minimal swap
+  /// program for the permutation. Fixed points are cycles of length 1.
+  ///
+  /// # Current implementation
+  ///
+  /// Like [`const_is_permutation_of_0_to_n`](#tymethod.const_is_permutation_of_0_to_n), the visited
+  /// indices are tracked in a fixed-size bitmap covering 4096 indices at a time. It does not
+  /// allocate and is *O*(*n*) for slices of up to 4096 elements and *O*(*n*^2 / 4096) worst-case
+  /// for longer ones.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the slice is not a permutation of `0..len()`, if `out` is shorter than the slice,
+  /// or if `cycle_lens` is shorter than the number of cycles.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstPermutationExt;
+  ///
+  /// const CYCLES: ([usize; 5], [usize; 5], usize) = {
+  ///   let mut out = [0; 5];
+  ///   let mut cycle_lens = [0; 5];
+  ///   let n = [2usize, 0, 1, 4, 3].const_permutation_cycles(&mut out, &mut cycle_lens);
+  ///   (out, cycle_lens, n)
+  /// };
+  /// assert_eq!(CYCLES, ([0, 2, 1, 3, 4], [3, 2, 0, 0, 0], 2));
+  /// ```
+  fn const_permutation_cycles(&self, out: &mut [usize], cycle_lens: &mut [usize]) -> usize;
+}
+
+// Number of indices tracked by the bitmap during one scan of the slice.
+const WINDOW: usize = 4096;
+const WORD_BITS: usize = 64;
+
+impl const ConstPermutationExt for [usize] {
+  fn const_is_permutation_of_0_to_n(&self) -> bool {
+    let len = self.len();
+    let mut base = 0;
+    while base < len {
+      let mut seen = [0u64; WINDOW / WORD_BITS];
+      let mut i = 0;
+      while i < len {
+        let index = self[i];
+        if index >= len {
+          return false;
+        }
+        if index >= base && index - base < WINDOW {
+          let bit = index - base;
+          let mask = 1 << (bit % WORD_BITS);
+          if seen[bit / WORD_BITS] & mask != 0 {
+            // Every index is in range, so a duplicate means some other index is missing.
+            return false;
+          }
+          seen[bit / WORD_BITS] |= mask;
+        }
+        i += 1;
+      }
+      base += WINDOW;
+    }
+    true
+  }
+  fn const_permutation_cycles(&self, out: &mut [usize], cycle_lens: &mut [usize]) -> usize {
+    assert!(
+      self.const_is_permutation_of_0_to_n(),
+      "slice is not a permutation of 0..len"
+    );
+    let len = self.len();
+    assert!(out.len() >= len, "cycle output buffers are too short");
+
+    // `out[..written]` holds all cycles whose leader is smaller than the current index.
+    let mut written = 0;
+    let mut cycles = 0;
+    let mut base = 0;
+    while base < len {
+      // Mark the indices of this window that belong to cycles with a leader in earlier windows.
+      let mut seen = [0u64; WINDOW / WORD_BITS];
+      let mut i = 0;
+      while i < written {
+        let index = out[i];
+        if index >= base && index - base < WINDOW {
+          let bit = index - base;
+          seen[bit / WORD_BITS] |= 1 << (bit % WORD_BITS);
+        }
+        i += 1;
+      }
+
+      let mut leader = base;
+      while leader < len && leader - base < WINDOW {
+        let bit = leader - base;
+        if seen[bit / WORD_BITS] & (1 << (bit % WORD_BITS)) == 0 {
+          assert!(
+            cycles < cycle_lens.len(),
+            "cycle output buffers are too short"
+          );
+          let start = written;
+          let mut index = leader;
+          loop {
+            out[written] = index;
+            written += 1;
+            if index >= base && index - base < WINDOW {
+              let bit = index - base;
+              seen[bit / WORD_BITS] |= 1 << (bit % WORD_BITS);
+            }
+            index = self[index];
+            if index == leader {
+              break;
+            }
+          }
+          cycle_lens[cycles] = written - start;
+          cycles += 1;
+        }
+        leader += 1;
+      }
+      base += WINDOW;
+    }
+    cycles
+  }
+}
@@ -1,6 +1,99 @@
-use core::{cmp::Ordering, marker::Destruct};
+use core::{cmp::Ordering, marker::Destruct, mem::MaybeUninit, ops::Sub};
 
-use crate::const_sort;
+use crate::{const_sort, ConstKey, ConstPermutationExt};
+
+/// Order statistics of a slice as returned by [`ConstSliceSortExt::const_order_summary`].
+///
+/// The quartiles use the lower nearest rank, i.e. `q1`, `median` and `q3` are the elements at the
+/// indices `(len - 1) / 4`, `(len - 1) / 2` and `3 * (len - 1) / 4` of the sorted slice.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OrderSummary<T> {
+  /// The smallest element.
+  pub min: T,
+  /// The first quartile.
+  pub q1: T,
+  /// The median.
+  pub median: T,
+  /// The third quartile.
+  pub q3: T,
+  /// The largest element.
+  pub max: T,
+}
+
+/// Panics if `perm` is not a permutation of `0..len`.
+const fn assert_permutation(len: usize, perm: &[usize]) {
+  assert!(
+    perm.len() == len && perm.const_is_permutation_of_0_to_n(),
+    "perm is not a permutation of 0..len"
+  );
+}
+
+/// Checks if `leader` is the smallest index of its cycle in `perm`.
+const fn is_cycle_leader(perm: &[usize], leader: usize) -> bool {
+  let mut i = perm[leader];
+  while i != leader {
+    if i < leader {
+      return false;
+    }
+    i = perm[i];
+  }
+  true
+}
+
+/// Returns whether `is_le` holds for every pair of adjacent elements of `v`.
+///
+/// The pairs are checked in unrolled chunks of four, so a long sorted slice costs a quarter of the
+/// loop iterations. The scan stops at the first unsorted pair, so `is_le` is called exactly as
+/// often as by checking one pair per iteration.
+const fn is_sorted_by_le<T, F>(v: &[T], mut is_le: F) -> bool
+where
+  F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+{
+  let len = v.len();
+  let mut i = 1;
+  while i + 4 <= len {
+    let sorted = is_le(&v[i - 1], &v[i])
+      && is_le(&v[i], &v[i + 1])
+      && is_le(&v[i + 1], &v[i + 2])
+      && is_le(&v[i + 2], &v[i + 3]);
+    if !sorted {
+      return false;
+    }
+    i += 4;
+  }
+  while i < len {
+    if !is_le(&v[i - 1], &v[i]) {
+      return false;
+    }
+    i += 1;
+  }
+  true
+}
+
+/// Returns the first index in `0..len` for which `is_less` is `false`, assuming that it is `true`
+/// for a prefix of the indices.
+///
+/// The indices 0, 2, 6, 14, ... are probed until one is not less, and the last step is then
+/// binary searched. This is *O*(log(*i*)) for a result *i*.
+const fn gallop<F>(len: usize, mut is_less: F) -> usize
+where
+  F: ~const FnMut(usize) -> bool + ~const Destruct,
+{
+  // `is_less` is `true` for `..low` and `false` for `high..`.
+  let mut low = 0;
+  let mut high = len;
+  let mut step = 1;
+  while step - 1 < len - low {
+    let probe = low + step - 1;
+    if !is_less(probe) {
+      high = probe;
+      break;
+    }
+    low = probe + 1;
+    step = step.saturating_mul(2);
+  }
+  const_sort::const_bisect(low, high, is_less)
+}
 
 #[const_trait]
 /// Trait for sorting slices in const items.
@@ -141,9 +234,9 @@ pub trait ConstSliceSortExt<T> {
   /// randomization to avoid degenerate cases, but with a fixed seed to always provide
   /// deterministic behaviour.
   ///
-  /// Due to its key calling strategy, [`sort_unstable_by_key`](#method.sort_unstable_by_key)
-  /// is likely to be slower than [`sort_by_cached_key`](#method.sort_by_cached_key) in
-  /// cases where the key function is expensive.
+  /// Due to its key calling strategy, this method is likely to be slower than
+  /// [`const_sort_unstable_by_cached_key`](#tymethod.const_sort_unstable_by_cached_key) in cases
+  /// where the key function is expensive.
   ///
   /// # Examples
   ///
@@ -171,6 +264,50 @@ pub trait ConstSliceSortExt<T> {
     F: FnMut(&T) -> K,
     K: Ord;
 
+  /// Sorts the slice with a key extraction function, calling it only once per element.
+  ///
+  /// The keys are computed into `buffer` together with the original index of their element, the
+  /// buffer is sorted by key, and the slice is then rearranged in place according to the sorted
+  /// indices. This is the decorate-sort-undecorate pattern, also known as the Schwartzian
+  /// transform.
+  ///
+  /// This sort is unstable (i.e., may reorder equal elements), does not allocate, and
+  /// *O*(*m* \* *n* + *n* \* log(*n*)) worst-case, where the key function is *O*(*m*).
+  ///
+  /// [`const_sort_unstable_by_key`](#tymethod.const_sort_unstable_by_key) calls the key function
+  /// twice per comparison, i.e. roughly 2 \* *n* \* log(*n*) times. This method pays for a
+  /// buffer of `len()` keys and for moving every element once more, so it is faster if computing a
+  /// key is more expensive than moving an element, e.g. if the key is derived by a loop, and
+  /// slower if the key is a field or a cheap conversion.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `buffer` is shorter than the slice or the slice is longer than `u32::MAX`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use core::mem::MaybeUninit;
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const V: [u32; 5] = {
+  ///   const fn ones(x: &u32) -> u32 {
+  ///     x.count_ones()
+  ///   }
+  ///   let mut v = [0b1111, 0b1, 0b111, 0b11111, 0b11];
+  ///   let mut buffer = [MaybeUninit::uninit(); 5];
+  ///   v.const_sort_unstable_by_cached_key(ones, &mut buffer);
+  ///   v
+  /// };
+  /// assert_eq!(V, [0b1, 0b11, 0b111, 0b1111, 0b11111]);
+  /// ```
+  fn const_sort_unstable_by_cached_key<K, F>(&mut self, f: F, buffer: &mut [MaybeUninit<(K, u32)>])
+  where
+    F: FnMut(&T) -> K,
+    K: Ord + Copy;
+
   /// Reorder the slice such that the element at `index` is at its final sorted position.
   ///
   /// This reordering has the additional property that any value at position `i < index` will be
@@ -335,6 +472,51 @@ pub trait ConstSliceSortExt<T> {
     F: FnMut(&T) -> K,
     K: Ord;
 
+  /// Computes the minimum, the quartiles and the maximum of the slice.
+  ///
+  /// The slice is reordered such that all five elements are at their final sorted positions. This
+  /// reordering is unstable (i.e. any number of equal elements may end up at these positions),
+  /// in-place (i.e. does not allocate), and *O*(*n*) worst-case. See [`OrderSummary`] for the
+  /// exact ranks of the quartiles.
+  ///
+  /// # Current implementation
+  ///
+  /// This is not a single multi-select pass. The median is selected first using the same
+  /// quickselect algorithm as [`const_select_nth_unstable`](#tymethod.const_select_nth_unstable).
+  /// The quartiles are then selected one after another inside the halves left and right of it, and
+  /// the minimum and maximum inside the outer quarters, so every selection only scans the part of
+  /// the slice that can contain it.
+  ///
+  /// # Panics
+  ///
+  /// Panics when the slice is empty.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::{ConstSliceSortExt, OrderSummary};
+  ///
+  /// const S: OrderSummary<u16> = {
+  ///   let mut calibration = [870u16, 120, 455, 310, 999, 640, 205, 730, 515];
+  ///   calibration.const_order_summary()
+  /// };
+  /// assert_eq!(
+  ///   S,
+  ///   OrderSummary {
+  ///     min: 120,
+  ///     q1: 310,
+  ///     median: 515,
+  ///     q3: 730,
+  ///     max: 999,
+  ///   }
+  /// );
+  /// ```
+  fn const_order_summary(&mut self) -> OrderSummary<T>
+  where
+    T: Ord + Copy;
+
   /// Checks if the elements of this slice are sorted.
   ///
   /// That is, for each element `a` and its following element `b`, `a <= b` must hold. If the
@@ -378,6 +560,36 @@ pub trait ConstSliceSortExt<T> {
   fn const_is_sorted_by<F>(&self, compare: F) -> bool
   where
     F: FnMut(&T, &T) -> Option<Ordering>;
+  /// Checks if the elements of this slice are sorted using the given total order comparator
+  /// function.
+  ///
+  /// Like [`const_is_sorted_by`](#tymethod.const_is_sorted_by), but `compare` returns an
+  /// [`Ordering`] directly, so the comparators used with
+  /// [`const_sort_unstable_by`](#tymethod.const_sort_unstable_by) can be reused without wrapping
+  /// their result in `Some`. The slice is sorted if `compare` never returns
+  /// [`Ordering::Greater`] for an element and its following element.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// #![feature(const_cmp)]
+  /// # use core::cmp::Ordering;
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const fn descending(a: &i32, b: &i32) -> Ordering {
+  ///   b.cmp(a)
+  /// }
+  /// const A: bool = [9, 4, 4, 1].const_is_sorted_by_cmp(descending);
+  /// assert!(A);
+  /// const B: bool = [1, 4, 9].const_is_sorted_by_cmp(descending);
+  /// assert!(!B);
+  /// ```
+  #[must_use]
+  fn const_is_sorted_by_cmp<F>(&self, compare: F) -> bool
+  where
+    F: FnMut(&T, &T) -> Ordering;
   /// Checks if the elements of this slice are sorted using the given key extraction function.
   ///
   /// Instead of comparing the slice's elements directly, this function compares the keys of the
@@ -410,95 +622,1358 @@ pub trait ConstSliceSortExt<T> {
   where
     F: FnMut(&T) -> K,
     K: PartialOrd;
-}
 
-impl<T> const ConstSliceSortExt<T> for [T] {
-  #[inline]
-  fn const_sort_unstable(&mut self)
+  /// Returns the first index at which `x` could be inserted while keeping the slice sorted.
+  ///
+  /// All elements before the returned index are less than `x` and all elements from the returned
+  /// index on are greater than or equal to `x`, i.e. `x` would be inserted before any elements
+  /// equal to it. The slice is assumed to be sorted; otherwise the result is unspecified.
+  ///
+  /// This is a binary search and therefore *O*(log(*n*)).
+  ///
+  /// See [`const_insertion_index_last`](#tymethod.const_insertion_index_last) for inserting after
+  /// equal elements.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const S: [i32; 5] = [1, 2, 2, 2, 5];
+  /// const A: usize = S.const_insertion_index(&2);
+  /// assert_eq!(A, 1);
+  /// const B: usize = S.const_insertion_index(&3);
+  /// assert_eq!(B, 4);
+  /// const C: usize = S.const_insertion_index(&9);
+  /// assert_eq!(C, 5);
+  /// ```
+  #[must_use]
+  fn const_insertion_index(&self, x: &T) -> usize
   where
-    T: ~const PartialOrd + Ord,
-  {
-    // https://doc.rust-lang.org/nightly/src/core/slice/mod.rs.html#2539
-    const_sort::const_quicksort(self, PartialOrd::lt);
-  }
-  #[inline]
-  fn const_sort_unstable_by<F>(&mut self, mut compare: F)
+    T: Ord;
+  /// Returns the last index at which `x` could be inserted while keeping the slice sorted.
+  ///
+  /// All elements before the returned index are less than or equal to `x` and all elements from
+  /// the returned index on are greater than `x`, i.e. `x` would be inserted after any elements
+  /// equal to it. Inserting equal elements at this index preserves their insertion order. The
+  /// slice is assumed to be sorted; otherwise the result is unspecified.
+  ///
+  /// This is a binary search and therefore *O*(log(*n*)).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const S: [i32; 5] = [1, 2, 2, 2, 5];
+  /// const A: usize = S.const_insertion_index_last(&2);
+  /// assert_eq!(A, 4);
+  /// const B: usize = S.const_insertion_index_last(&0);
+  /// assert_eq!(B, 0);
+  /// ```
+  #[must_use]
+  fn const_insertion_index_last(&self, x: &T) -> usize
   where
-    F: ~const FnMut(&T, &T) -> Ordering + ~const Destruct,
-  {
-    // https://doc.rust-lang.org/nightly/src/core/slice/mod.rs.html#2594
-    const_sort::const_quicksort(self, const |a, b| compare(a, b) == Ordering::Less);
-  }
-  #[inline]
-  fn const_sort_unstable_by_key<K, F>(&mut self, mut f: F)
+    T: Ord;
+  /// Returns the index of the last element for which `pred` returns `true`.
+  ///
+  /// The slice is assumed to be partitioned according to `pred`, i.e. all elements for which
+  /// `pred` returns `true` are at the start of the slice and all elements for which it returns
+  /// `false` are at the end, as is the case for `|x| x <= &limit` on sorted slices. If it is not
+  /// partitioned, the returned result is unspecified and meaningless.
+  ///
+  /// Returns `None` if `pred` returns `false` for every element.
+  ///
+  /// # Current implementation
+  ///
+  /// The search gallops backwards from the end of the slice with exponentially growing steps and
+  /// then binary searches the last step. It is *O*(log(*d*)) where *d* is the distance of the
+  /// result from the end of the slice, so lookups near the end are cheaper than a binary search.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// // Sorted end points of consecutive ranges.
+  /// const ENDS: [u32; 5] = [10, 20, 30, 40, 50];
+  /// const fn ends_before_35(end: &u32) -> bool {
+  ///   *end <= 35
+  /// }
+  /// const I: Option<usize> = ENDS.const_last_index_where(ends_before_35);
+  /// assert_eq!(I, Some(2));
+  ///
+  /// const fn ends_before_5(end: &u32) -> bool {
+  ///   *end <= 5
+  /// }
+  /// const J: Option<usize> = ENDS.const_last_index_where(ends_before_5);
+  /// assert_eq!(J, None);
+  /// ```
+  #[must_use]
+  fn const_last_index_where<P>(&self, pred: P) -> Option<usize>
   where
-    F: ~const FnMut(&T) -> K + ~const Destruct,
-    K: Ord + ~const PartialOrd + ~const Destruct,
-  {
-    // https://doc.rust-lang.org/nightly/src/core/slice/mod.rs.html#2632
-    const_sort::const_quicksort(self, const |a, b| f(a).lt(&f(b)));
-  }
-
-  #[inline]
-  fn const_select_nth_unstable(&mut self, index: usize) -> (&mut [T], &mut T, &mut [T])
+    P: FnMut(&T) -> bool;
+  /// Returns the last element for which `pred` returns `true`.
+  ///
+  /// This is the element at the index returned by
+  /// [`const_last_index_where`](#tymethod.const_last_index_where); see its documentation for the
+  /// requirements on `pred`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const BREAKPOINTS: [i16; 4] = [-100, 0, 100, 200];
+  /// const fn at_most_150(x: &i16) -> bool {
+  ///   *x <= 150
+  /// }
+  /// const B: Option<&i16> = BREAKPOINTS.const_rfind_sorted(at_most_150);
+  /// assert_eq!(B, Some(&100));
+  /// ```
+  #[must_use]
+  fn const_rfind_sorted<P>(&self, pred: P) -> Option<&T>
   where
-    T: ~const PartialOrd + Ord,
-  {
-    // https://doc.rust-lang.org/nightly/src/core/slice/mod.rs.html#2678
-    const_sort::const_partition_at_index(self, index, PartialOrd::lt)
-  }
-  #[inline]
-  fn const_select_nth_unstable_by<F>(
-    &mut self,
-    index: usize,
-    mut compare: F,
-  ) -> (&mut [T], &mut T, &mut [T])
+    P: FnMut(&T) -> bool;
+  /// Returns the number of distinct elements of a sorted slice.
+  ///
+  /// Only adjacent elements are compared, so for unsorted slices this counts the runs of equal
+  /// elements instead. This function is *O*(*n*).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const N: usize = [1, 1, 2, 3, 3, 3].const_unique_count();
+  /// assert_eq!(N, 3);
+  /// ```
+  #[must_use]
+  fn const_unique_count(&self) -> usize
   where
-    F: ~const FnMut(&T, &T) -> Ordering + ~const Destruct,
-  {
-    // https://doc.rust-lang.org/nightly/src/core/slice/mod.rs.html#2725
-    let mut f = const |a: &T, b: &T| compare(a, b) == Ordering::Less;
-    const_sort::const_partition_at_index(self, index, &mut f)
-  }
-  #[inline]
-  fn const_select_nth_unstable_by_key<K, F>(
-    &mut self,
-    index: usize,
-    mut f: F,
-  ) -> (&mut [T], &mut T, &mut [T])
+    T: PartialEq;
+  /// Writes every distinct element of a sorted slice together with its number of occurrences to
+  /// `values` and `counts`.
+  ///
+  /// The distinct elements are written in the order of the slice. Returns the number of distinct
+  /// elements, see [`const_unique_count`](#tymethod.const_unique_count). This function is
+  /// *O*(*n*).
+  ///
+  /// # Panics
+  ///
+  /// Panics if `values` or `counts` is shorter than the number of distinct elements.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const FREQUENCIES: ([char; 3], [usize; 3]) = {
+  ///   let mut values = ['\0'; 3];
+  ///   let mut counts = [0; 3];
+  ///   ['a', 'a', 'b', 'c', 'c', 'c'].const_value_counts(&mut values, &mut counts);
+  ///   (values, counts)
+  /// };
+  /// assert_eq!(FREQUENCIES, (['a', 'b', 'c'], [2, 1, 3]));
+  /// ```
+  fn const_value_counts(&self, values: &mut [T], counts: &mut [usize]) -> usize
   where
-    F: ~const FnMut(&T) -> K + ~const Destruct,
-    K: Ord + ~const PartialOrd + ~const Destruct,
-  {
-    // https://doc.rust-lang.org/nightly/src/core/slice/mod.rs.html#2776
-    let mut g = const |a: &T, b: &T| f(a).lt(&f(b));
-    const_sort::const_partition_at_index(self, index, &mut g)
-  }
-
-  #[inline]
-  fn const_is_sorted(&self) -> bool
+    T: PartialEq + Copy;
+  /// Returns the index of the element of a sorted slice closest to `x`.
+  ///
+  /// The distance between two elements is the difference of the larger and the smaller one. It is
+  /// never computed directly, so elements of signed types far apart on both sides of zero do not
+  /// overflow. If two elements are equally close, the lower index is returned. This is useful for
+  /// quantization tables where exact matches are not expected. See
+  /// [`const_nearest_sorted_by`](#tymethod.const_nearest_sorted_by) for elements without
+  /// subtraction.
+  ///
+  /// This is a binary search and therefore *O*(log(*n*)).
+  ///
+  /// # Panics
+  ///
+  /// Panics if the slice is empty.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const LEVELS: [u8; 4] = [0, 85, 170, 255];
+  /// const A: usize = LEVELS.const_nearest_sorted(&100);
+  /// assert_eq!(A, 1);
+  /// const B: usize = LEVELS.const_nearest_sorted(&255);
+  /// assert_eq!(B, 3);
+  /// ```
+  #[must_use]
+  fn const_nearest_sorted(&self, x: &T) -> usize
   where
-    T: ~const PartialOrd,
-  {
-    self.const_is_sorted_by(PartialOrd::partial_cmp)
-  }
-  fn const_is_sorted_by<F>(&self, mut compare: F) -> bool
+    T: Ord + Sub<Output = T> + Copy;
+  /// Returns the index of the element of a sorted slice closest to `x` according to `distance`.
+  ///
+  /// `distance(x, y)` must grow the further `y` is from `x` in the order of the slice. If two
+  /// elements are equally close, the lower index is returned.
+  ///
+  /// This is a binary search and therefore *O*(log(*n*)).
+  ///
+  /// # Panics
+  ///
+  /// Panics if the slice is empty.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const fn distance(a: &char, b: &char) -> u32 {
+  ///   (*a as u32).abs_diff(*b as u32)
+  /// }
+  /// const I: usize = ['a', 'f', 'k'].const_nearest_sorted_by(&'i', distance);
+  /// assert_eq!(I, 2);
+  /// ```
+  #[must_use]
+  fn const_nearest_sorted_by<F, D>(&self, x: &T, distance: F) -> usize
   where
-    F: ~const FnMut(&T, &T) -> Option<Ordering> + ~const Destruct,
-  {
-    // https://doc.rust-lang.org/nightly/src/core/iter/traits/iterator.rs.html#3794
-    let mut i = 1;
-    while i < self.len() {
-      let ord_opt = compare(&self[i - 1], &self[i]);
-      if ord_opt.is_none() {
-        return false;
-      }
-      if ord_opt.unwrap() == Ordering::Greater {
-        return false;
+    T: Ord,
+    F: FnMut(&T, &T) -> D,
+    D: Ord;
+  /// Reorders the slice such that the median is at its final sorted position.
+  ///
+  /// This is [`const_select_nth_unstable`](#tymethod.const_select_nth_unstable) with the index
+  /// `len() / 2`, i.e. the upper median for slices of even length. It returns all elements less
+  /// than or equal to the median, the median, and all elements greater than or equal to the
+  /// median.
+  ///
+  /// # Panics
+  ///
+  /// Panics when the slice is empty.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const MEDIAN: i32 = {
+  ///   let mut v = [-5i32, 4, 1, -3, 2];
+  ///   *v.const_partition_by_median().1
+  /// };
+  /// assert_eq!(MEDIAN, 1);
+  /// ```
+  fn const_partition_by_median(&mut self) -> (&mut [T], &mut T, &mut [T])
+  where
+    T: Ord;
+  /// Binary searches this sorted slice for `x`, starting the search around `hint`.
+  ///
+  /// Behaves like [`binary_search`]: if the value is found then [`Ok`] is returned, containing
+  /// the index of a matching element. If there are multiple matches, then any one of the
+  /// matches could be returned. If the value is not found then [`Err`] is returned, containing
+  /// the index where a matching element could be inserted while maintaining sorted order.
+  ///
+  /// [`binary_search`]: slice::binary_search
+  ///
+  /// # Errors
+  ///
+  /// Returns the insertion index of `x` as [`Err`] if the slice does not contain `x`.
+  ///
+  /// # Current implementation
+  ///
+  /// The search gallops outwards from `hint` with exponentially growing steps and then binary
+  /// searches the last step. It is *O*(log(*d*)) where *d* is the distance of the result from
+  /// `hint`, so correlated queries, e.g. walking a range table in order while passing the
+  /// previous result as the hint, are cheaper than independent binary searches. Hints past the
+  /// end of the slice are treated like the index of the last element.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const S: [u32; 8] = [1, 3, 5, 7, 9, 11, 13, 15];
+  /// const A: Result<usize, usize> = S.const_binary_search_with_hint(&11, 4);
+  /// assert_eq!(A, Ok(5));
+  /// const B: Result<usize, usize> = S.const_binary_search_with_hint(&4, 7);
+  /// assert_eq!(B, Err(2));
+  /// ```
+  fn const_binary_search_with_hint(&self, x: &T, hint: usize) -> Result<usize, usize>
+  where
+    T: Ord;
+  /// Sorts the slice by the [`ConstKey`] of its elements, but might not preserve the order of
+  /// equal elements.
+  ///
+  /// This is [`const_sort_unstable_by_key`](#tymethod.const_sort_unstable_by_key) with
+  /// [`ConstKey::as_ord_key`] as the key function.
+  fn const_sort_unstable_by_const_key(&mut self)
+  where
+    T: ConstKey,
+    T::Key: Ord;
+  /// Binary searches this slice, sorted by the [`ConstKey`] of its elements, for `key`.
+  ///
+  /// Behaves like [`binary_search_by_key`]: if an element with the key is found then [`Ok`] is
+  /// returned, containing its index. If there are multiple matches, then any one of the matches
+  /// could be returned. If the key is not found then [`Err`] is returned, containing the index
+  /// where an element with the key could be inserted while maintaining sorted order.
+  ///
+  /// [`binary_search_by_key`]: slice::binary_search_by_key
+  ///
+  /// # Errors
+  ///
+  /// Returns the insertion index of `key` as [`Err`] if no element has the key.
+  fn const_search_by_const_key(&self, key: &T::Key) -> Result<usize, usize>
+  where
+    T: ConstKey,
+    T::Key: Ord;
+  /// Returns the element that gives the minimum value from the key function `f`.
+  ///
+  /// If several elements are equally minimum, the first element is returned. If the slice is
+  /// empty, `None` is returned. Like [`Iterator::min_by_key`], `f` is called once per element.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const fn abs(x: &i32) -> i32 {
+  ///   x.abs()
+  /// }
+  /// const MIN: Option<&i32> = [-3, 2, -7, -2].const_min_by_key(abs);
+  /// assert_eq!(MIN, Some(&2));
+  /// ```
+  #[must_use]
+  fn const_min_by_key<F, K>(&self, f: F) -> Option<&T>
+  where
+    F: FnMut(&T) -> K,
+    K: Ord;
+  /// Returns the element that gives the maximum value from the key function `f`.
+  ///
+  /// If several elements are equally maximum, the last element is returned. If the slice is
+  /// empty, `None` is returned. Like [`Iterator::max_by_key`], `f` is called once per element.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const fn abs(x: &i32) -> i32 {
+  ///   x.abs()
+  /// }
+  /// const MAX: Option<&i32> = [-3, 7, -7, -2].const_max_by_key(abs);
+  /// assert_eq!(MAX, Some(&-7));
+  /// ```
+  #[must_use]
+  fn const_max_by_key<F, K>(&self, f: F) -> Option<&T>
+  where
+    F: FnMut(&T) -> K,
+    K: Ord;
+  /// Returns `true` if the slice contains an element equal to `x`.
+  ///
+  /// The slice does not need to be sorted. This is a linear scan stopping at the first match and
+  /// therefore *O*(*n*). Use [`const_binary_search`](#tymethod.const_binary_search) for
+  /// membership tests on sorted slices.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const HAS_SSH: bool = [443u16, 80, 22].const_contains(&22);
+  /// assert!(HAS_SSH);
+  /// ```
+  #[must_use]
+  fn const_contains(&self, x: &T) -> bool
+  where
+    T: PartialEq;
+  /// Returns the index of the first element equal to `x`, or `None` if there is none.
+  ///
+  /// The slice does not need to be sorted. This is a linear scan stopping at the first match and
+  /// therefore *O*(*n*).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const POS: Option<usize> = ['b', 'a', 'b'].const_position(&'b');
+  /// assert_eq!(POS, Some(0));
+  /// ```
+  #[must_use]
+  fn const_position(&self, x: &T) -> Option<usize>
+  where
+    T: PartialEq;
+  /// Reorders the slice such that the elements with the ranks `lo..=hi` are at their final sorted
+  /// positions, and returns them as a sorted slice.
+  ///
+  /// All elements before `lo` are less than or equal to the returned elements and all elements
+  /// after `hi` are greater than or equal to them. This partially sorts a band in the middle of
+  /// the slice, e.g. to drop outliers at both ends before computing statistics, without sorting
+  /// the whole slice.
+  ///
+  /// # Current implementation
+  ///
+  /// The elements at `lo` and `hi` are selected with
+  /// [`const_select_nth_unstable`](#tymethod.const_select_nth_unstable) and the elements between
+  /// them are sorted with [`const_sort_unstable`](#tymethod.const_sort_unstable). This is
+  /// *O*(*n* + *k* \* log(*k*)) on average for a band of *k* elements.
+  ///
+  /// # Panics
+  ///
+  /// Panics when `lo > hi` or `hi >= len()`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const BAND: [u8; 3] = {
+  ///   let mut v = [9, 0, 7, 2, 5, 1, 8, 3];
+  ///   let band = v.const_select_range_unstable(2, 4);
+  ///   [band[0], band[1], band[2]]
+  /// };
+  /// assert_eq!(BAND, [2, 3, 5]);
+  /// ```
+  fn const_select_range_unstable(&mut self, lo: usize, hi: usize) -> &mut [T]
+  where
+    T: Ord;
+  /// Reorders the slice such that its `trim` smallest and `trim` largest elements are at the
+  /// ends, and returns the remaining elements in between.
+  ///
+  /// The returned elements are in no particular order, except that the first one is the smallest
+  /// and the last one is the largest of them. Every element before them is less than or equal to
+  /// the first one and every element after them is greater than or equal to the last one. Use it
+  /// to drop outliers at both ends before computing a trimmed mean or similar statistics.
+  ///
+  /// # Current implementation
+  ///
+  /// Like [`const_select_range_unstable`](#tymethod.const_select_range_unstable), but the
+  /// elements between the bounds are not sorted, so this is *O*(*n*) on average.
+  ///
+  /// # Panics
+  ///
+  /// Panics when no element remains, i.e. when `2 * trim >= len()`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const TRIMMED_SUM: u32 = {
+  ///   let mut readings = [12u32, 11, 250, 13, 0, 12, 14];
+  ///   let kept = readings.const_trim_unstable(1);
+  ///   let mut sum = 0;
+  ///   let mut i = 0;
+  ///   while i < kept.len() {
+  ///     sum += kept[i];
+  ///     i += 1;
+  ///   }
+  ///   sum
+  /// };
+  /// assert_eq!(TRIMMED_SUM, 62);
+  /// ```
+  fn const_trim_unstable(&mut self, trim: usize) -> &mut [T]
+  where
+    T: Ord;
+  /// Returns the smallest and the largest element left after removing the `trim` smallest and
+  /// `trim` largest elements.
+  ///
+  /// The slice is reordered like by [`const_trim_unstable`](#tymethod.const_trim_unstable). The
+  /// bounds are the range a trimmed mean is computed over, and clamping the trimmed elements to
+  /// them gives the winsorized data.
+  ///
+  /// # Panics
+  ///
+  /// Panics when no element remains, i.e. when `2 * trim >= len()`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const BOUNDS: (u32, u32) = {
+  ///   let mut readings = [12u32, 11, 250, 13, 0, 12, 14];
+  ///   let (low, high) = readings.const_trimmed_mean_bounds(1);
+  ///   (*low, *high)
+  /// };
+  /// assert_eq!(BOUNDS, (11, 14));
+  /// ```
+  fn const_trimmed_mean_bounds(&mut self, trim: usize) -> (&T, &T)
+  where
+    T: Ord;
+  /// Sorts the slice with a key extraction function like
+  /// [`const_sort_unstable_by_key`](#tymethod.const_sort_unstable_by_key) and returns how often
+  /// the key function was called.
+  ///
+  /// The key function is called twice per comparison. If the count is large compared to the
+  /// length of the slice and the key function is expensive, computing the keys once and sorting
+  /// with [`const_sort_by_external_key`](crate::const_sort::const_sort_by_external_key) may fit
+  /// better into the const evaluation limit.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const CALLS: usize = {
+  ///   const fn len(s: &&str) -> usize {
+  ///     s.len()
+  ///   }
+  ///   let mut v = ["ccc", "a", "bb"];
+  ///   v.const_sort_unstable_by_key_counted(len)
+  /// };
+  /// assert!(CALLS >= 4);
+  /// ```
+  fn const_sort_unstable_by_key_counted<K, F>(&mut self, f: F) -> usize
+  where
+    F: FnMut(&T) -> K,
+    K: Ord;
+  /// Sorts every segment `boundaries[i]..boundaries[i + 1]` of the slice independently, but
+  /// might not preserve the order of equal elements.
+  ///
+  /// `boundaries` are the start offsets of groups followed by the end of the last group, e.g.
+  /// `[0, 3, 7, len()]` for a table of three groups stored one after another. Elements before
+  /// the first and after the last boundary are not moved.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `boundaries` is not sorted in ascending order or if a boundary is greater than
+  /// `len()`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const GROUPED: [u8; 7] = {
+  ///   let mut v = [3, 1, 2, 9, 8, 5, 4];
+  ///   v.const_sort_segments(&[0, 3, 3, 7]);
+  ///   v
+  /// };
+  /// assert_eq!(GROUPED, [1, 2, 3, 4, 5, 8, 9]);
+  /// ```
+  fn const_sort_segments(&mut self, boundaries: &[usize])
+  where
+    T: Ord;
+  /// Moves the elements for which `pred` returns `true` to the front of the slice, preserving
+  /// their order, and returns their number.
+  ///
+  /// This is the const counterpart of [`Vec::retain`] for fixed-size storage: afterwards
+  /// `self[..n]` holds the retained elements and `self[n..]` the removed ones in an unspecified
+  /// order, where `n` is the returned length. `pred` is called exactly once per element, in
+  /// order.
+  ///
+  /// [`Vec::retain`]: https://doc.rust-lang.org/std/vec/struct.Vec.html#method.retain
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const EVENS: ([u8; 6], usize) = {
+  ///   const fn is_even(x: &u8) -> bool {
+  ///     *x % 2 == 0
+  ///   }
+  ///   let mut v = [1, 4, 2, 7, 8, 3];
+  ///   let len = v.const_retain(is_even);
+  ///   (v, len)
+  /// };
+  /// assert_eq!(EVENS.0[..EVENS.1], [4, 2, 8]);
+  /// ```
+  fn const_retain<P>(&mut self, pred: P) -> usize
+  where
+    P: FnMut(&T) -> bool;
+  /// Checks if the elements of this slice are sorted in descending order.
+  ///
+  /// That is, for each element `a` and its following element `b`, `a >= b` must hold. If the
+  /// slice yields exactly zero or one element, `true` is returned.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const A: bool = [9, 4, 4, 1].const_is_sorted_desc();
+  /// assert!(A);
+  /// const B: bool = [1, 4, 9].const_is_sorted_desc();
+  /// assert!(!B);
+  /// ```
+  #[must_use]
+  fn const_is_sorted_desc(&self) -> bool
+  where
+    T: PartialOrd;
+  /// Returns the first index at which `x` could be inserted while keeping the slice sorted in
+  /// descending order.
+  ///
+  /// All elements before the returned index are greater than `x` and all elements from the
+  /// returned index on are less than or equal to `x`, i.e. this is the partition point of
+  /// `|e| e > x`. It is the descending counterpart of
+  /// [`const_insertion_index`](#tymethod.const_insertion_index). The slice is assumed to be sorted
+  /// in descending order; otherwise the result is unspecified.
+  ///
+  /// This is a binary search and therefore *O*(log(*n*)).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const S: [i32; 5] = [9, 5, 5, 5, 1];
+  /// const A: usize = S.const_insertion_index_desc(&5);
+  /// assert_eq!(A, 1);
+  /// const B: usize = S.const_insertion_index_desc(&3);
+  /// assert_eq!(B, 4);
+  /// const C: usize = S.const_insertion_index_desc(&0);
+  /// assert_eq!(C, 5);
+  /// ```
+  #[must_use]
+  fn const_insertion_index_desc(&self, x: &T) -> usize
+  where
+    T: Ord;
+  /// Binary searches this slice sorted in descending order for `x`.
+  ///
+  /// If `x` is found, [`Ok`] with the index of the first element equal to `x` is returned.
+  /// Otherwise [`Err`] with the index where `x` could be inserted while keeping the slice sorted
+  /// in descending order is returned. The slice is assumed to be sorted in descending order;
+  /// otherwise the result is unspecified.
+  ///
+  /// This is *O*(log(*n*)).
+  ///
+  /// # Errors
+  ///
+  /// Returns the insertion index of `x` as [`Err`] if the slice does not contain `x`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const PRIORITIES: [u8; 5] = [200, 120, 120, 30, 5];
+  /// const A: Result<usize, usize> = PRIORITIES.const_binary_search_desc(&120);
+  /// assert_eq!(A, Ok(1));
+  /// const B: Result<usize, usize> = PRIORITIES.const_binary_search_desc(&100);
+  /// assert_eq!(B, Err(3));
+  /// ```
+  fn const_binary_search_desc(&self, x: &T) -> Result<usize, usize>
+  where
+    T: Ord;
+  /// Returns the smallest difference between adjacent elements of a sorted slice, together with
+  /// the index of the first element of the closest pair.
+  ///
+  /// The difference of the pair at index `i` is `self[i + 1] - self[i]`. If several pairs are
+  /// equally close, the lowest index is returned. Returns `None` if the slice has fewer than two
+  /// elements. This validates that a quantization table has no near-duplicate breakpoints. See
+  /// [`const_min_adjacent_gap_by`](#tymethod.const_min_adjacent_gap_by) for elements without
+  /// subtraction. The slice is assumed to be sorted; otherwise the result is unspecified.
+  ///
+  /// This is *O*(*n*).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const BREAKPOINTS: [u16; 5] = [0, 120, 250, 252, 400];
+  /// const CLOSEST: Option<(usize, u16)> = BREAKPOINTS.const_min_adjacent_gap();
+  /// assert_eq!(CLOSEST, Some((2, 2)));
+  /// ```
+  #[must_use]
+  fn const_min_adjacent_gap(&self) -> Option<(usize, T)>
+  where
+    T: Ord + Sub<Output = T> + Copy;
+  /// Returns the smallest `distance` between adjacent elements of a sorted slice, together with
+  /// the index of the first element of the closest pair.
+  ///
+  /// Like [`const_min_adjacent_gap`](#tymethod.const_min_adjacent_gap), but the distance of the
+  /// pair at index `i` is `distance(&self[i], &self[i + 1])`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const fn distance(a: &char, b: &char) -> u32 {
+  ///   *b as u32 - *a as u32
+  /// }
+  /// const CLOSEST: Option<(usize, u32)> =
+  ///   ['a', 'f', 'h', 'z'].const_min_adjacent_gap_by(distance);
+  /// assert_eq!(CLOSEST, Some((1, 2)));
+  /// ```
+  #[must_use]
+  fn const_min_adjacent_gap_by<F, D>(&self, distance: F) -> Option<(usize, D)>
+  where
+    F: FnMut(&T, &T) -> D,
+    D: Ord;
+  /// Sorts the slice with `algorithm`, regardless of the `force-heapsort` and `force-insertion`
+  /// features.
+  ///
+  /// See [`const_sort_with`](crate::const_sort::const_sort_with).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::const_sort::Algorithm;
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const V: [u8; 5] = {
+  ///   let mut v = [5, 1, 4, 2, 3];
+  ///   v.const_sort_unstable_with(Algorithm::Pdq);
+  ///   v
+  /// };
+  /// assert_eq!(V, [1, 2, 3, 4, 5]);
+  /// ```
+  fn const_sort_unstable_with(&mut self, algorithm: const_sort::Algorithm)
+  where
+    T: Ord;
+  /// Rearranges the slice such that the element at index `i` is the element previously at index
+  /// `perm[i]`.
+  ///
+  /// This gathers the elements in the order given by `perm`, e.g. applying the result of an
+  /// argsort to a parallel table sorts the table. It is the inverse of
+  /// [`const_scatter_permutation`](#tymethod.const_scatter_permutation).
+  ///
+  /// # Current implementation
+  ///
+  /// Every cycle of `perm` is rotated in place with swaps, starting at its smallest index. Finding
+  /// the smallest indices follows the cycles without allocating, which is *O*(*n*^2) worst-case
+  /// for a single long cycle and *O*(*n*) if all cycles are short.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `perm` is not a permutation of `0..len()`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const GATHERED: [char; 4] = {
+  ///   let mut v = ['a', 'b', 'c', 'd'];
+  ///   v.const_apply_permutation(&[2, 0, 3, 1]);
+  ///   v
+  /// };
+  /// assert_eq!(GATHERED, ['c', 'a', 'd', 'b']);
+  /// ```
+  fn const_apply_permutation(&mut self, perm: &[usize]);
+  /// Rearranges the slice such that the element previously at index `i` is moved to index
+  /// `perm[i]`.
+  ///
+  /// This scatters the elements to the positions given by `perm`, e.g. moving rows to the ranks
+  /// computed for them. It is the inverse of
+  /// [`const_apply_permutation`](#tymethod.const_apply_permutation), so scattering with a
+  /// permutation undoes gathering with it.
+  ///
+  /// # Current implementation
+  ///
+  /// Like [`const_apply_permutation`](#tymethod.const_apply_permutation), this is *O*(*n*^2)
+  /// worst-case for a single long cycle and *O*(*n*) if all cycles are short.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `perm` is not a permutation of `0..len()`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const SCATTERED: [char; 4] = {
+  ///   let mut v = ['a', 'b', 'c', 'd'];
+  ///   v.const_scatter_permutation(&[2, 0, 3, 1]);
+  ///   v
+  /// };
+  /// assert_eq!(SCATTERED, ['b', 'd', 'a', 'c']);
+  /// ```
+  fn const_scatter_permutation(&mut self, perm: &[usize]);
+  /// Checks if the slice is a rotation of a sorted slice and returns the rotation point.
+  ///
+  /// The rotation point is the index `r` such that `self[r..]` followed by `self[..r]` is sorted,
+  /// e.g. the position of the oldest entry of a snapshot of a sorted ring buffer. It is 0 if the
+  /// slice is sorted, including if it is empty. Returns `None` if the slice is not a rotation of
+  /// a sorted slice.
+  ///
+  /// This is *O*(*n*).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const A: Option<usize> = [40, 50, 10, 20, 30].const_is_sorted_rotated();
+  /// assert_eq!(A, Some(2));
+  /// const B: Option<usize> = [10, 20, 30].const_is_sorted_rotated();
+  /// assert_eq!(B, Some(0));
+  /// const C: Option<usize> = [40, 10, 50, 20].const_is_sorted_rotated();
+  /// assert_eq!(C, None);
+  /// ```
+  #[must_use]
+  fn const_is_sorted_rotated(&self) -> Option<usize>
+  where
+    T: PartialOrd;
+  /// Binary searches this rotated sorted slice for `x`.
+  ///
+  /// `rotation` is the rotation point of the slice as returned by
+  /// [`const_is_sorted_rotated`](#tymethod.const_is_sorted_rotated). If `x` is found, [`Ok`] with
+  /// the index of the first element equal to `x` in rotated order is returned. Otherwise [`Err`]
+  /// with the index where `x` could be inserted while keeping the slice a rotation of a sorted
+  /// slice is returned. The slice is assumed to be sorted after rotating it by `rotation`;
+  /// otherwise the result is unspecified.
+  ///
+  /// This is *O*(log(*n*)).
+  ///
+  /// # Errors
+  ///
+  /// Returns the insertion index of `x` as [`Err`] if the slice does not contain `x`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `rotation > len()`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const RING: [u32; 5] = [40, 50, 10, 20, 30];
+  /// const ROTATION: usize = match RING.const_is_sorted_rotated() {
+  ///   Some(rotation) => rotation,
+  ///   None => panic!("ring buffer is not sorted"),
+  /// };
+  /// const A: Result<usize, usize> = RING.const_binary_search_rotated(ROTATION, &20);
+  /// assert_eq!(A, Ok(3));
+  /// const B: Result<usize, usize> = RING.const_binary_search_rotated(ROTATION, &45);
+  /// assert_eq!(B, Err(1));
+  /// ```
+  fn const_binary_search_rotated(&self, rotation: usize, x: &T) -> Result<usize, usize>
+  where
+    T: Ord;
+  /// Sorts the slice.
+  ///
+  /// This sort is stable (i.e., does not reorder equal elements), in-place (i.e., does not
+  /// allocate), and *O*(*n* \* log(*n*)) comparisons and *O*(*n* \* log(*n*)^2) moves
+  /// worst-case.
+  ///
+  /// When equal elements are indistinguishable, e.g. integers,
+  /// [`const_sort_unstable`](#tymethod.const_sort_unstable) is faster.
+  ///
+  /// # Current implementation
+  ///
+  /// See [`const_merge_sort`](crate::const_sort::const_merge_sort).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const V: [i32; 5] = {
+  ///   let mut v = [-5, 4, 1, -3, 2];
+  ///   v.const_sort();
+  ///   v
+  /// };
+  /// assert_eq!(V, [-5, -3, 1, 2, 4]);
+  /// ```
+  fn const_sort(&mut self)
+  where
+    T: Ord;
+  /// Sorts the slice with a comparator function.
+  ///
+  /// This sort is stable (i.e., does not reorder equal elements), in-place (i.e., does not
+  /// allocate), and *O*(*n* \* log(*n*)) comparisons and *O*(*n* \* log(*n*)^2) moves
+  /// worst-case.
+  ///
+  /// The comparator function must define a total ordering for the elements in the slice. If the
+  /// ordering is not total, the order of the elements is unspecified.
+  ///
+  /// # Current implementation
+  ///
+  /// See [`const_merge_sort`](crate::const_sort::const_merge_sort).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// #![feature(const_cmp)]
+  /// use core::cmp::Ordering;
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const ROUTES: [(u8, &str); 4] = {
+  ///   // no const closures yet
+  ///   const fn by_prefix_len(a: &(u8, &str), b: &(u8, &str)) -> Ordering {
+  ///     a.0.cmp(&b.0)
+  ///   }
+  ///   let mut routes = [(16, "lan"), (8, "corp"), (16, "vpn"), (8, "lab")];
+  ///   routes.const_sort_by(by_prefix_len);
+  ///   routes
+  /// };
+  /// // Routes with the same prefix length keep their order.
+  /// assert_eq!(ROUTES, [(8, "corp"), (8, "lab"), (16, "lan"), (16, "vpn")]);
+  /// ```
+  fn const_sort_by<F>(&mut self, compare: F)
+  where
+    F: FnMut(&T, &T) -> Ordering;
+  /// Binary searches this sorted slice for `x`.
+  ///
+  /// Behaves like [`binary_search`]: if the value is found then [`Ok`] is returned, containing
+  /// the index of a matching element. If there are multiple matches, then any one of the
+  /// matches could be returned. If the value is not found then [`Err`] is returned, containing
+  /// the index where a matching element could be inserted while maintaining sorted order. Use
+  /// [`const_insertion_index`](#tymethod.const_insertion_index) to find the first match.
+  ///
+  /// The slice is assumed to be sorted; otherwise the result is unspecified. This is
+  /// *O*(log(*n*)).
+  ///
+  /// [`binary_search`]: slice::binary_search
+  ///
+  /// # Errors
+  ///
+  /// Returns the insertion index of `x` as [`Err`] if the slice does not contain `x`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const PRIMES: [u16; 6] = [2, 3, 5, 7, 11, 13];
+  /// const A: Result<usize, usize> = PRIMES.const_binary_search(&7);
+  /// assert_eq!(A, Ok(3));
+  /// const B: Result<usize, usize> = PRIMES.const_binary_search(&4);
+  /// assert_eq!(B, Err(2));
+  /// ```
+  fn const_binary_search(&self, x: &T) -> Result<usize, usize>
+  where
+    T: Ord;
+  /// Binary searches this sorted slice with a comparator function.
+  ///
+  /// Behaves like [`binary_search_by`]: `f` returns whether an element is [`Less`], [`Equal`] or
+  /// [`Greater`] than the desired target, and must be consistent with the sort order of the
+  /// slice, e.g. the comparator passed to
+  /// [`const_sort_unstable_by`](#tymethod.const_sort_unstable_by) applied to the target. If an
+  /// element is found then [`Ok`] is returned, containing its index. If there are multiple
+  /// matches, then any one of the matches could be returned. Otherwise [`Err`] is returned,
+  /// containing the index where a matching element could be inserted while maintaining sorted
+  /// order.
+  ///
+  /// This is *O*(log(*n*)).
+  ///
+  /// [`binary_search_by`]: slice::binary_search_by
+  /// [`Less`]: Ordering::Less
+  /// [`Equal`]: Ordering::Equal
+  /// [`Greater`]: Ordering::Greater
+  ///
+  /// # Errors
+  ///
+  /// Returns the insertion index of the target as [`Err`] if no element is equal to it.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// #![feature(const_cmp)]
+  /// use core::cmp::Ordering;
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// // Sorted by descending length.
+  /// const WORDS: [&str; 4] = ["const", "sort", "rs", "a"];
+  /// const fn cmp_to_two(word: &&str) -> Ordering {
+  ///   2.cmp(&word.len())
+  /// }
+  /// const TWO: Result<usize, usize> = WORDS.const_binary_search_by(cmp_to_two);
+  /// assert_eq!(TWO, Ok(2));
+  /// ```
+  fn const_binary_search_by<F>(&self, f: F) -> Result<usize, usize>
+  where
+    F: FnMut(&T) -> Ordering;
+  /// Binary searches this sorted slice for `x` without data-dependent branches.
+  ///
+  /// If `x` is found, [`Ok`] with the index of the first element equal to `x` is returned.
+  /// Otherwise [`Err`] with the index where `x` could be inserted while maintaining sorted order
+  /// is returned. The slice is assumed to be sorted; otherwise the result is unspecified.
+  ///
+  /// # Current implementation
+  ///
+  /// The search window is halved exactly ceil(log2(*n*)) times without an early exit, and every
+  /// step advances the window by the result of a comparison multiplied by the step length. At
+  /// runtime the compiler turns this into conditional moves, which avoids the branch
+  /// mispredictions of [`const_binary_search`](#tymethod.const_binary_search) on large tables
+  /// that do not fit into the cache. The result is the same during const evaluation.
+  ///
+  /// For slices with a power of two length, e.g. tables padded with
+  /// [`const_pad_sorted_to_pow2`](crate::const_array::const_pad_sorted_to_pow2), every step halves
+  /// the window exactly.
+  ///
+  /// # Errors
+  ///
+  /// Returns the insertion index of `x` as [`Err`] if the slice does not contain `x`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const S: [u32; 7] = [1, 4, 4, 4, 9, 16, 25];
+  /// const A: Result<usize, usize> = S.const_binary_search_branchless(&4);
+  /// assert_eq!(A, Ok(1));
+  /// const B: Result<usize, usize> = S.const_binary_search_branchless(&10);
+  /// assert_eq!(B, Err(5));
+  /// ```
+  fn const_binary_search_branchless(&self, x: &T) -> Result<usize, usize>
+  where
+    T: Ord;
+  /// Returns the index of the partition point according to the given predicate (the index of the
+  /// first element of the second partition).
+  ///
+  /// Behaves like [`partition_point`]: the slice is assumed to be partitioned according to
+  /// `pred`, i.e. all elements for which `pred` returns `true` are at the start of the slice and
+  /// all elements for which it returns `false` are at the end; otherwise the result is
+  /// unspecified. For a sorted slice, `|e| e < x` gives the insertion index of `x`.
+  ///
+  /// This is a binary search with [`const_bisect`](crate::const_sort::const_bisect) and therefore
+  /// *O*(log(*n*)).
+  ///
+  /// [`partition_point`]: slice::partition_point
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// // Upper bounds of the tax brackets.
+  /// const BRACKETS: [u32; 4] = [10_000, 40_000, 90_000, 200_000];
+  /// const fn below_50_000(bound: &u32) -> bool {
+  ///   *bound < 50_000
+  /// }
+  /// const BRACKET: usize = BRACKETS.const_partition_point(below_50_000);
+  /// assert_eq!(BRACKET, 2);
+  /// ```
+  #[must_use]
+  fn const_partition_point<P>(&self, pred: P) -> usize
+  where
+    P: FnMut(&T) -> bool;
+  /// Searches this sorted slice for `x` by galloping from the front.
+  ///
+  /// If `x` is found, [`Ok`] with the index of the first element equal to `x` is returned.
+  /// Otherwise [`Err`] with the index where `x` could be inserted while maintaining sorted order
+  /// is returned. The slice is assumed to be sorted; otherwise the result is unspecified.
+  ///
+  /// # Current implementation
+  ///
+  /// The elements at the indices 0, 2, 6, 14, ... are compared with `x` until one is not less
+  /// than `x`, and the last step is then binary searched. This is *O*(log(*i*)) for a result
+  /// *i*, so it beats [`const_binary_search`](#tymethod.const_binary_search) when the match tends
+  /// to be near the front, e.g. when repeatedly searching the rest of a table while merging it
+  /// with another one.
+  ///
+  /// # Errors
+  ///
+  /// Returns the insertion index of `x` as [`Err`] if the slice does not contain `x`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// const EVENTS: [u32; 8] = [3, 3, 5, 8, 13, 21, 34, 55];
+  /// const A: Result<usize, usize> = EVENTS.const_exponential_search(&3);
+  /// assert_eq!(A, Ok(0));
+  /// const B: Result<usize, usize> = EVENTS.const_exponential_search(&9);
+  /// assert_eq!(B, Err(4));
+  /// ```
+  fn const_exponential_search(&self, x: &T) -> Result<usize, usize>
+  where
+    T: Ord;
+  /// Searches this sorted slice with a comparator function by galloping from the front.
+  ///
+  /// `f` returns whether an element is [`Less`], [`Equal`] or [`Greater`] than the desired
+  /// target, like for [`const_binary_search_by`](#tymethod.const_binary_search_by). If an element
+  /// is found, [`Ok`] with the index of the first match is returned. Otherwise [`Err`] is
+  /// returned, containing the index where a matching element could be inserted while maintaining
+  /// sorted order.
+  ///
+  /// This is *O*(log(*i*)) for a result *i*; see
+  /// [`const_exponential_search`](#tymethod.const_exponential_search).
+  ///
+  /// [`Less`]: Ordering::Less
+  /// [`Equal`]: Ordering::Equal
+  /// [`Greater`]: Ordering::Greater
+  ///
+  /// # Errors
+  ///
+  /// Returns the insertion index of the target as [`Err`] if no element is equal to it.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// #![feature(const_mut_refs)]
+  /// #![feature(const_trait_impl)]
+  /// #![feature(const_cmp)]
+  /// use core::cmp::Ordering;
+  /// use const_sort::ConstSliceSortExt;
+  ///
+  /// // Events sorted by their timestamp.
+  /// const EVENTS: [(u32, &str); 5] =
+  ///   [(1, "boot"), (4, "load"), (4, "link"), (9, "run"), (12, "exit")];
+  /// const fn cmp_to_4(event: &(u32, &str)) -> Ordering {
+  ///   event.0.cmp(&4)
+  /// }
+  /// const AT_4: Result<usize, usize> = EVENTS.const_exponential_search_by(cmp_to_4);
+  /// assert_eq!(AT_4, Ok(1));
+  /// ```
+  fn const_exponential_search_by<F>(&self, f: F) -> Result<usize, usize>
+  where
+    F: FnMut(&T) -> Ordering;
+}
+
+impl<T> const ConstSliceSortExt<T> for [T] {
+  #[inline]
+  fn const_sort_unstable(&mut self)
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    // https://doc.rust-lang.org/nightly/src/core/slice/mod.rs.html#2539
+    const_sort::const_quicksort(self, PartialOrd::lt);
+  }
+  #[inline]
+  fn const_sort_unstable_by<F>(&mut self, mut compare: F)
+  where
+    F: ~const FnMut(&T, &T) -> Ordering + ~const Destruct,
+  {
+    // https://doc.rust-lang.org/nightly/src/core/slice/mod.rs.html#2594
+    const_sort::const_quicksort(self, const |a, b| compare(a, b) == Ordering::Less);
+  }
+  #[inline]
+  fn const_sort_unstable_by_key<K, F>(&mut self, mut f: F)
+  where
+    F: ~const FnMut(&T) -> K + ~const Destruct,
+    K: Ord + ~const PartialOrd + ~const Destruct,
+  {
+    // https://doc.rust-lang.org/nightly/src/core/slice/mod.rs.html#2632
+    const_sort::const_quicksort(self, const |a, b| f(a).lt(&f(b)));
+  }
+
+  fn const_sort_unstable_by_cached_key<K, F>(
+    &mut self,
+    mut f: F,
+    buffer: &mut [MaybeUninit<(K, u32)>],
+  ) where
+    F: ~const FnMut(&T) -> K + ~const Destruct,
+    K: Ord + Copy + ~const PartialOrd,
+  {
+    let len = self.len();
+    assert!(buffer.len() >= len, "buffer is shorter than the slice");
+    let Ok(len) = u32::try_from(len) else {
+      panic!("slice is too long for u32 indices")
+    };
+    let buffer = &mut buffer[..len as usize];
+    let mut i = 0;
+    while i < len {
+      buffer[i as usize].write((f(&self[i as usize]), i));
+      i += 1;
+    }
+    const_sort::const_quicksort(
+      buffer,
+      // SAFETY: All elements of `buffer` were initialized above.
+      const |a: &MaybeUninit<(K, u32)>, b: &MaybeUninit<(K, u32)>| unsafe {
+        a.assume_init_ref().0.lt(&b.assume_init_ref().0)
+      },
+    );
+
+    // Move the element at `buffer[j].1` to `j`. Every placed element gets its own index in the
+    // buffer, so the cycles are only followed once.
+    let mut i = 0;
+    while i < len {
+      let mut j = i;
+      loop {
+        // SAFETY: All elements of `buffer` are initialized.
+        let (key, source) = unsafe { *buffer[j as usize].assume_init_ref() };
+        buffer[j as usize].write((key, j));
+        if source == i {
+          break;
+        }
+        self.swap(j as usize, source as usize);
+        j = source;
+      }
+      i += 1;
+    }
+  }
+
+  #[inline]
+  fn const_select_nth_unstable(&mut self, index: usize) -> (&mut [T], &mut T, &mut [T])
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    // https://doc.rust-lang.org/nightly/src/core/slice/mod.rs.html#2678
+    const_sort::const_partition_at_index(self, index, PartialOrd::lt)
+  }
+  #[inline]
+  fn const_select_nth_unstable_by<F>(
+    &mut self,
+    index: usize,
+    compare: F,
+  ) -> (&mut [T], &mut T, &mut [T])
+  where
+    F: ~const FnMut(&T, &T) -> Ordering + ~const Destruct,
+  {
+    // https://doc.rust-lang.org/nightly/src/core/slice/mod.rs.html#2725
+    const_sort::const_partition_at_index_by(self, index, compare)
+  }
+  #[inline]
+  fn const_select_nth_unstable_by_key<K, F>(
+    &mut self,
+    index: usize,
+    f: F,
+  ) -> (&mut [T], &mut T, &mut [T])
+  where
+    F: ~const FnMut(&T) -> K + ~const Destruct,
+    K: Ord + ~const PartialOrd + ~const Destruct,
+  {
+    // https://doc.rust-lang.org/nightly/src/core/slice/mod.rs.html#2776
+    const_sort::const_partition_at_index_by_key(self, index, f)
+  }
+
+  fn const_order_summary(&mut self) -> OrderSummary<T>
+  where
+    T: ~const PartialOrd + Ord + Copy,
+  {
+    assert!(!self.is_empty(), "order summary of an empty slice");
+    let last = self.len() - 1;
+    let q1_index = last / 4;
+    let median_index = last / 2;
+    // `3 * last / 4` without overflowing.
+    let q3_index = last / 4 * 3 + last % 4 * 3 / 4;
+
+    let (lower, median, upper) =
+      const_sort::const_partition_at_index(self, median_index, PartialOrd::lt);
+    let median = *median;
+
+    let (min, q1) = if q1_index == median_index {
+      (median, median)
+    } else {
+      let (lower, q1, _) = const_sort::const_partition_at_index(lower, q1_index, PartialOrd::lt);
+      let q1 = *q1;
+      if lower.is_empty() {
+        (q1, q1)
+      } else {
+        (
+          *const_sort::const_partition_at_index(lower, 0, PartialOrd::lt).1,
+          q1,
+        )
       }
-      i += 1;
+    };
+
+    let (q3, upper) = if q3_index == median_index {
+      (median, upper)
+    } else {
+      let (_, q3, upper) =
+        const_sort::const_partition_at_index(upper, q3_index - median_index - 1, PartialOrd::lt);
+      (*q3, upper)
+    };
+    let max = if upper.is_empty() {
+      q3
+    } else {
+      let upper_last = upper.len() - 1;
+      *const_sort::const_partition_at_index(upper, upper_last, PartialOrd::lt).1
+    };
+
+    OrderSummary {
+      min,
+      q1,
+      median,
+      q3,
+      max,
     }
-    true
+  }
+
+  #[inline]
+  fn const_is_sorted(&self) -> bool
+  where
+    T: ~const PartialOrd,
+  {
+    is_sorted_by_le(self, PartialOrd::le)
+  }
+  fn const_is_sorted_by<F>(&self, mut compare: F) -> bool
+  where
+    F: ~const FnMut(&T, &T) -> Option<Ordering> + ~const Destruct,
+  {
+    // https://doc.rust-lang.org/nightly/src/core/iter/traits/iterator.rs.html#3794
+    is_sorted_by_le(self, const |a, b| {
+      matches!(compare(a, b), Some(Ordering::Less | Ordering::Equal))
+    })
+  }
+  fn const_is_sorted_by_cmp<F>(&self, mut compare: F) -> bool
+  where
+    F: ~const FnMut(&T, &T) -> Ordering + ~const Destruct,
+  {
+    is_sorted_by_le(self, const |a, b| {
+      !matches!(compare(a, b), Ordering::Greater)
+    })
   }
   #[inline]
   fn const_is_sorted_by_key<F, K>(&self, mut f: F) -> bool
@@ -506,6 +1981,623 @@ impl<T> const ConstSliceSortExt<T> for [T] {
     F: ~const FnMut(&T) -> K + ~const Destruct,
     K: ~const PartialOrd + ~const Destruct,
   {
-    self.const_is_sorted_by(const |a, b| f(a).partial_cmp(&f(b)))
+    is_sorted_by_le(self, const |a, b| f(a).le(&f(b)))
+  }
+
+  fn const_insertion_index(&self, x: &T) -> usize
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    const_sort::const_bisect(0, self.len(), const |i: usize| self[i].lt(x))
+  }
+  fn const_insertion_index_last(&self, x: &T) -> usize
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    const_sort::const_bisect(0, self.len(), const |i: usize| !x.lt(&self[i]))
+  }
+  fn const_last_index_where<P>(&self, mut pred: P) -> Option<usize>
+  where
+    P: ~const FnMut(&T) -> bool + ~const Destruct,
+  {
+    // `pred` holds at `low` and fails for every index from `high` on.
+    let mut high = self.len();
+    let mut step = 1;
+    let mut low = loop {
+      if high == 0 {
+        return None;
+      }
+      let probe = if high > step { high - step } else { 0 };
+      if pred(&self[probe]) {
+        break probe;
+      }
+      high = probe;
+      step = step.saturating_mul(2);
+    };
+
+    while high - low > 1 {
+      let mid = low + (high - low) / 2;
+      if pred(&self[mid]) {
+        low = mid;
+      } else {
+        high = mid;
+      }
+    }
+    Some(low)
+  }
+  #[inline]
+  fn const_rfind_sorted<P>(&self, pred: P) -> Option<&T>
+  where
+    P: ~const FnMut(&T) -> bool + ~const Destruct,
+  {
+    self
+      .const_last_index_where(pred)
+      .map(const |index: usize| &self[index])
+  }
+  fn const_unique_count(&self) -> usize
+  where
+    T: ~const PartialEq,
+  {
+    if self.is_empty() {
+      return 0;
+    }
+    let mut count = 1;
+    let mut i = 1;
+    while i < self.len() {
+      if self[i] != self[i - 1] {
+        count += 1;
+      }
+      i += 1;
+    }
+    count
+  }
+  fn const_value_counts(&self, values: &mut [T], counts: &mut [usize]) -> usize
+  where
+    T: ~const PartialEq + Copy,
+  {
+    let mut n = 0;
+    let mut i = 0;
+    while i < self.len() {
+      let mut end = i + 1;
+      while end < self.len() && self[end] == self[i] {
+        end += 1;
+      }
+      assert!(
+        n < values.len() && n < counts.len(),
+        "value count buffers are too short"
+      );
+      values[n] = self[i];
+      counts[n] = end - i;
+      n += 1;
+      i = end;
+    }
+    n
+  }
+
+  fn const_nearest_sorted(&self, x: &T) -> usize
+  where
+    T: ~const PartialOrd + Ord + ~const Sub<Output = T> + Copy,
+  {
+    assert!(!self.is_empty(), "nearest element of an empty slice");
+    let i = self.const_insertion_index(x);
+    if i == 0 {
+      return 0;
+    }
+    if i == self.len() {
+      return i - 1;
+    }
+    // `lo < x <= hi`. The distances are compared such that every subtraction stays in range, even
+    // for signed types whose elements lie on both sides of zero.
+    let (x, lo, hi) = (*x, self[i - 1], self[i]);
+    // `T` is only known to implement `Sub`, so zero is the difference of two equal values.
+    #[allow(clippy::eq_op)]
+    let zero = x - x;
+    let hi_closer = if x.lt(&zero) {
+      // `lo < x < 0`, so `x - lo` fits, and `hi - (x - lo)` lies between `lo` and `hi`.
+      (hi - (x - lo)).lt(&x)
+    } else if lo.lt(&zero) {
+      // `lo < 0 <= x <= hi`, so `hi - x` fits, and `x - (hi - x)` is at least `-MAX`.
+      lo.lt(&(x - (hi - x)))
+    } else {
+      // `0 <= lo < x <= hi`.
+      (hi - x).lt(&(x - lo))
+    };
+    if hi_closer {
+      i
+    } else {
+      i - 1
+    }
+  }
+  fn const_nearest_sorted_by<F, D>(&self, x: &T, mut distance: F) -> usize
+  where
+    T: ~const PartialOrd + Ord,
+    F: ~const FnMut(&T, &T) -> D + ~const Destruct,
+    D: ~const PartialOrd + Ord + ~const Destruct,
+  {
+    assert!(!self.is_empty(), "nearest element of an empty slice");
+    let i = self.const_insertion_index(x);
+    if i == 0 {
+      return 0;
+    }
+    if i == self.len() {
+      return i - 1;
+    }
+    if distance(x, &self[i]).lt(&distance(x, &self[i - 1])) {
+      i
+    } else {
+      i - 1
+    }
+  }
+
+  #[inline]
+  fn const_partition_by_median(&mut self) -> (&mut [T], &mut T, &mut [T])
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    let index = self.len() / 2;
+    self.const_select_nth_unstable(index)
+  }
+  fn const_binary_search_with_hint(&self, x: &T, hint: usize) -> Result<usize, usize>
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    let len = self.len();
+    if len == 0 {
+      return Err(0);
+    }
+    let hint = if hint < len { hint } else { len - 1 };
+
+    // The first element not less than `x` is in `low..=high`.
+    let mut low;
+    let mut high;
+    if self[hint].lt(x) {
+      low = hint + 1;
+      high = len;
+      let mut step = 1;
+      // `step < len - hint` can not overflow, unlike `hint + step < len`, even for slices of
+      // zero-sized types that are `usize::MAX` long.
+      while step < len - hint {
+        let probe = hint + step;
+        if !self[probe].lt(x) {
+          high = probe;
+          break;
+        }
+        low = probe + 1;
+        step = step.saturating_mul(2);
+      }
+    } else if x.lt(&self[hint]) {
+      low = 0;
+      high = hint;
+      let mut step = 1;
+      while step <= hint {
+        let probe = hint - step;
+        if self[probe].lt(x) {
+          low = probe + 1;
+          break;
+        }
+        high = probe;
+        step = step.saturating_mul(2);
+      }
+    } else {
+      return Ok(hint);
+    }
+
+    while low < high {
+      let mid = low + (high - low) / 2;
+      if self[mid].lt(x) {
+        low = mid + 1;
+      } else {
+        high = mid;
+      }
+    }
+    if low < len && !x.lt(&self[low]) {
+      Ok(low)
+    } else {
+      Err(low)
+    }
+  }
+
+  #[inline]
+  fn const_sort_unstable_by_const_key(&mut self)
+  where
+    T: ~const ConstKey,
+    T::Key: ~const PartialOrd + Ord + ~const Destruct,
+  {
+    const_sort::const_quicksort(self, const |a: &T, b: &T| {
+      a.as_ord_key().lt(&b.as_ord_key())
+    });
+  }
+  fn const_search_by_const_key(&self, key: &T::Key) -> Result<usize, usize>
+  where
+    T: ~const ConstKey,
+    T::Key: ~const PartialOrd + Ord + ~const Destruct,
+  {
+    let mut low = 0;
+    let mut high = self.len();
+    while low < high {
+      let mid = low + (high - low) / 2;
+      let mid_key = self[mid].as_ord_key();
+      if mid_key.lt(key) {
+        low = mid + 1;
+      } else if key.lt(&mid_key) {
+        high = mid;
+      } else {
+        return Ok(mid);
+      }
+    }
+    Err(low)
+  }
+
+  fn const_min_by_key<F, K>(&self, mut f: F) -> Option<&T>
+  where
+    F: ~const FnMut(&T) -> K + ~const Destruct,
+    K: ~const PartialOrd + Ord + ~const Destruct,
+  {
+    if self.is_empty() {
+      return None;
+    }
+    let mut best = 0;
+    let mut best_key = f(&self[0]);
+    let mut i = 1;
+    while i < self.len() {
+      let key = f(&self[i]);
+      if key.lt(&best_key) {
+        best = i;
+        best_key = key;
+      }
+      i += 1;
+    }
+    Some(&self[best])
+  }
+  fn const_max_by_key<F, K>(&self, mut f: F) -> Option<&T>
+  where
+    F: ~const FnMut(&T) -> K + ~const Destruct,
+    K: ~const PartialOrd + Ord + ~const Destruct,
+  {
+    if self.is_empty() {
+      return None;
+    }
+    let mut best = 0;
+    let mut best_key = f(&self[0]);
+    let mut i = 1;
+    while i < self.len() {
+      let key = f(&self[i]);
+      if !key.lt(&best_key) {
+        best = i;
+        best_key = key;
+      }
+      i += 1;
+    }
+    Some(&self[best])
+  }
+  #[inline]
+  fn const_contains(&self, x: &T) -> bool
+  where
+    T: ~const PartialEq,
+  {
+    self.const_position(x).is_some()
+  }
+  fn const_position(&self, x: &T) -> Option<usize>
+  where
+    T: ~const PartialEq,
+  {
+    let mut i = 0;
+    while i < self.len() {
+      if self[i] == *x {
+        return Some(i);
+      }
+      i += 1;
+    }
+    None
+  }
+  fn const_select_range_unstable(&mut self, lo: usize, hi: usize) -> &mut [T]
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    assert!(lo <= hi && hi < self.len(), "selection range out of bounds");
+    let (_, _, greater) = self.const_select_nth_unstable(lo);
+    if hi > lo {
+      // `greater` starts at `lo + 1`, so `hi` is at `hi - lo - 1` in it.
+      let (band, _, _) = greater.const_select_nth_unstable(hi - lo - 1);
+      band.const_sort_unstable();
+    }
+    &mut self[lo..=hi]
+  }
+  fn const_trim_unstable(&mut self, trim: usize) -> &mut [T]
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    let len = self.len();
+    assert!(
+      matches!(trim.checked_mul(2), Some(removed) if removed < len),
+      "trimming removes all elements"
+    );
+    let hi = len - trim - 1;
+    let (_, _, greater) = self.const_select_nth_unstable(trim);
+    if hi > trim {
+      // `greater` starts at `trim + 1`, so `hi` is at `hi - trim - 1` in it.
+      greater.const_select_nth_unstable(hi - trim - 1);
+    }
+    &mut self[trim..=hi]
+  }
+  fn const_trimmed_mean_bounds(&mut self, trim: usize) -> (&T, &T)
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    let kept: &[T] = self.const_trim_unstable(trim);
+    (&kept[0], &kept[kept.len() - 1])
+  }
+  fn const_sort_unstable_by_key_counted<K, F>(&mut self, mut f: F) -> usize
+  where
+    F: ~const FnMut(&T) -> K + ~const Destruct,
+    K: Ord + ~const PartialOrd + ~const Destruct,
+  {
+    let mut calls = 0;
+    const_sort::const_quicksort(self, const |a, b| {
+      calls += 2;
+      f(a).lt(&f(b))
+    });
+    calls
+  }
+  fn const_sort_segments(&mut self, boundaries: &[usize])
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    let mut i = 1;
+    while i < boundaries.len() {
+      let (start, end) = (boundaries[i - 1], boundaries[i]);
+      assert!(
+        start <= end && end <= self.len(),
+        "segment boundaries are out of order or out of bounds"
+      );
+      self[start..end].const_sort_unstable();
+      i += 1;
+    }
+  }
+  fn const_retain<P>(&mut self, mut pred: P) -> usize
+  where
+    P: ~const FnMut(&T) -> bool + ~const Destruct,
+  {
+    let mut len = 0;
+    let mut i = 0;
+    while i < self.len() {
+      if pred(&self[i]) {
+        // `self[len..i]` only holds removed elements, so this keeps the retained ones in order.
+        self.swap(len, i);
+        len += 1;
+      }
+      i += 1;
+    }
+    len
+  }
+  fn const_is_sorted_desc(&self) -> bool
+  where
+    T: ~const PartialOrd,
+  {
+    is_sorted_by_le(self, PartialOrd::ge)
+  }
+  fn const_insertion_index_desc(&self, x: &T) -> usize
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    const_sort::const_bisect(0, self.len(), const |i: usize| x.lt(&self[i]))
+  }
+  fn const_binary_search_desc(&self, x: &T) -> Result<usize, usize>
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    let index = self.const_insertion_index_desc(x);
+    if index < self.len() && !self[index].lt(x) {
+      Ok(index)
+    } else {
+      Err(index)
+    }
+  }
+  fn const_min_adjacent_gap(&self) -> Option<(usize, T)>
+  where
+    T: ~const PartialOrd + Ord + ~const Sub<Output = T> + Copy,
+  {
+    self.const_min_adjacent_gap_by(const |a: &T, b: &T| *b - *a)
+  }
+  fn const_min_adjacent_gap_by<F, D>(&self, mut distance: F) -> Option<(usize, D)>
+  where
+    F: ~const FnMut(&T, &T) -> D + ~const Destruct,
+    D: ~const PartialOrd + Ord + ~const Destruct,
+  {
+    if self.len() < 2 {
+      return None;
+    }
+    let mut min_index = 0;
+    let mut min = distance(&self[0], &self[1]);
+    let mut i = 1;
+    while i + 1 < self.len() {
+      let gap = distance(&self[i], &self[i + 1]);
+      if gap.lt(&min) {
+        min_index = i;
+        min = gap;
+      }
+      i += 1;
+    }
+    Some((min_index, min))
+  }
+  fn const_sort_unstable_with(&mut self, algorithm: const_sort::Algorithm)
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    const_sort::const_sort_with(algorithm, self, PartialOrd::lt);
+  }
+  fn const_apply_permutation(&mut self, perm: &[usize]) {
+    assert_permutation(self.len(), perm);
+    let mut leader = 0;
+    while leader < perm.len() {
+      if is_cycle_leader(perm, leader) {
+        // Pull every element of the cycle one step towards the leader.
+        let mut i = leader;
+        while perm[i] != leader {
+          self.swap(i, perm[i]);
+          i = perm[i];
+        }
+      }
+      leader += 1;
+    }
+  }
+  fn const_scatter_permutation(&mut self, perm: &[usize]) {
+    assert_permutation(self.len(), perm);
+    let mut leader = 0;
+    while leader < perm.len() {
+      if is_cycle_leader(perm, leader) {
+        // Push the element at the leader along the cycle, carrying the displaced element back.
+        let mut i = perm[leader];
+        while i != leader {
+          self.swap(leader, i);
+          i = perm[i];
+        }
+      }
+      leader += 1;
+    }
+  }
+  fn const_is_sorted_rotated(&self) -> Option<usize>
+  where
+    T: ~const PartialOrd,
+  {
+    let len = self.len();
+    let mut rotation = 0;
+    let mut i = 1;
+    while i < len {
+      if self[i].lt(&self[i - 1]) {
+        // A rotated sorted slice descends at most once, at the rotation point.
+        if rotation != 0 {
+          return None;
+        }
+        rotation = i;
+      }
+      i += 1;
+    }
+    if rotation != 0 && self[0].lt(&self[len - 1]) {
+      return None;
+    }
+    Some(rotation)
+  }
+  fn const_binary_search_rotated(&self, rotation: usize, x: &T) -> Result<usize, usize>
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    // `self[rotation..]` holds the smaller elements and `self[..rotation]` the larger ones.
+    let smaller = &self[rotation..];
+    let index = smaller.const_insertion_index(x);
+    if index < smaller.len() {
+      return if x.lt(&smaller[index]) {
+        Err(rotation + index)
+      } else {
+        Ok(rotation + index)
+      };
+    }
+    let index = self[..rotation].const_insertion_index(x);
+    if index < rotation && !x.lt(&self[index]) {
+      Ok(index)
+    } else if index == 0 {
+      // `x` goes between the smaller and the larger elements, which is at the end of the slice.
+      Err(self.len())
+    } else {
+      Err(index)
+    }
+  }
+  fn const_sort(&mut self)
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    const_sort::const_merge_sort(self, PartialOrd::lt);
+  }
+  fn const_sort_by<F>(&mut self, mut compare: F)
+  where
+    F: ~const FnMut(&T, &T) -> Ordering + ~const Destruct,
+  {
+    const_sort::const_merge_sort(self, const |a, b| compare(a, b) == Ordering::Less);
+  }
+  fn const_binary_search(&self, x: &T) -> Result<usize, usize>
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    let mut low = 0;
+    let mut high = self.len();
+    while low < high {
+      let mid = low + (high - low) / 2;
+      if self[mid].lt(x) {
+        low = mid + 1;
+      } else if x.lt(&self[mid]) {
+        high = mid;
+      } else {
+        return Ok(mid);
+      }
+    }
+    Err(low)
+  }
+  fn const_binary_search_by<F>(&self, mut f: F) -> Result<usize, usize>
+  where
+    F: ~const FnMut(&T) -> Ordering + ~const Destruct,
+  {
+    let mut low = 0;
+    let mut high = self.len();
+    while low < high {
+      let mid = low + (high - low) / 2;
+      match f(&self[mid]) {
+        Ordering::Less => low = mid + 1,
+        Ordering::Greater => high = mid,
+        Ordering::Equal => return Ok(mid),
+      }
+    }
+    Err(low)
+  }
+  fn const_binary_search_branchless(&self, x: &T) -> Result<usize, usize>
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    if self.is_empty() {
+      return Err(0);
+    }
+    // The first element not less than `x` is in `base..=base + size`.
+    let mut base = 0;
+    let mut size = self.len();
+    while size > 1 {
+      let half = size / 2;
+      base += usize::from(self[base + half - 1].lt(x)) * half;
+      size -= half;
+    }
+    base += usize::from(self[base].lt(x));
+    if base < self.len() && !x.lt(&self[base]) {
+      Ok(base)
+    } else {
+      Err(base)
+    }
+  }
+  fn const_partition_point<P>(&self, mut pred: P) -> usize
+  where
+    P: ~const FnMut(&T) -> bool + ~const Destruct,
+  {
+    const_sort::const_bisect(0, self.len(), const |i: usize| pred(&self[i]))
+  }
+  fn const_exponential_search(&self, x: &T) -> Result<usize, usize>
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    let index = gallop(self.len(), const |i: usize| self[i].lt(x));
+    if index < self.len() && !x.lt(&self[index]) {
+      Ok(index)
+    } else {
+      Err(index)
+    }
+  }
+  fn const_exponential_search_by<F>(&self, mut f: F) -> Result<usize, usize>
+  where
+    F: ~const FnMut(&T) -> Ordering + ~const Destruct,
+  {
+    let index = gallop(self.len(), const |i: usize| {
+      matches!(f(&self[i]), Ordering::Less)
+    });
+    if index < self.len() && matches!(f(&self[index]), Ordering::Equal) {
+      Ok(index)
+    } else {
+      Err(index)
+    }
   }
 }
@@ -0,0 +1,27 @@
+#[const_trait]
+/// Bound for types that the sorts and searches of this crate accept in const contexts.
+///
+/// Generic const code can write `T: ~const ConstSortable` instead of spelling out the bounds
+/// this crate requires of its elements, which may change between nightly versions. It is
+/// implemented for every type that satisfies them.
+///
+/// Sorting never drops elements, so no `Destruct` bound is needed on `T`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::{ConstSliceSortExt, ConstSortable};
+///
+/// const fn sorted<T: ~const ConstSortable, const N: usize>(mut arr: [T; N]) -> [T; N] {
+///   arr.const_sort_unstable();
+///   arr
+/// }
+///
+/// const V: [u8; 4] = sorted([3, 1, 4, 1]);
+/// assert_eq!(V, [1, 1, 3, 4]);
+/// ```
+pub trait ConstSortable: ~const PartialOrd + Ord {}
+
+impl<T> const ConstSortable for T where T: ~const PartialOrd + Ord {}
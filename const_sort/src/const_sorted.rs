@@ -0,0 +1,161 @@
+use core::ops::Deref;
+
+use crate::{ConstSliceSortExt, ConstSortedSlice, OrderSummary};
+
+/// An array that is guaranteed to be sorted in ascending order.
+///
+/// Order statistics like [`select_nth`](Self::select_nth), [`min`](Self::min),
+/// [`max`](Self::max) and [`median`](Self::median) are answered by direct indexing in *O*(1)
+/// instead of running a selection algorithm. The array is only accessible through shared
+/// references, see the [`Deref`] implementation, so the order can not be broken.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::ConstSorted;
+///
+/// const S: ConstSorted<i32, 5> = ConstSorted::new([-5, 4, 1, -3, 2]);
+/// const MEDIAN: Option<&i32> = S.median();
+/// assert_eq!(MEDIAN, Some(&1));
+/// assert_eq!(S.into_inner(), [-5, -3, 1, 2, 4]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConstSorted<T, const N: usize>([T; N]);
+
+impl<T, const N: usize> ConstSorted<T, N> {
+  /// Sorts `arr` with [`ConstSliceSortExt::const_sort_unstable`] and wraps it.
+  #[must_use]
+  pub const fn new(mut arr: [T; N]) -> Self
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    arr.const_sort_unstable();
+    Self(arr)
+  }
+
+  /// Wraps `arr` after checking that it is sorted in ascending order.
+  ///
+  /// This is *O*(*N*), so prefer it over [`new`](Self::new) for large arrays that are already
+  /// sorted, e.g. the output of a generator.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `arr` is not sorted.
+  #[must_use]
+  pub const fn from_sorted_checked(arr: [T; N]) -> Self
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    assert!(arr.const_is_sorted(), "array is not sorted");
+    Self(arr)
+  }
+
+  /// Wraps `arr` without checking that it is sorted.
+  ///
+  /// This is *O*(1), for huge generated arrays where even the check of
+  /// [`from_sorted_checked`](Self::from_sorted_checked) is too expensive.
+  ///
+  /// # Safety
+  ///
+  /// `arr` must be sorted in ascending order. The methods of `ConstSorted` return wrong results
+  /// otherwise, and unsafe code may rely on the order of [`as_slice`](Self::as_slice).
+  #[must_use]
+  pub const unsafe fn from_sorted_unchecked(arr: [T; N]) -> Self {
+    Self(arr)
+  }
+
+  /// Returns the sorted elements as a slice.
+  #[must_use]
+  pub const fn as_slice(&self) -> &[T] {
+    &self.0
+  }
+
+  /// Returns the sorted elements as a [`ConstSortedSlice`], without checking the order again.
+  #[must_use]
+  pub const fn as_sorted_slice(&self) -> ConstSortedSlice<'_, T> {
+    // SAFETY: The array of a `ConstSorted` is always sorted.
+    unsafe { ConstSortedSlice::from_sorted_unchecked(&self.0) }
+  }
+
+  /// Returns the sorted array.
+  #[must_use]
+  pub const fn into_inner(self) -> [T; N]
+  where
+    T: Copy,
+  {
+    self.0
+  }
+
+  /// Returns the elements before `index`, the element at `index` and the elements after `index`.
+  ///
+  /// This is the result of
+  /// [`const_select_nth_unstable`](ConstSliceSortExt::const_select_nth_unstable) on already
+  /// sorted data, in *O*(1).
+  ///
+  /// # Panics
+  ///
+  /// Panics when `index >= N`.
+  #[must_use]
+  pub const fn select_nth(&self, index: usize) -> (&[T], &T, &[T]) {
+    let (left, right) = self.0.split_at(index);
+    let (nth, right) = right.split_first().unwrap();
+    (left, nth, right)
+  }
+
+  /// Returns the smallest element, or `None` if the array is empty.
+  #[must_use]
+  pub const fn min(&self) -> Option<&T> {
+    self.0.first()
+  }
+
+  /// Returns the largest element, or `None` if the array is empty.
+  #[must_use]
+  pub const fn max(&self) -> Option<&T> {
+    self.0.last()
+  }
+
+  /// Returns the lower median, i.e. the element at index `(N - 1) / 2`, or `None` if the array is
+  /// empty.
+  #[must_use]
+  pub const fn median(&self) -> Option<&T> {
+    if N == 0 {
+      None
+    } else {
+      Some(&self.0[(N - 1) / 2])
+    }
+  }
+
+  /// Returns the minimum, the quartiles and the maximum.
+  ///
+  /// This is the result of [`ConstSliceSortExt::const_order_summary`] on already sorted data, in
+  /// *O*(1).
+  ///
+  /// # Panics
+  ///
+  /// Panics when the array is empty.
+  #[must_use]
+  pub const fn order_summary(&self) -> OrderSummary<T>
+  where
+    T: Copy,
+  {
+    assert!(N != 0, "order summary of an empty slice");
+    let last = N - 1;
+    OrderSummary {
+      min: self.0[0],
+      q1: self.0[last / 4],
+      median: self.0[last / 2],
+      q3: self.0[last / 4 * 3 + last % 4 * 3 / 4],
+      max: self.0[last],
+    }
+  }
+}
+
+impl<T, const N: usize> const Deref for ConstSorted<T, N> {
+  type Target = [T];
+
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
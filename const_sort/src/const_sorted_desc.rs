@@ -0,0 +1,145 @@
+use core::ops::Deref;
+
+use crate::const_sort::const_quicksort;
+use crate::ConstSliceSortExt;
+
+/// An array that is guaranteed to be sorted in descending order.
+///
+/// This is the descending counterpart of [`ConstSorted`](crate::ConstSorted) for tables that are
+/// stored from high to low, e.g. priority lists, without negating or wrapping their keys. The
+/// largest element comes first, and [`binary_search`](Self::binary_search) and
+/// [`insertion_index`](Self::insertion_index) search in descending order. The array is only
+/// accessible through shared references, see the [`Deref`] implementation, so the order can not be
+/// broken.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::ConstSortedDesc;
+///
+/// const PRIORITIES: ConstSortedDesc<u8, 5> = ConstSortedDesc::new([30, 200, 5, 120, 120]);
+/// const HIGHEST: Option<&u8> = PRIORITIES.max();
+/// assert_eq!(HIGHEST, Some(&200));
+/// assert_eq!(PRIORITIES.binary_search(&120), Ok(1));
+/// assert_eq!(PRIORITIES.into_inner(), [200, 120, 120, 30, 5]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConstSortedDesc<T, const N: usize>([T; N]);
+
+impl<T, const N: usize> ConstSortedDesc<T, N> {
+  /// Sorts `arr` in descending order and wraps it.
+  #[must_use]
+  pub const fn new(mut arr: [T; N]) -> Self
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    const_quicksort(&mut arr, const |a: &T, b: &T| b.lt(a));
+    Self(arr)
+  }
+
+  /// Wraps `arr` after checking that it is sorted in descending order.
+  ///
+  /// This is *O*(*N*), so prefer it over [`new`](Self::new) for large arrays that are already
+  /// sorted.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `arr` is not sorted in descending order.
+  #[must_use]
+  pub const fn from_sorted_checked(arr: [T; N]) -> Self
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    assert!(
+      arr.const_is_sorted_desc(),
+      "array is not sorted in descending order"
+    );
+    Self(arr)
+  }
+
+  /// Wraps `arr` without checking that it is sorted in descending order.
+  ///
+  /// # Safety
+  ///
+  /// `arr` must be sorted in descending order. The methods of `ConstSortedDesc` return wrong
+  /// results otherwise, and unsafe code may rely on the order of [`as_slice`](Self::as_slice).
+  #[must_use]
+  pub const unsafe fn from_sorted_unchecked(arr: [T; N]) -> Self {
+    Self(arr)
+  }
+
+  /// Returns the sorted elements as a slice.
+  #[must_use]
+  pub const fn as_slice(&self) -> &[T] {
+    &self.0
+  }
+
+  /// Returns the sorted array.
+  #[must_use]
+  pub const fn into_inner(self) -> [T; N]
+  where
+    T: Copy,
+  {
+    self.0
+  }
+
+  /// Returns the largest element, or `None` if the array is empty.
+  #[must_use]
+  pub const fn max(&self) -> Option<&T> {
+    self.0.first()
+  }
+
+  /// Returns the smallest element, or `None` if the array is empty.
+  #[must_use]
+  pub const fn min(&self) -> Option<&T> {
+    self.0.last()
+  }
+
+  /// Returns the lower median, i.e. the same element as
+  /// [`ConstSorted::median`](crate::ConstSorted::median) of the ascending array, or `None` if the
+  /// array is empty.
+  #[must_use]
+  pub const fn median(&self) -> Option<&T> {
+    if N == 0 {
+      None
+    } else {
+      Some(&self.0[N / 2])
+    }
+  }
+
+  /// Binary searches the array for `x`.
+  ///
+  /// See [`ConstSliceSortExt::const_binary_search_desc`].
+  ///
+  /// # Errors
+  ///
+  /// Returns the insertion index of `x` as [`Err`] if the array does not contain `x`.
+  pub const fn binary_search(&self, x: &T) -> Result<usize, usize>
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    self.0.const_binary_search_desc(x)
+  }
+
+  /// Returns the first index at which `x` could be inserted while keeping the array sorted in
+  /// descending order.
+  ///
+  /// See [`ConstSliceSortExt::const_insertion_index_desc`].
+  #[must_use]
+  pub const fn insertion_index(&self, x: &T) -> usize
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    self.0.const_insertion_index_desc(x)
+  }
+}
+
+impl<T, const N: usize> const Deref for ConstSortedDesc<T, N> {
+  type Target = [T];
+
+  fn deref(&self) -> &Self::Target {
+    &self.0
+  }
+}
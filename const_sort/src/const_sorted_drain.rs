@@ -0,0 +1,121 @@
+/// Yields the elements of an array one at a time in ascending order.
+///
+/// The array is turned into a binary min-heap once in *O*(*n*). Every call to
+/// [`next_smallest`](Self::next_smallest) then takes *O*(log(*n*)), so consuming only the first
+/// few elements in sorted order is cheaper than sorting the whole array. This works inside const
+/// fn loops, which can not use iterators.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::ConstSortedDrain;
+///
+/// // Sum of the three smallest elements.
+/// const SUM: u32 = {
+///   let mut drain = ConstSortedDrain::new([7, 3, 9, 1, 4]);
+///   let mut sum = 0;
+///   let mut i = 0;
+///   while i < 3 {
+///     if let Some(x) = drain.next_smallest() {
+///       sum += x;
+///     }
+///     i += 1;
+///   }
+///   sum
+/// };
+/// assert_eq!(SUM, 8);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ConstSortedDrain<T, const N: usize> {
+  /// Binary heap respecting the invariant `parent <= child` in `heap[..len]`.
+  heap: [T; N],
+  len: usize,
+}
+
+impl<T, const N: usize> ConstSortedDrain<T, N> {
+  /// Builds the heap from `arr` in linear time.
+  #[must_use]
+  pub const fn new(arr: [T; N]) -> Self
+  where
+    T: ~const PartialOrd + Ord + Copy,
+  {
+    let mut drain = Self { heap: arr, len: N };
+    // for i in (0..N / 2).rev() {
+    let mut i = N / 2;
+    while i > 0 {
+      i -= 1;
+      drain.sift_down(i);
+    }
+    drain
+  }
+
+  /// Returns the number of remaining elements.
+  #[must_use]
+  pub const fn len(&self) -> usize {
+    self.len
+  }
+
+  /// Returns `true` if all elements were drained.
+  #[must_use]
+  pub const fn is_empty(&self) -> bool {
+    self.len == 0
+  }
+
+  /// Returns the smallest remaining element without removing it, or `None` if all elements were
+  /// drained.
+  #[must_use]
+  pub const fn peek_smallest(&self) -> Option<&T> {
+    if self.len == 0 {
+      None
+    } else {
+      Some(&self.heap[0])
+    }
+  }
+
+  /// Removes and returns the smallest remaining element, or `None` if all elements were drained.
+  ///
+  /// Equal elements are returned in an unspecified order.
+  pub const fn next_smallest(&mut self) -> Option<T>
+  where
+    T: ~const PartialOrd + Ord + Copy,
+  {
+    if self.len == 0 {
+      return None;
+    }
+    let smallest = self.heap[0];
+    self.len -= 1;
+    self.heap.swap(0, self.len);
+    self.sift_down(0);
+    Some(smallest)
+  }
+
+  const fn sift_down(&mut self, mut node: usize)
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    loop {
+      // `node` has no children, checked before computing them to avoid overflow.
+      if node >= self.len / 2 {
+        break;
+      }
+      // Children of `node`.
+      let mut child = 2 * node + 1;
+
+      // Choose the smaller child.
+      if child + 1 < self.len && self.heap[child + 1].lt(&self.heap[child]) {
+        child += 1;
+      }
+
+      // Stop if the invariant holds at `node`.
+      if !self.heap[child].lt(&self.heap[node]) {
+        break;
+      }
+
+      // Swap `node` with the smaller child, move one step down, and continue sifting.
+      self.heap.swap(node, child);
+      node = child;
+    }
+  }
+}
@@ -0,0 +1,127 @@
+use crate::const_sort::const_quicksort;
+
+/// A fixed-size map from keys to values, sorted by key at compile time.
+///
+/// [`new`](Self::new) sorts an array of `(key, value)` entries by key, and lookups binary search
+/// it, which is *O*(log(*N*)). The entries stay in a single array, so the map has no overhead
+/// over the entry array and can be placed in a `const` or `static` item.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::ConstSortedMap;
+///
+/// const REASONS: ConstSortedMap<u16, &str, 4> = ConstSortedMap::new([
+///   (404, "Not Found"),
+///   (200, "OK"),
+///   (503, "Service Unavailable"),
+///   (301, "Moved Permanently"),
+/// ]);
+/// const NOT_FOUND: Option<&&str> = REASONS.get(&404);
+/// assert_eq!(NOT_FOUND, Some(&"Not Found"));
+/// assert_eq!(REASONS.get(&418), None);
+/// assert_eq!(REASONS.entries()[0], (200, "OK"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConstSortedMap<K, V, const N: usize>([(K, V); N]);
+
+impl<K, V, const N: usize> ConstSortedMap<K, V, N> {
+  /// Sorts `entries` by key and wraps them.
+  ///
+  /// This is *O*(*N* \* log(*N*)).
+  ///
+  /// # Panics
+  ///
+  /// Panics if a key occurs more than once.
+  #[must_use]
+  pub const fn new(mut entries: [(K, V); N]) -> Self
+  where
+    K: ~const PartialOrd + Ord,
+  {
+    const_quicksort(&mut entries, const |a: &(K, V), b: &(K, V)| a.0.lt(&b.0));
+    let mut i = 1;
+    while i < N {
+      assert!(
+        entries[i - 1].0.lt(&entries[i].0),
+        "map keys are not unique"
+      );
+      i += 1;
+    }
+    Self(entries)
+  }
+
+  /// Returns the index of the entry of `key` in [`entries`](Self::entries), or `None` if it is
+  /// not a key of the map.
+  #[must_use]
+  pub const fn position(&self, key: &K) -> Option<usize>
+  where
+    K: ~const PartialOrd + Ord,
+  {
+    let mut low = 0;
+    let mut high = N;
+    while low < high {
+      let mid = low + (high - low) / 2;
+      if self.0[mid].0.lt(key) {
+        low = mid + 1;
+      } else if key.lt(&self.0[mid].0) {
+        high = mid;
+      } else {
+        return Some(mid);
+      }
+    }
+    None
+  }
+
+  /// Returns the value of `key`, or `None` if it is not a key of the map.
+  #[must_use]
+  pub const fn get(&self, key: &K) -> Option<&V>
+  where
+    K: ~const PartialOrd + Ord,
+  {
+    match self.position(key) {
+      Some(index) => Some(&self.0[index].1),
+      None => None,
+    }
+  }
+
+  /// Returns the stored key and the value of `key`, or `None` if it is not a key of the map.
+  #[must_use]
+  pub const fn get_key_value(&self, key: &K) -> Option<(&K, &V)>
+  where
+    K: ~const PartialOrd + Ord,
+  {
+    match self.position(key) {
+      Some(index) => Some((&self.0[index].0, &self.0[index].1)),
+      None => None,
+    }
+  }
+
+  /// Returns `true` if `key` is a key of the map.
+  #[must_use]
+  pub const fn contains_key(&self, key: &K) -> bool
+  where
+    K: ~const PartialOrd + Ord,
+  {
+    self.position(key).is_some()
+  }
+
+  /// Returns the entries sorted by key.
+  #[must_use]
+  pub const fn entries(&self) -> &[(K, V); N] {
+    &self.0
+  }
+
+  /// Returns the number of entries.
+  #[must_use]
+  pub const fn len(&self) -> usize {
+    N
+  }
+
+  /// Returns `true` if the map has no entries.
+  #[must_use]
+  pub const fn is_empty(&self) -> bool {
+    N == 0
+  }
+}
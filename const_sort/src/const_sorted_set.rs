@@ -0,0 +1,117 @@
+use crate::ConstSliceSortExt;
+
+/// A fixed-size set, sorted and checked for duplicates at compile time.
+///
+/// [`new`](Self::new) sorts the elements, so membership tests are binary searches in
+/// *O*(log(*N*)), and set comparisons like [`is_subset`](Self::is_subset) merge both sets in
+/// *O*(*N* + *M*). This suits keyword sets, opcode sets or allow lists in `no_std` code.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::ConstSortedSet;
+///
+/// const ALLOWED_BAUD_RATES: ConstSortedSet<u32, 4> =
+///   ConstSortedSet::new([115_200, 9_600, 57_600, 19_200]);
+/// const DEFAULTS: ConstSortedSet<u32, 2> = ConstSortedSet::new([9_600, 115_200]);
+///
+/// const _: () = assert!(DEFAULTS.is_subset(&ALLOWED_BAUD_RATES));
+/// assert!(ALLOWED_BAUD_RATES.contains(&57_600));
+/// assert!(!ALLOWED_BAUD_RATES.contains(&38_400));
+/// assert_eq!(ALLOWED_BAUD_RATES.as_slice(), &[9_600, 19_200, 57_600, 115_200]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConstSortedSet<T, const N: usize>([T; N]);
+
+impl<T, const N: usize> ConstSortedSet<T, N> {
+  /// Sorts `elements` and wraps them.
+  ///
+  /// This is *O*(*N* \* log(*N*)).
+  ///
+  /// # Panics
+  ///
+  /// Panics if an element occurs more than once.
+  #[must_use]
+  pub const fn new(mut elements: [T; N]) -> Self
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    elements.const_sort_unstable();
+    let mut i = 1;
+    while i < N {
+      assert!(
+        elements[i - 1].lt(&elements[i]),
+        "set elements are not unique"
+      );
+      i += 1;
+    }
+    Self(elements)
+  }
+
+  /// Returns `true` if the set contains `x`.
+  ///
+  /// This is a binary search and therefore *O*(log(*N*)).
+  #[must_use]
+  pub const fn contains(&self, x: &T) -> bool
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    self.0.const_binary_search(x).is_ok()
+  }
+
+  /// Returns `true` if every element of this set is also an element of `other`.
+  ///
+  /// This is *O*(*N* + *M*).
+  #[must_use]
+  pub const fn is_subset<const M: usize>(&self, other: &ConstSortedSet<T, M>) -> bool
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    self.intersection_count(other) == N
+  }
+
+  /// Returns the number of elements that are in both this set and `other`.
+  ///
+  /// This is *O*(*N* + *M*).
+  #[must_use]
+  pub const fn intersection_count<const M: usize>(&self, other: &ConstSortedSet<T, M>) -> usize
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    let mut count = 0;
+    let mut i = 0;
+    let mut j = 0;
+    while i < N && j < M {
+      if self.0[i].lt(&other.0[j]) {
+        i += 1;
+      } else if other.0[j].lt(&self.0[i]) {
+        j += 1;
+      } else {
+        count += 1;
+        i += 1;
+        j += 1;
+      }
+    }
+    count
+  }
+
+  /// Returns the elements in ascending order.
+  #[must_use]
+  pub const fn as_slice(&self) -> &[T] {
+    &self.0
+  }
+
+  /// Returns the number of elements.
+  #[must_use]
+  pub const fn len(&self) -> usize {
+    N
+  }
+
+  /// Returns `true` if the set has no elements.
+  #[must_use]
+  pub const fn is_empty(&self) -> bool {
+    N == 0
+  }
+}
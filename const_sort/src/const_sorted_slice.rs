@@ -0,0 +1,113 @@
+use core::ops::{Deref, Range};
+
+use crate::ConstSliceSortExt;
+
+/// A slice that is verified to be sorted in ascending order.
+///
+/// The sortedness is checked once by [`new`](Self::new), so functions that take a
+/// `ConstSortedSlice` can rely on it instead of checking it again or documenting an unspecified
+/// result for unsorted input. The searches below are binary searches and therefore
+/// *O*(log(*n*)). The slice is only accessible through shared references, see the [`Deref`]
+/// implementation.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::ConstSortedSlice;
+///
+/// const fn count_of(ids: ConstSortedSlice<u32>, id: u32) -> usize {
+///   let range = ids.equal_range(&id);
+///   range.end - range.start
+/// }
+///
+/// const IDS: ConstSortedSlice<u32> = ConstSortedSlice::new(&[1, 4, 4, 4, 9]);
+/// const FOURS: usize = count_of(IDS, 4);
+/// assert_eq!(FOURS, 3);
+/// assert!(!IDS.contains(&5));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConstSortedSlice<'a, T>(&'a [T]);
+
+impl<'a, T> ConstSortedSlice<'a, T> {
+  /// Wraps `slice` after checking that it is sorted in ascending order.
+  ///
+  /// This is *O*(*n*).
+  ///
+  /// # Panics
+  ///
+  /// Panics if `slice` is not sorted.
+  #[must_use]
+  pub const fn new(slice: &'a [T]) -> Self
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    assert!(slice.const_is_sorted(), "slice is not sorted");
+    Self(slice)
+  }
+
+  /// Wraps `slice` without checking that it is sorted.
+  ///
+  /// # Safety
+  ///
+  /// `slice` must be sorted in ascending order. The methods of `ConstSortedSlice` return wrong
+  /// results otherwise, and unsafe code may rely on the order of [`as_slice`](Self::as_slice).
+  #[must_use]
+  pub const unsafe fn from_sorted_unchecked(slice: &'a [T]) -> Self {
+    Self(slice)
+  }
+
+  /// Returns the sorted elements.
+  #[must_use]
+  pub const fn as_slice(&self) -> &'a [T] {
+    self.0
+  }
+
+  /// Binary searches for `x`.
+  ///
+  /// Behaves like [`ConstSliceSortExt::const_binary_search`]: returns [`Ok`] with the index of
+  /// any element equal to `x`, or [`Err`] with the index where `x` could be inserted while
+  /// maintaining sorted order.
+  ///
+  /// # Errors
+  ///
+  /// Returns the insertion index of `x` as [`Err`] if the slice does not contain `x`.
+  pub const fn binary_search(&self, x: &T) -> Result<usize, usize>
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    self.0.const_binary_search(x)
+  }
+
+  /// Returns `true` if the slice contains an element equal to `x`.
+  #[must_use]
+  pub const fn contains(&self, x: &T) -> bool
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    self.0.const_binary_search(x).is_ok()
+  }
+
+  /// Returns the range of indices of the elements equal to `x`.
+  ///
+  /// The range is empty and starts at the insertion index of `x` if the slice does not contain
+  /// `x`.
+  #[must_use]
+  pub const fn equal_range(&self, x: &T) -> Range<usize>
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    let start = self.0.const_insertion_index(x);
+    let end = start + self.0[start..].const_insertion_index_last(x);
+    start..end
+  }
+}
+
+impl<'a, T> const Deref for ConstSortedSlice<'a, T> {
+  type Target = [T];
+
+  fn deref(&self) -> &Self::Target {
+    self.0
+  }
+}
@@ -0,0 +1,617 @@
+//! Relational operations on sorted key/value tables.
+//!
+//! A table is a slice of keys sorted in ascending order together with a parallel slice holding
+//! the value belonging to each key. Such tables are typically produced by sorting with
+//! [`const_argsort_lexicographic`](crate::const_sort::const_argsort_lexicographic) or one of the
+//! `const_sort_unstable*` functions in a const item.
+//!
+//! None of the functions allocate. Results are written into caller supplied buffers and the
+//! number of written rows is returned.
+
+use core::marker::Destruct;
+
+use crate::ConstSliceSortExt;
+
+/// Returns the end of the run of keys equal to `keys[start]`.
+const fn run_end<K>(keys: &[K], start: usize) -> usize
+where
+  K: ~const PartialOrd,
+{
+  let mut end = start + 1;
+  while end < keys.len() && !keys[start].lt(&keys[end]) {
+    end += 1;
+  }
+  end
+}
+
+/// Performs a sort-merge inner join of the tables `a` and `b`.
+///
+/// For every pair of rows with equal keys, the key and both values are written to the same index
+/// of `out_keys`, `out_a` and `out_b`. Rows are emitted in key order; rows with equal keys are
+/// emitted as the cross product of both tables in their original order.
+///
+/// Returns the number of joined rows. This function is *O*(*n* + *m* + *r*) where *r* is the number
+/// of joined rows.
+///
+/// # Panics
+///
+/// Panics if a key slice and its value slice differ in length, or if the output buffers are too
+/// short to hold all joined rows.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_table::const_merge_join;
+///
+/// const JOINED: ([u8; 3], [char; 3], [u16; 3], usize) = {
+///   let mut keys = [0; 3];
+///   let mut names = ['\0'; 3];
+///   let mut ports = [0; 3];
+///   let n = const_merge_join(
+///     &[1, 2, 4],
+///     &['a', 'b', 'd'],
+///     &[2, 3, 4, 4],
+///     &[80, 22, 443, 8443],
+///     &mut keys,
+///     &mut names,
+///     &mut ports,
+///   );
+///   (keys, names, ports, n)
+/// };
+/// assert_eq!(JOINED, ([2, 4, 4], ['b', 'd', 'd'], [80, 443, 8443], 3));
+/// ```
+pub const fn const_merge_join<K, A, B>(
+  a_keys: &[K],
+  a_vals: &[A],
+  b_keys: &[K],
+  b_vals: &[B],
+  out_keys: &mut [K],
+  out_a: &mut [A],
+  out_b: &mut [B],
+) -> usize
+where
+  K: Ord + ~const PartialOrd + Copy,
+  A: Copy,
+  B: Copy,
+{
+  assert!(
+    a_keys.len() == a_vals.len() && b_keys.len() == b_vals.len(),
+    "keys and values must have the same length"
+  );
+
+  let mut n = 0;
+  let mut i = 0;
+  let mut j = 0;
+  while i < a_keys.len() && j < b_keys.len() {
+    if a_keys[i].lt(&b_keys[j]) {
+      i += 1;
+    } else if b_keys[j].lt(&a_keys[i]) {
+      j += 1;
+    } else {
+      let a_end = run_end(a_keys, i);
+      let b_end = run_end(b_keys, j);
+      while i < a_end {
+        let mut k = j;
+        while k < b_end {
+          assert!(
+            n < out_keys.len() && n < out_a.len() && n < out_b.len(),
+            "join output buffers are too short"
+          );
+          out_keys[n] = a_keys[i];
+          out_a[n] = a_vals[i];
+          out_b[n] = b_vals[k];
+          n += 1;
+          k += 1;
+        }
+        i += 1;
+      }
+      j = b_end;
+    }
+  }
+  n
+}
+
+/// Performs a sort-merge left join of the tables `a` and `b`.
+///
+/// Behaves like [`const_merge_join`], except that every row of `a` without a matching key in `b`
+/// is emitted once with `None` as its `b` value.
+///
+/// Returns the number of joined rows, which is at least `a_keys.len()`.
+///
+/// # Panics
+///
+/// Panics if a key slice and its value slice differ in length, or if the output buffers are too
+/// short to hold all joined rows.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_table::const_merge_join_left;
+///
+/// const JOINED: ([u8; 3], [char; 3], [Option<u16>; 3]) = {
+///   let mut keys = [0; 3];
+///   let mut names = ['\0'; 3];
+///   let mut ports = [None; 3];
+///   const_merge_join_left(
+///     &[1, 2, 4],
+///     &['a', 'b', 'd'],
+///     &[2, 3, 4],
+///     &[80, 22, 443],
+///     &mut keys,
+///     &mut names,
+///     &mut ports,
+///   );
+///   (keys, names, ports)
+/// };
+/// assert_eq!(
+///   JOINED,
+///   ([1, 2, 4], ['a', 'b', 'd'], [None, Some(80), Some(443)])
+/// );
+/// ```
+pub const fn const_merge_join_left<K, A, B>(
+  a_keys: &[K],
+  a_vals: &[A],
+  b_keys: &[K],
+  b_vals: &[B],
+  out_keys: &mut [K],
+  out_a: &mut [A],
+  out_b: &mut [Option<B>],
+) -> usize
+where
+  K: Ord + ~const PartialOrd + Copy,
+  A: Copy,
+  B: Copy,
+{
+  assert!(
+    a_keys.len() == a_vals.len() && b_keys.len() == b_vals.len(),
+    "keys and values must have the same length"
+  );
+
+  let mut n = 0;
+  let mut i = 0;
+  let mut j = 0;
+  while i < a_keys.len() {
+    // Skip all rows of `b` that are smaller than the current key of `a`.
+    while j < b_keys.len() && b_keys[j].lt(&a_keys[i]) {
+      j += 1;
+    }
+    let b_end = if j < b_keys.len() && !a_keys[i].lt(&b_keys[j]) {
+      run_end(b_keys, j)
+    } else {
+      j
+    };
+    let a_end = run_end(a_keys, i);
+    while i < a_end {
+      let mut k = j;
+      loop {
+        assert!(
+          n < out_keys.len() && n < out_a.len() && n < out_b.len(),
+          "join output buffers are too short"
+        );
+        out_keys[n] = a_keys[i];
+        out_a[n] = a_vals[i];
+        out_b[n] = if k < b_end { Some(b_vals[k]) } else { None };
+        n += 1;
+        k += 1;
+        if k >= b_end {
+          break;
+        }
+      }
+      i += 1;
+    }
+    j = b_end;
+  }
+  n
+}
+
+/// Collapses every run of equal keys in the table into a single row.
+///
+/// The values of a run are combined from left to right with `reduce`, e.g. to compute the sum,
+/// minimum or maximum of every group. The key and the aggregate of each group are written to the
+/// same index of `out_keys` and `out_aggs`, in key order.
+///
+/// Returns the number of groups. This function is *O*(*n*).
+///
+/// # Panics
+///
+/// Panics if `sorted_keys` and `vals` differ in length, or if the output buffers are shorter than
+/// the number of groups.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_table::const_group_reduce;
+///
+/// const SUMS: ([char; 3], [u32; 3]) = {
+///   let mut keys = ['\0'; 3];
+///   let mut sums = [0; 3];
+///   const fn sum(a: u32, b: u32) -> u32 {
+///     a + b
+///   }
+///   const_group_reduce(
+///     &['a', 'a', 'b', 'c', 'c', 'c'],
+///     &[1, 2, 3, 4, 5, 6],
+///     &mut keys,
+///     &mut sums,
+///     sum,
+///   );
+///   (keys, sums)
+/// };
+/// assert_eq!(SUMS, (['a', 'b', 'c'], [3, 3, 15]));
+/// ```
+pub const fn const_group_reduce<K, V, F>(
+  sorted_keys: &[K],
+  vals: &[V],
+  out_keys: &mut [K],
+  out_aggs: &mut [V],
+  mut reduce: F,
+) -> usize
+where
+  K: Ord + ~const PartialOrd + Copy,
+  V: Copy,
+  F: ~const FnMut(V, V) -> V + ~const Destruct,
+{
+  assert!(
+    sorted_keys.len() == vals.len(),
+    "keys and values must have the same length"
+  );
+
+  let mut n = 0;
+  let mut i = 0;
+  while i < sorted_keys.len() {
+    let end = run_end(sorted_keys, i);
+    let mut agg = vals[i];
+    let mut j = i + 1;
+    while j < end {
+      agg = reduce(agg, vals[j]);
+      j += 1;
+    }
+    assert!(
+      n < out_keys.len() && n < out_aggs.len(),
+      "group output buffers are too short"
+    );
+    out_keys[n] = sorted_keys[i];
+    out_aggs[n] = agg;
+    n += 1;
+    i = end;
+  }
+  n
+}
+
+/// Computes the differences between the sorted tables `old` and `new`.
+///
+/// Elements of `new` missing in `old` are written to `added` and elements of `old` missing in
+/// `new` are written to `removed`, both in ascending order. Repeated elements are matched one to
+/// one, e.g. an element occurring twice in `old` and once in `new` is removed once.
+///
+/// Returns the number of added and removed elements. This function is *O*(*n* + *m*).
+///
+/// # Panics
+///
+/// Panics if `added` or `removed` is too short to hold all differences.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_table::const_sorted_diff;
+///
+/// const DIFF: ([u16; 2], [u16; 2], (usize, usize)) = {
+///   let mut added = [0; 2];
+///   let mut removed = [0; 2];
+///   let counts = const_sorted_diff(&[21, 22, 80], &[22, 443, 8080], &mut added, &mut removed);
+///   (added, removed, counts)
+/// };
+/// assert_eq!(DIFF, ([443, 8080], [21, 80], (2, 2)));
+/// ```
+pub const fn const_sorted_diff<T>(
+  old: &[T],
+  new: &[T],
+  added: &mut [T],
+  removed: &mut [T],
+) -> (usize, usize)
+where
+  T: Ord + ~const PartialOrd + Copy,
+{
+  let mut n_added = 0;
+  let mut n_removed = 0;
+  let mut i = 0;
+  let mut j = 0;
+  while i < old.len() || j < new.len() {
+    if j == new.len() || (i < old.len() && old[i].lt(&new[j])) {
+      assert!(
+        n_removed < removed.len(),
+        "diff output buffers are too short"
+      );
+      removed[n_removed] = old[i];
+      n_removed += 1;
+      i += 1;
+    } else if i == old.len() || new[j].lt(&old[i]) {
+      assert!(n_added < added.len(), "diff output buffers are too short");
+      added[n_added] = new[j];
+      n_added += 1;
+      j += 1;
+    } else {
+      i += 1;
+      j += 1;
+    }
+  }
+  (n_added, n_removed)
+}
+
+/// Binary searches the sorted `keys` column of a table for `x`.
+///
+/// If `x` is found, [`Ok`] with the index of the first row with key `x` is returned. That index
+/// is also the index of the matching value in every parallel value column. Otherwise [`Err`] with
+/// the index where a row with key `x` could be inserted while keeping the table sorted is
+/// returned. See [`const_lookup_by_parallel_key`] for fetching the value directly.
+///
+/// This is *O*(log(*n*)).
+///
+/// # Errors
+///
+/// Returns the insertion index of `x` as [`Err`] if `keys` does not contain `x`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_table::const_binary_search_parallel_key;
+///
+/// const PORTS: [u16; 4] = [22, 80, 443, 8080];
+/// const NAMES: [&str; 4] = ["ssh", "http", "https", "http-alt"];
+///
+/// const HTTPS: Result<usize, usize> = const_binary_search_parallel_key(&PORTS, &443);
+/// assert_eq!(NAMES[HTTPS.unwrap()], "https");
+/// const FTP: Result<usize, usize> = const_binary_search_parallel_key(&PORTS, &21);
+/// assert_eq!(FTP, Err(0));
+/// ```
+pub const fn const_binary_search_parallel_key<K>(keys: &[K], x: &K) -> Result<usize, usize>
+where
+  K: Ord + ~const PartialOrd,
+{
+  let index = keys.const_insertion_index(x);
+  if index < keys.len() && !x.lt(&keys[index]) {
+    Ok(index)
+  } else {
+    Err(index)
+  }
+}
+
+/// Returns the value of the first row with key `x` in the table made of the sorted `keys` and the
+/// parallel `values` column, or `None` if no row has key `x`.
+///
+/// This is *O*(log(*n*)).
+///
+/// # Panics
+///
+/// Panics if `keys` and `values` differ in length.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_table::const_lookup_by_parallel_key;
+///
+/// const PORTS: [u16; 4] = [22, 80, 443, 8080];
+/// const NAMES: [&str; 4] = ["ssh", "http", "https", "http-alt"];
+///
+/// const HTTP: Option<&&str> = const_lookup_by_parallel_key(&PORTS, &NAMES, &80);
+/// assert_eq!(HTTP, Some(&"http"));
+/// const FTP: Option<&&str> = const_lookup_by_parallel_key(&PORTS, &NAMES, &21);
+/// assert_eq!(FTP, None);
+/// ```
+#[must_use]
+pub const fn const_lookup_by_parallel_key<'v, K, V>(
+  keys: &[K],
+  values: &'v [V],
+  x: &K,
+) -> Option<&'v V>
+where
+  K: Ord + ~const PartialOrd,
+{
+  assert!(
+    keys.len() == values.len(),
+    "keys and values must have the same length"
+  );
+  match const_binary_search_parallel_key(keys, x) {
+    Ok(index) => Some(&values[index]),
+    Err(_) => None,
+  }
+}
+
+/// Looks up many keys in the sorted `table_keys` column at once.
+///
+/// For every `queries[i]`, `out[i]` is set to the index of the first row with that key, or to
+/// `None` if no row has it. The queries must be sorted in ascending order, so that a single
+/// merge-style pass over both slices resolves all of them. This is *O*(*n* + *m*) instead of the
+/// *O*(*m* \* log(*n*)) of one [`const_binary_search_parallel_key`] per query, which pays off
+/// when resolving many references at once, e.g. against a symbol table. Unsorted queries can be
+/// sorted together with their indices with
+/// [`const_argsort_lexicographic`](crate::const_sort::const_argsort_lexicographic) first.
+///
+/// # Panics
+///
+/// Panics if `out` and `queries` differ in length or if `queries` is not sorted.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_table::const_lookup_many;
+///
+/// // Sorted symbol addresses.
+/// const SYMBOLS: [u32; 5] = [0x1000, 0x1040, 0x10a0, 0x1200, 0x1280];
+/// const RESOLVED: [Option<usize>; 4] = {
+///   let mut out = [None; 4];
+///   const_lookup_many(&SYMBOLS, &[0x1040, 0x1100, 0x1200, 0x1280], &mut out);
+///   out
+/// };
+/// assert_eq!(RESOLVED, [Some(1), None, Some(3), Some(4)]);
+/// ```
+pub const fn const_lookup_many<K>(table_keys: &[K], queries: &[K], out: &mut [Option<usize>])
+where
+  K: Ord + ~const PartialOrd,
+{
+  assert!(
+    out.len() == queries.len(),
+    "out must have the same length as queries"
+  );
+  let mut row = 0;
+  let mut i = 0;
+  while i < queries.len() {
+    assert!(
+      i == 0 || !queries[i].lt(&queries[i - 1]),
+      "queries are not sorted"
+    );
+    while row < table_keys.len() && table_keys[row].lt(&queries[i]) {
+      row += 1;
+    }
+    out[i] = if row < table_keys.len() && !queries[i].lt(&table_keys[row]) {
+      Some(row)
+    } else {
+      None
+    };
+    i += 1;
+  }
+}
+
+/// Defines a const table together with companion constants describing it.
+///
+/// Every table is defined as a const array `NAME` and an empty struct of the same name whose
+/// associated constants describe the array:
+///
+/// - `NAME::LEN`: the number of rows.
+/// - `NAME::IS_SORTED`: whether the rows are sorted in ascending order, by key if a key is given.
+/// - `NAME::UNIQUE_KEYS`: the number of distinct keys, or elements if no key is given. For
+///   unsorted tables, this is the number of runs of equal keys instead.
+///
+/// The array and the struct live in different namespaces, so `NAME[i]` indexes the table and
+/// `NAME::LEN` refers to a companion constant. A table is defined by one of
+///
+/// - `const NAME: [T; N] sorted_by key = expr;`, which sorts the rows of `expr` with
+///   [`const_sort_unstable_by_key`](crate::ConstSliceSortExt::const_sort_unstable_by_key) and
+///   the key function `key`, which must be a path to a const fn,
+/// - `const NAME: [T; N] sorted = expr;`, which sorts the rows of `expr` with
+///   [`const_sort_unstable`](crate::ConstSliceSortExt::const_sort_unstable), and
+/// - `const NAME: [T; N] = expr;`, which keeps the rows of `expr` in their order, e.g. to assert
+///   that a hand-written table is sorted.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// #![feature(const_cmp)]
+/// use const_sort::const_table;
+///
+/// const fn port(service: &(u16, &str)) -> u16 {
+///   service.0
+/// }
+///
+/// const_table! {
+///   const SERVICES: [(u16, &str); 4] sorted_by port = [
+///     (443, "https"),
+///     (22, "ssh"),
+///     (80, "http"),
+///     (443, "quic"),
+///   ];
+///   const LEVELS: [u8; 3] = [0, 128, 64];
+/// }
+/// assert_eq!(SERVICES[0], (22, "ssh"));
+/// assert_eq!(SERVICES::LEN, 4);
+/// assert!(SERVICES::IS_SORTED);
+/// assert_eq!(SERVICES::UNIQUE_KEYS, 3);
+/// const _: () = assert!(!LEVELS::IS_SORTED);
+/// ```
+#[macro_export]
+macro_rules! const_table {
+  () => {};
+  (@companion $vis:vis $name:ident) => {
+    $crate::const_table!(
+      @companion $vis $name,
+      $crate::ConstSliceSortExt::const_is_sorted($name.as_slice()),
+      $crate::ConstSliceSortExt::const_unique_count($name.as_slice())
+    );
+  };
+  (@companion $vis:vis $name:ident, $is_sorted:expr, $unique_keys:expr) => {
+    #[doc = concat!("Companion constants of the table [`", stringify!($name), "`].")]
+    #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+    $vis struct $name {}
+
+    #[allow(dead_code)]
+    impl $name {
+      /// The number of rows.
+      $vis const LEN: usize = $name.len();
+      /// Whether the rows are sorted in ascending order of their keys.
+      $vis const IS_SORTED: bool = $is_sorted;
+      /// The number of runs of equal keys, i.e. of distinct keys if the rows are sorted.
+      $vis const UNIQUE_KEYS: usize = $unique_keys;
+    }
+  };
+  (
+    $(#[$attr:meta])*
+    $vis:vis const $name:ident: [$t:ty; $n:expr] sorted_by $key:path = $rows:expr;
+    $($rest:tt)*
+  ) => {
+    $(#[$attr])*
+    $vis const $name: [$t; $n] = {
+      let mut table: [$t; $n] = $rows;
+      let rows: &mut [$t] = &mut table;
+      $crate::ConstSliceSortExt::const_sort_unstable_by_key(rows, $key);
+      table
+    };
+    $crate::const_table!(
+      @companion $vis $name,
+      $crate::ConstSliceSortExt::const_is_sorted_by_key($name.as_slice(), $key),
+      {
+        let mut runs = if $name.is_empty() { 0 } else { 1 };
+        let mut i = 1;
+        while i < $name.len() {
+          let (previous, key) = ($key(&$name[i - 1]), $key(&$name[i]));
+          if previous.lt(&key) || key.lt(&previous) {
+            runs += 1;
+          }
+          i += 1;
+        }
+        runs
+      }
+    );
+    $crate::const_table!($($rest)*);
+  };
+  (
+    $(#[$attr:meta])*
+    $vis:vis const $name:ident: [$t:ty; $n:expr] sorted = $rows:expr;
+    $($rest:tt)*
+  ) => {
+    $(#[$attr])*
+    $vis const $name: [$t; $n] = {
+      let mut table: [$t; $n] = $rows;
+      let rows: &mut [$t] = &mut table;
+      $crate::ConstSliceSortExt::const_sort_unstable(rows);
+      table
+    };
+    $crate::const_table!(@companion $vis $name);
+    $crate::const_table!($($rest)*);
+  };
+  (
+    $(#[$attr:meta])*
+    $vis:vis const $name:ident: [$t:ty; $n:expr] = $rows:expr;
+    $($rest:tt)*
+  ) => {
+    $(#[$attr])*
+    $vis const $name: [$t; $n] = $rows;
+    $crate::const_table!(@companion $vis $name);
+    $crate::const_table!($($rest)*);
+  };
+}
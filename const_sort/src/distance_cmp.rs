@@ -0,0 +1,82 @@
+//! Comparators ordering integers by their distance to a target.
+//!
+//! Sorting with [`cmp_by_distance_to`] puts the closest match first, e.g. to build a table of the
+//! supported baud rates ordered by how close they are to a requested rate. Values at the same
+//! distance, i.e. one below and one above the target, are ordered by value, so the comparator is
+//! a total order and the smaller value comes first.
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(const_mut_refs)]
+//! #![feature(const_trait_impl)]
+//! use const_sort::distance_cmp::cmp_by_distance_to;
+//! use const_sort::ConstSliceSortExt;
+//!
+//! const RATES: [u32; 5] = {
+//!   let mut rates = [9600, 115_200, 19_200, 57_600, 38_400];
+//!   rates.const_sort_unstable_by(cmp_by_distance_to(50_000));
+//!   rates
+//! };
+//! assert_eq!(RATES, [57_600, 38_400, 19_200, 9600, 115_200]);
+//! ```
+
+use core::cmp::Ordering;
+
+/// Comparator ordering integers by their distance to [`target`](Self::target).
+///
+/// Returned by [`cmp_by_distance_to`]. It implements `~const Fn(&T, &T) -> Ordering` for all
+/// primitive integer types `T`, so it can be passed to every `*_by` function taking an
+/// `Ordering` comparator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByDistanceTo<T> {
+  /// The value that compares smallest.
+  pub target: T,
+}
+
+/// Returns a comparator ordering integers by their distance to `target`, breaking ties by value.
+#[must_use]
+pub const fn cmp_by_distance_to<T>(target: T) -> ByDistanceTo<T> {
+  ByDistanceTo { target }
+}
+
+macro_rules! by_distance_to_impl {
+  ($($int:ident)*) => {$(
+    impl ByDistanceTo<$int> {
+      /// Compares `a` and `b` by their distance to the target and by value if it is the same.
+      #[must_use]
+      pub const fn compare(&self, a: $int, b: $int) -> Ordering {
+        let (da, db) = (a.abs_diff(self.target), b.abs_diff(self.target));
+        if da < db || (da == db && a < b) {
+          Ordering::Less
+        } else if da == db && a == b {
+          Ordering::Equal
+        } else {
+          Ordering::Greater
+        }
+      }
+    }
+
+    impl<'a, 'b> const FnOnce<(&'a $int, &'b $int)> for ByDistanceTo<$int> {
+      type Output = Ordering;
+
+      extern "rust-call" fn call_once(self, args: (&'a $int, &'b $int)) -> Ordering {
+        self.compare(*args.0, *args.1)
+      }
+    }
+
+    impl<'a, 'b> const FnMut<(&'a $int, &'b $int)> for ByDistanceTo<$int> {
+      extern "rust-call" fn call_mut(&mut self, args: (&'a $int, &'b $int)) -> Ordering {
+        self.compare(*args.0, *args.1)
+      }
+    }
+
+    impl<'a, 'b> const Fn<(&'a $int, &'b $int)> for ByDistanceTo<$int> {
+      extern "rust-call" fn call(&self, args: (&'a $int, &'b $int)) -> Ordering {
+        self.compare(*args.0, *args.1)
+      }
+    }
+  )*};
+}
+
+by_distance_to_impl! { u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize }
@@ -0,0 +1,106 @@
+//! Comparators for floats that treat nearly equal values as equal.
+//!
+//! Measured data rarely contains exactly equal floats, so sorting it with [`f64::total_cmp`] and
+//! then grouping or deduplicating equal neighbours finds nothing to merge. The comparators of
+//! this module consider values within a tolerance equal and order all other values by
+//! [`f64::total_cmp`].
+//!
+//! Equality within a tolerance is not transitive: with a tolerance of `1.0`, `0.0 == 0.6` and
+//! `0.6 == 1.2`, but `0.0 < 1.2`. Such a comparator is not a total order, so sorting with it
+//! only guarantees that every element is in order relative to its neighbours, and which of
+//! several nearly equal values comes first depends on the input order. It never causes undefined
+//! behavior or panics. If the values form clusters that are further apart than the tolerance,
+//! the comparator is a total order on them, which
+//! [`const_check_total_order`](crate::const_sort::const_check_total_order) can verify on a
+//! sample.
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(const_mut_refs)]
+//! #![feature(const_trait_impl)]
+//! use const_sort::float_cmp::cmp_f64_with_epsilon;
+//! use const_sort::ConstSliceSortExt;
+//!
+//! const V: [f64; 4] = {
+//!   let mut v = [2.0, 1.0 + 1e-12, 3.0, 1.0];
+//!   v.const_sort_unstable_by(cmp_f64_with_epsilon(1e-9));
+//!   v
+//! };
+//! assert_eq!(V[3], 3.0);
+//! assert!(V[0] - 1.0 < 1e-9 && V[1] - 1.0 < 1e-9);
+//! ```
+
+use core::cmp::Ordering;
+
+use crate::key_transform::f64_to_key;
+
+/// Comparator for `f64`s that considers values at most `epsilon` apart equal.
+///
+/// Returned by [`cmp_f64_with_epsilon`]. It implements `~const Fn(&f64, &f64) -> Ordering`, so it
+/// can be passed to every `*_by` function taking an `Ordering` comparator.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct F64EpsilonCmp {
+  epsilon: f64,
+}
+
+impl F64EpsilonCmp {
+  /// Returns the largest difference of values that compare equal.
+  #[must_use]
+  pub const fn epsilon(&self) -> f64 {
+    self.epsilon
+  }
+
+  /// Returns `Equal` if `a` and `b` are at most `epsilon` apart and orders them by
+  /// [`f64::total_cmp`] otherwise.
+  ///
+  /// NaNs are never within `epsilon` of any value, so they are ordered like by
+  /// [`f64::total_cmp`].
+  #[must_use]
+  pub const fn compare(&self, a: f64, b: f64) -> Ordering {
+    if a - b <= self.epsilon && b - a <= self.epsilon {
+      return Ordering::Equal;
+    }
+    let (a, b) = (f64_to_key(a), f64_to_key(b));
+    if a < b {
+      Ordering::Less
+    } else if a > b {
+      Ordering::Greater
+    } else {
+      Ordering::Equal
+    }
+  }
+}
+
+impl<'a, 'b> const FnOnce<(&'a f64, &'b f64)> for F64EpsilonCmp {
+  type Output = Ordering;
+
+  extern "rust-call" fn call_once(self, args: (&'a f64, &'b f64)) -> Ordering {
+    self.compare(*args.0, *args.1)
+  }
+}
+
+impl<'a, 'b> const FnMut<(&'a f64, &'b f64)> for F64EpsilonCmp {
+  extern "rust-call" fn call_mut(&mut self, args: (&'a f64, &'b f64)) -> Ordering {
+    self.compare(*args.0, *args.1)
+  }
+}
+
+impl<'a, 'b> const Fn<(&'a f64, &'b f64)> for F64EpsilonCmp {
+  extern "rust-call" fn call(&self, args: (&'a f64, &'b f64)) -> Ordering {
+    self.compare(*args.0, *args.1)
+  }
+}
+
+/// Returns a comparator that considers `f64`s at most `epsilon` apart equal.
+///
+/// See the [module documentation](self) for why the comparator is not a total order.
+///
+/// # Panics
+///
+/// Panics if `epsilon` is negative or NaN.
+#[must_use]
+pub const fn cmp_f64_with_epsilon(epsilon: f64) -> F64EpsilonCmp {
+  assert!(epsilon >= 0.0, "epsilon must not be negative or NaN");
+  F64EpsilonCmp { epsilon }
+}
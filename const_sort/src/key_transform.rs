@@ -0,0 +1,98 @@
+//! Order-preserving transforms between primitive types and unsigned integer keys.
+//!
+//! Every `*_to_key` function maps its input to an unsigned integer of the same width such that
+//! `a < b` if and only if `x_to_key(a) < x_to_key(b)`. The matching `key_to_*` function reverses
+//! the transform. The keys can be compared bytewise in big-endian order, so they are suited for
+//! radix sorting, tries and other structures working on the bits of a key.
+//!
+//! Floats are ordered by [`f32::total_cmp`] and [`f64::total_cmp`]: negative NaNs come first,
+//! followed by the negative numbers, `-0.0`, `+0.0`, the positive numbers and positive NaNs.
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(const_mut_refs)]
+//! #![feature(const_trait_impl)]
+//! use const_sort::key_transform::{f32_to_key, i16_to_key, key_to_i16};
+//!
+//! const KEYS: [u16; 3] = [i16_to_key(-300), i16_to_key(0), i16_to_key(7)];
+//! assert!(KEYS[0] < KEYS[1] && KEYS[1] < KEYS[2]);
+//! assert_eq!(key_to_i16(KEYS[0]), -300);
+//!
+//! const NEG_ZERO: u32 = f32_to_key(-0.0);
+//! const ZERO: u32 = f32_to_key(0.0);
+//! assert!(NEG_ZERO < ZERO && f32_to_key(-1.5) < NEG_ZERO);
+//! ```
+
+macro_rules! signed_key_transform {
+  ($($int:ident, $key:ident, $to_key:ident, $from_key:ident;)*) => {$(
+    #[doc = concat!("Maps `x` to an `", stringify!($key), "` key with the same order.")]
+    ///
+    /// Flips the sign bit, so negative numbers map below positive ones.
+    #[must_use]
+    pub const fn $to_key(x: $int) -> $key {
+      (x as $key) ^ (1 << ($key::BITS - 1))
+    }
+
+    #[doc = concat!("Reverses [`", stringify!($to_key), "`].")]
+    #[must_use]
+    pub const fn $from_key(key: $key) -> $int {
+      (key ^ (1 << ($key::BITS - 1))) as $int
+    }
+  )*};
+}
+
+signed_key_transform! {
+  i8, u8, i8_to_key, key_to_i8;
+  i16, u16, i16_to_key, key_to_i16;
+  i32, u32, i32_to_key, key_to_i32;
+  i64, u64, i64_to_key, key_to_i64;
+  i128, u128, i128_to_key, key_to_i128;
+}
+
+macro_rules! float_key_transform {
+  ($($float:ident, $key:ident, $to_key:ident, $from_key:ident;)*) => {$(
+    #[doc = concat!("Maps `x` to an `", stringify!($key), "` key with the order of [`", stringify!($float), "::total_cmp`].")]
+    ///
+    /// Positive numbers get their sign bit set. Negative numbers get all bits flipped, so larger
+    /// magnitudes map to smaller keys.
+    #[must_use]
+    pub const fn $to_key(x: $float) -> $key {
+      let bits = x.to_bits();
+      let sign = 1 << ($key::BITS - 1);
+      if bits & sign == 0 {
+        bits | sign
+      } else {
+        !bits
+      }
+    }
+
+    #[doc = concat!("Reverses [`", stringify!($to_key), "`].")]
+    #[must_use]
+    pub const fn $from_key(key: $key) -> $float {
+      let sign = 1 << ($key::BITS - 1);
+      $float::from_bits(if key & sign == 0 { !key } else { key & !sign })
+    }
+  )*};
+}
+
+float_key_transform! {
+  f32, u32, f32_to_key, key_to_f32;
+  f64, u64, f64_to_key, key_to_f64;
+}
+
+/// Maps `c` to a `u32` key with the same order.
+///
+/// Chars already compare by their code point, so this is the code point.
+#[must_use]
+pub const fn char_to_key(c: char) -> u32 {
+  c as u32
+}
+
+/// Reverses [`char_to_key`].
+///
+/// Returns `None` if `key` is not a valid code point, i.e. not the key of any `char`.
+#[must_use]
+pub const fn key_to_char(key: u32) -> Option<char> {
+  char::from_u32(key)
+}
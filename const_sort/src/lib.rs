@@ -16,6 +16,9 @@
 #![feature(const_deref)] // const_sort_core
 #![feature(const_reverse)] // const_sort_core
 #![feature(const_slice_split_at_mut)] // const_sort_core
+#![feature(const_slice_split_at_not_mut)] // const_sorted
+#![feature(const_float_bits_conv)] // key_transform
+#![feature(const_fn_floating_point_arithmetic)] // float_cmp
 #![feature(const_maybe_uninit_write)] // const_sort_core
 #![feature(core_intrinsics)] // const_sort_core
 #![feature(const_eval_select)] // const_sort_core
@@ -24,25 +27,98 @@
 #![feature(const_cmp)] // const_sort_core
 #![feature(unboxed_closures)] // const_slice_sort_ext
 #![feature(fn_traits)] // const_slice_sort_ext
+#![feature(const_option_ext)] // const_slice_sort_ext
+#![feature(allow_internal_unstable)] // const_array
+#![feature(const_slice_from_raw_parts_mut)] // const_chunked_map
+#![cfg_attr(feature = "const-closure-compat", feature(tuple_trait))] // const_closure
 // For tests
 #![feature(is_sorted)]
+#![cfg_attr(all(test, feature = "const-tests"), feature(inline_const))]
 #![doc = include_str!("../README.md")]
 
-pub(crate) mod fake_usize_ptr;
+pub use const_sort_core as const_sort;
 
-#[allow(
-  clippy::undocumented_unsafe_blocks,
-  clippy::identity_op,
-  clippy::unnecessary_mut_passed,
-  clippy::too_many_lines,
-  clippy::doc_markdown,
-  clippy::cognitive_complexity,
-  clippy::cast_possible_truncation
-)]
-pub mod const_sort;
+pub mod api;
+
+pub mod const_table;
+
+pub mod const_array;
+
+#[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
+pub mod key_transform;
+
+pub mod float_cmp;
+
+pub mod distance_cmp;
+
+pub mod testgen;
 
 mod const_slice_sort_ext;
-pub use const_slice_sort_ext::ConstSliceSortExt;
+pub use const_slice_sort_ext::{ConstSliceSortExt, OrderSummary};
+
+mod const_key;
+pub use const_key::ConstKey;
+
+mod const_sortable;
+pub use const_sortable::ConstSortable;
+
+mod priority_sort;
+
+mod const_sorted;
+pub use const_sorted::ConstSorted;
+
+mod const_sorted_desc;
+pub use const_sorted_desc::ConstSortedDesc;
+
+mod const_sorted_slice;
+pub use const_sorted_slice::ConstSortedSlice;
+
+mod const_index_map;
+pub use const_index_map::ConstIndexMap;
+
+mod const_sorted_map;
+pub use const_sorted_map::ConstSortedMap;
+
+mod const_sorted_set;
+pub use const_sorted_set::ConstSortedSet;
+
+mod const_chunked_map;
+pub use const_chunked_map::{ConstChunkedMap, ConstChunkedMapBuilder};
+
+mod const_column_table;
+pub use const_column_table::{ConstTable1, ConstTable2, ConstTable3, ConstTable4};
+
+mod const_sorted_drain;
+pub use const_sorted_drain::ConstSortedDrain;
+
+mod sorted_cursor;
+pub use sorted_cursor::SortedCursor;
+
+mod stability_probe;
+pub use stability_probe::StabilityProbe;
+
+mod with_index;
+pub use with_index::WithIndex;
+
+mod const_permutation_ext;
+pub use const_permutation_ext::ConstPermutationExt;
+
+mod const_interpolation_search_ext;
+pub use const_interpolation_search_ext::ConstInterpolationSearchExt;
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+mod alloc_ext;
+#[cfg(feature = "alloc")]
+pub use alloc_ext::SliceSortVecExt;
+
+#[cfg(feature = "const-closure-compat")]
+pub mod const_closure;
+
+mod self_check;
+pub use self_check::self_check;
 
 #[cfg(test)]
 mod test;
@@ -0,0 +1,70 @@
+/// Sorts a slice of enums by a priority assigned to every variant.
+///
+/// `const_priority_sort!(slice, Enum { A => 3, B => 1, C => 2 })` sorts the `&mut [Enum]`
+/// `slice` by ascending priority. The priorities are `i64` values and are matched exhaustively,
+/// so adding a variant to the enum fails to compile until it is given a priority, instead of
+/// silently sorting it first or last. Variants with fields are matched regardless of their
+/// fields.
+///
+/// The sort is unstable like [`ConstSliceSortExt::const_sort_unstable_by_key`], so elements with
+/// the same priority may be reordered.
+///
+/// [`ConstSliceSortExt::const_sort_unstable_by_key`]: crate::ConstSliceSortExt::const_sort_unstable_by_key
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_priority_sort;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Task {
+///   Render,
+///   Input(u8),
+///   Audio { channel: u8 },
+/// }
+///
+/// const TASKS: [Task; 4] = {
+///   let mut tasks = [Task::Render, Task::Audio { channel: 1 }, Task::Input(0), Task::Render];
+///   const_priority_sort!(&mut tasks, Task { Render => 3, Input => 1, Audio => 2 });
+///   tasks
+/// };
+/// assert_eq!(
+///   TASKS,
+///   [Task::Input(0), Task::Audio { channel: 1 }, Task::Render, Task::Render]
+/// );
+/// ```
+///
+/// A missing variant is a compile error:
+///
+/// ```compile_fail
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_priority_sort;
+///
+/// enum Level {
+///   Low,
+///   High,
+/// }
+///
+/// const LEVELS: [Level; 2] = {
+///   let mut levels = [Level::High, Level::Low];
+///   const_priority_sort!(&mut levels, Level { Low => 0 });
+///   levels
+/// };
+/// ```
+// `#[macro_export]` places the macro at the crate root, outside of this module.
+#[allow(clippy::module_name_repetitions)]
+#[macro_export]
+macro_rules! const_priority_sort {
+  ($slice:expr, $enum:ident { $($variant:ident => $priority:expr),+ $(,)? }) => {{
+    const fn priority(value: &$enum) -> i64 {
+      match value {
+        $($enum::$variant { .. } => $priority,)+
+      }
+    }
+    let slice: &mut [$enum] = $slice;
+    $crate::ConstSliceSortExt::const_sort_unstable_by_key(slice, priority);
+  }};
+}
@@ -0,0 +1,74 @@
+use crate::const_sort::{const_argsort_lexicographic, const_heapsort};
+use crate::{testgen, ConstPermutationExt, ConstSliceSortExt};
+
+/// Length of the fixtures, long enough to not be handled by insertion sort alone.
+const LEN: usize = 64;
+/// Number of distinct fixture patterns generated by [`fixture`].
+const FIXTURES: usize = 7;
+
+const fn fixture(kind: usize) -> [usize; LEN] {
+  match kind {
+    0 => testgen::random(0x9E37_79B9, 1000),
+    1 => testgen::descending(),
+    2 => testgen::all_equal(7),
+    3 => testgen::sawtooth(8),
+    4 => testgen::organ_pipe(),
+    5 => testgen::push_front(),
+    _ => testgen::median_of_3_killer(),
+  }
+}
+
+/// Runs the sorting, selection and search functions of this crate on a set of fixtures and
+/// returns whether all of them behaved correctly.
+///
+/// The fixtures cover the patterns of the [`testgen`] module. Evaluating this function in
+/// a const item verifies that the crate still works with the nightly compiler used to build it:
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+///
+/// const _: () = assert!(const_sort::self_check());
+/// ```
+#[must_use]
+pub const fn self_check() -> bool {
+  let mut kind = 0;
+  while kind < FIXTURES {
+    let orig = fixture(kind);
+
+    let mut sorted = orig;
+    sorted.const_sort_unstable();
+    if !sorted.const_is_sorted() {
+      return false;
+    }
+
+    let mut heap_sorted = orig;
+    const_heapsort(&mut heap_sorted, PartialOrd::lt);
+    let perm: [usize; LEN] = const_argsort_lexicographic(&orig, &orig);
+    if !perm.const_is_permutation_of_0_to_n() {
+      return false;
+    }
+    let mut i = 0;
+    while i < LEN {
+      if heap_sorted[i] != sorted[i] || orig[perm[i]] != sorted[i] {
+        return false;
+      }
+      i += 1;
+    }
+
+    let mut selected = orig;
+    if *selected.const_select_nth_unstable(LEN / 3).1 != sorted[LEN / 3] {
+      return false;
+    }
+
+    let x = orig[LEN / 2];
+    let first = sorted.const_insertion_index(&x);
+    let last = sorted.const_insertion_index_last(&x);
+    if first >= last || sorted[first] != x || sorted[last - 1] != x {
+      return false;
+    }
+
+    kind += 1;
+  }
+  true
+}
@@ -0,0 +1,102 @@
+use crate::ConstSliceSortExt;
+
+/// A position in a sorted slice that only moves forward.
+///
+/// Looking up many keys in ascending order with independent binary searches costs
+/// *O*(log(*n*)) per key, even if consecutive keys are close together in the slice.
+/// [`advance_to`](Self::advance_to) instead gallops forward from the current position, which
+/// takes *O*(log(*d*)) for a distance *d*, so walking *m* sorted keys against a sorted slice of
+/// *n* elements takes *O*(*m* \* log(*n* / *m*)) in total. This also works inside const fn loops,
+/// e.g. to join two sorted tables.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::SortedCursor;
+///
+/// const TABLE: [u16; 8] = [2, 3, 5, 7, 11, 13, 17, 19];
+///
+/// // Counts how many of the sorted queries are in the table.
+/// const FOUND: usize = {
+///   let queries = [3, 4, 11, 19, 20];
+///   let mut cursor = SortedCursor::new(&TABLE);
+///   let mut found = 0;
+///   let mut i = 0;
+///   while i < queries.len() {
+///     if cursor.advance_to(&queries[i]).is_some() {
+///       found += 1;
+///     }
+///     i += 1;
+///   }
+///   found
+/// };
+/// assert_eq!(FOUND, 3);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SortedCursor<'a, T> {
+  slice: &'a [T],
+  // All elements before this index are less than the last key advanced to.
+  position: usize,
+}
+
+impl<'a, T> SortedCursor<'a, T> {
+  /// Creates a cursor at the start of the sorted slice `slice`.
+  #[must_use]
+  pub const fn new(slice: &'a [T]) -> Self {
+    Self { slice, position: 0 }
+  }
+
+  /// Returns the current position, i.e. the index of the first element that is not less than
+  /// the last key advanced to.
+  #[must_use]
+  pub const fn position(&self) -> usize {
+    self.position
+  }
+
+  /// Returns the elements from the current position on.
+  #[must_use]
+  pub const fn remaining(&self) -> &'a [T] {
+    &self.slice[self.position..]
+  }
+
+  /// Moves the cursor forward to the first element that is not less than `x` and returns its
+  /// index if it is equal to `x`.
+  ///
+  /// The cursor never moves backwards, so `x` must not be less than the keys passed before;
+  /// otherwise the result is unspecified. The slice is assumed to be sorted; otherwise the result
+  /// is unspecified as well.
+  ///
+  /// # Current implementation
+  ///
+  /// The search probes the elements 1, 2, 4, ... places after the current position until it finds
+  /// one that is not less than `x`, and then binary searches the last step. This is
+  /// *O*(log(*d*)), where *d* is the distance the cursor moves.
+  pub const fn advance_to(&mut self, x: &T) -> Option<usize>
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    let len = self.slice.len();
+    // `self.slice[..low]` is less than `x` and `self.slice[high..]` is not.
+    let mut low = self.position;
+    let mut high = len;
+    let mut step = 1;
+    while step - 1 < len - low {
+      let probe = low + step - 1;
+      if !self.slice[probe].lt(x) {
+        high = probe;
+        break;
+      }
+      low = probe + 1;
+      step = step.saturating_mul(2);
+    }
+    self.position = low + self.slice[low..high].const_insertion_index(x);
+
+    if self.position < len && !x.lt(&self.slice[self.position]) {
+      Some(self.position)
+    } else {
+      None
+    }
+  }
+}
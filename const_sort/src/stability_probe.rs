@@ -0,0 +1,105 @@
+use core::cmp::Ordering;
+use core::mem::MaybeUninit;
+use core::ptr;
+
+/// A value tagged with its original position, for checking whether a sort is stable.
+///
+/// Probes compare only by their [`value`](Self::value), so a sort can not tell apart probes with
+/// equal values. After sorting, a stable sort must have kept the [`index`](Self::index) of equal
+/// values in ascending order, which [`is_stably_sorted`](Self::is_stably_sorted) verifies.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::const_sort::const_binary_insertion_sort;
+/// use const_sort::StabilityProbe;
+///
+/// const STABLE: bool = {
+///   let mut probes = StabilityProbe::probe_array([3, 1, 3, 2, 1]);
+///   const_binary_insertion_sort(&mut probes, PartialOrd::lt);
+///   StabilityProbe::is_stably_sorted(&probes)
+/// };
+/// assert!(STABLE);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct StabilityProbe<T> {
+  /// The value the probe is compared by.
+  pub value: T,
+  /// The position of the probe before sorting.
+  pub index: usize,
+}
+
+impl<T> StabilityProbe<T> {
+  /// Tags every element of `arr` with its index.
+  #[must_use]
+  pub const fn probe_array<const N: usize>(arr: [T; N]) -> [Self; N]
+  where
+    T: Copy,
+  {
+    let mut probes = [MaybeUninit::<Self>::uninit(); N];
+    let mut i = 0;
+    while i < N {
+      probes[i].write(Self {
+        value: arr[i],
+        index: i,
+      });
+      i += 1;
+    }
+    // SAFETY: All `N` elements were initialized above and `[MaybeUninit<Self>; N]` has the same
+    // layout as `[Self; N]`.
+    unsafe { ptr::read(probes.as_ptr().cast::<[Self; N]>()) }
+  }
+
+  /// Checks if `probes` is sorted by value and probes with equal values are in the order of
+  /// their original index.
+  #[must_use]
+  pub const fn is_stably_sorted(probes: &[Self]) -> bool
+  where
+    T: ~const PartialOrd + Ord,
+  {
+    let mut i = 1;
+    while i < probes.len() {
+      let (a, b) = (&probes[i - 1], &probes[i]);
+      if b.value.lt(&a.value) || (!a.value.lt(&b.value) && a.index > b.index) {
+        return false;
+      }
+      i += 1;
+    }
+    true
+  }
+}
+
+impl<T> const PartialEq for StabilityProbe<T>
+where
+  T: ~const PartialEq,
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.value == other.value
+  }
+}
+
+impl<T> Eq for StabilityProbe<T> where T: Eq {}
+
+impl<T> const PartialOrd for StabilityProbe<T>
+where
+  T: ~const PartialOrd,
+{
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    self.value.partial_cmp(&other.value)
+  }
+
+  fn lt(&self, other: &Self) -> bool {
+    self.value.lt(&other.value)
+  }
+}
+
+impl<T> const Ord for StabilityProbe<T>
+where
+  T: ~const PartialOrd + ~const Ord,
+{
+  fn cmp(&self, other: &Self) -> Ordering {
+    self.value.cmp(&other.value)
+  }
+}
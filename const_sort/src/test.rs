@@ -1,11 +1,45 @@
 extern crate alloc;
 
+use alloc::string::{String, ToString};
 use alloc::vec;
 use alloc::vec::Vec;
+use core::hint::black_box;
+use core::mem::MaybeUninit;
 use rand::{prelude::StdRng, Rng, SeedableRng};
 
-pub use crate::const_sort::{const_heapsort, const_quicksort};
-use crate::ConstSliceSortExt;
+use crate::const_array::{
+  const_binary_search_padded, const_dedup_by_key_keep_first, const_dedup_by_key_keep_last,
+  const_find_duplicate_key, const_fold_sorted_pairs, const_for_each_sorted_pair,
+  const_map_keys_monotonic, const_pad_sorted_to_pow2, const_prefix, const_sorted,
+  const_sorted_dedup, panic_duplicate_key,
+};
+pub use crate::const_sort::{
+  const_argsort_lexicographic, const_argsort_rows_by, const_binary_insertion_sort, const_bisect,
+  const_check_total_order, const_cocktail_sort, const_heapsort, const_kmerge_tree,
+  const_merge_sort, const_min_swaps_to_sort, const_partition_at_index_by_key,
+  const_partition_at_index_instrumented, const_quicksort, const_quicksort_instrumented,
+  const_same_order, const_search_sorted_matrix, const_selection_sort, const_sort_adaptive,
+  const_sort_bool, const_sort_by_external_key, const_sort_by_interned, const_sort_by_weight_table,
+  const_sort_byte_arrays, const_sort_each, const_sort_with, const_sorted_insert_into_prefix,
+  Algorithm, SelectStats, SortStats,
+};
+use crate::const_table::{
+  const_binary_search_parallel_key, const_group_reduce, const_lookup_by_parallel_key,
+  const_lookup_many, const_merge_join, const_merge_join_left, const_sorted_diff,
+};
+use crate::distance_cmp::cmp_by_distance_to;
+use crate::float_cmp::cmp_f64_with_epsilon;
+use crate::key_transform::{
+  char_to_key, f32_to_key, f64_to_key, i128_to_key, i8_to_key, key_to_char, key_to_f32, key_to_f64,
+  key_to_i128, key_to_i8,
+};
+use crate::testgen;
+use crate::{
+  self_check, ConstChunkedMap, ConstChunkedMapBuilder, ConstIndexMap, ConstInterpolationSearchExt,
+  ConstKey, ConstPermutationExt, ConstSliceSortExt, ConstSortable, ConstSorted, ConstSortedDesc,
+  ConstSortedDrain, ConstSortedMap, ConstSortedSet, ConstSortedSlice, ConstTable1, ConstTable2,
+  ConstTable3, ConstTable4, OrderSummary, SortedCursor, StabilityProbe, WithIndex,
+};
 
 const RAND_CNT: usize = 10_000;
 
@@ -14,49 +48,3411 @@ fn gen_array(n: usize) -> Vec<u32> {
   (0..n).map(|_| rng.gen()).collect()
 }
 
+/// Sorts copies of `v` with every sorting algorithm if the `cross-check` feature is enabled and
+/// checks that they agree element-wise.
+///
+/// The quadratic algorithms are only run on slices of up to 1000 elements.
+fn cross_check<T: Ord + Clone + core::fmt::Debug>(v: &[T]) {
+  if !cfg!(feature = "cross-check") {
+    return;
+  }
+  let mut quicksorted = v.to_vec();
+  const_quicksort(&mut quicksorted, PartialOrd::lt);
+  let mut sorted: Vec<(&str, Vec<T>)> = Vec::new();
+  let mut heapsorted = v.to_vec();
+  const_heapsort(&mut heapsorted, PartialOrd::lt);
+  sorted.push(("const_heapsort", heapsorted));
+  if v.len() <= 1000 {
+    let mut binary_insertion_sorted = v.to_vec();
+    const_binary_insertion_sort(&mut binary_insertion_sorted, PartialOrd::lt);
+    sorted.push(("const_binary_insertion_sort", binary_insertion_sorted));
+    let mut selection_sorted = v.to_vec();
+    const_selection_sort(&mut selection_sorted, PartialOrd::lt);
+    sorted.push(("const_selection_sort", selection_sorted));
+    let mut cocktail_sorted = v.to_vec();
+    const_cocktail_sort(&mut cocktail_sorted, PartialOrd::lt);
+    sorted.push(("const_cocktail_sort", cocktail_sorted));
+  }
+  let mut merge_sorted = v.to_vec();
+  const_merge_sort(&mut merge_sorted, PartialOrd::lt);
+  sorted.push(("const_merge_sort", merge_sorted));
+  let mut adaptive_sorted = v.to_vec();
+  const_sort_adaptive(&mut adaptive_sorted, PartialOrd::lt);
+  sorted.push(("const_sort_adaptive", adaptive_sorted));
+  for (algorithm, sorted) in sorted {
+    assert_eq!(
+      sorted, quicksorted,
+      "{algorithm} disagrees with const_quicksort on {v:?}"
+    );
+  }
+}
+
+#[test]
+fn const_core_slice_heapsort() {
+  const ARR: [u8; 4] = {
+    let mut v = [2, 3, 5, 4];
+    const_heapsort(&mut v, PartialOrd::lt);
+    v
+  };
+  assert_eq!(&ARR, &[2, 3, 4, 5]);
+}
+#[test]
+fn const_core_slice_heapsort_rng() {
+  let mut v = gen_array(RAND_CNT);
+  cross_check(&v);
+  const_heapsort(&mut v, PartialOrd::lt);
+  assert!(v.is_sorted());
+}
+
+#[test]
+fn const_core_slice_quicksort() {
+  const ARR: [u8; 4] = {
+    let mut v = [2, 3, 5, 4];
+    const_quicksort(&mut v, PartialOrd::lt);
+    v
+  };
+  assert_eq!(&ARR, &[2, 3, 4, 5]);
+}
+#[test]
+fn const_core_slice_quicksort_rng() {
+  let mut v = gen_array(RAND_CNT);
+  cross_check(&v);
+  const_quicksort(&mut v, PartialOrd::lt);
+  assert!(v.is_sorted());
+}
+
+#[test]
+fn const_core_slice_partition_at_index_by_key() {
+  const ARR: [i8; 5] = {
+    let mut v = [-5, 4, 1, -3, 2];
+    const_partition_at_index_by_key(&mut v, 2, const |k: &i8| k.unsigned_abs());
+    v
+  };
+  assert_eq!(ARR[2], -3);
+  assert!(ARR[..2].iter().all(|k| k.abs() < 3));
+}
+
+#[test]
+fn const_core_slice_sort_unstable() {
+  let mut v = gen_array(RAND_CNT);
+  cross_check(&v);
+  v.const_sort_unstable();
+  assert!(v.is_sorted());
+}
+#[test]
+fn const_core_slice_sort_unstable_by() {
+  let mut v = gen_array(RAND_CNT);
+  cross_check(&v);
+  v.const_sort_unstable_by(Ord::cmp);
+  assert!(v.is_sorted());
+}
+
+#[test]
+fn const_core_slice_order_summary() {
+  const S: OrderSummary<u8> = {
+    let mut v = [9, 1, 8, 2, 7, 3, 6, 4, 5];
+    v.const_order_summary()
+  };
+  assert_eq!(
+    S,
+    OrderSummary {
+      min: 1,
+      q1: 3,
+      median: 5,
+      q3: 7,
+      max: 9,
+    }
+  );
+
+  let orig = gen_array(RAND_CNT);
+  for len in (1..40).chain([RAND_CNT]) {
+    let mut v = orig[..len].to_vec();
+    let mut sorted = v.clone();
+    sorted.sort_unstable();
+    let last = len - 1;
+    assert_eq!(
+      v.const_order_summary(),
+      OrderSummary {
+        min: sorted[0],
+        q1: sorted[last / 4],
+        median: sorted[last / 2],
+        q3: sorted[3 * last / 4],
+        max: sorted[last],
+      }
+    );
+  }
+}
+
+#[test]
+fn const_permutation_of_0_to_n() {
+  const _: () = assert!([3usize, 0, 2, 1].const_is_permutation_of_0_to_n());
+  const _: () = assert!(![3usize, 0, 3, 1].const_is_permutation_of_0_to_n());
+  const _: () = assert!(![0usize, 4, 2, 1].const_is_permutation_of_0_to_n());
+  assert!([0usize; 0].const_is_permutation_of_0_to_n());
+
+  // Spans several bitmap windows.
+  let mut v: Vec<usize> = (0..RAND_CNT).rev().collect();
+  assert!(v.const_is_permutation_of_0_to_n());
+  v[RAND_CNT - 1] = RAND_CNT - 1;
+  assert!(!v.const_is_permutation_of_0_to_n());
+}
+
+#[test]
+fn const_core_slice_argsort_lexicographic() {
+  const KEYS1: [u8; 5] = [2, 1, 2, 1, 0];
+  const KEYS2: [char; 5] = ['b', 'z', 'a', 'a', 'q'];
+  const PERM: [usize; 5] = const_argsort_lexicographic(&KEYS1, &KEYS2);
+  assert_eq!(PERM, [4, 3, 1, 2, 0]);
+}
+#[test]
+fn const_core_slice_argsort_lexicographic_rng() {
+  let keys1: Vec<u32> = gen_array(RAND_CNT).iter().map(|k| k % 16).collect();
+  let keys2 = gen_array(RAND_CNT);
+  let perm: [usize; RAND_CNT] = const_argsort_lexicographic(&keys1, &keys2);
+  assert!(perm.const_is_permutation_of_0_to_n());
+  assert!(perm
+    .windows(2)
+    .all(|w| (keys1[w[0]], keys2[w[0]], w[0]) < (keys1[w[1]], keys2[w[1]], w[1])));
+}
+
+fn gen_table(n: usize, seed: u64) -> (Vec<u8>, Vec<u32>) {
+  let mut rng = StdRng::seed_from_u64(seed);
+  let mut keys: Vec<u8> = (0..n).map(|_| rng.gen_range(0..64)).collect();
+  keys.sort_unstable();
+  let vals = (0..n).map(|_| rng.gen()).collect();
+  (keys, vals)
+}
+
+#[test]
+fn const_table_merge_join() {
+  const JOINED: ([u8; 5], [char; 5], [i8; 5], usize) = {
+    let mut keys = [0; 5];
+    let mut a = ['\0'; 5];
+    let mut b = [0; 5];
+    let n = const_merge_join(
+      &[1, 2, 2, 5],
+      &['a', 'b', 'c', 'e'],
+      &[0, 2, 2, 5],
+      &[-1, -2, -3, -5],
+      &mut keys,
+      &mut a,
+      &mut b,
+    );
+    (keys, a, b, n)
+  };
+  assert_eq!(
+    JOINED,
+    (
+      [2, 2, 2, 2, 5],
+      ['b', 'b', 'c', 'c', 'e'],
+      [-2, -3, -2, -3, -5],
+      5
+    )
+  );
+
+  let (a_keys, a_vals) = gen_table(200, 1);
+  let (b_keys, b_vals) = gen_table(150, 2);
+  let mut expected = Vec::new();
+  for (ak, av) in a_keys.iter().zip(&a_vals) {
+    for (bk, bv) in b_keys.iter().zip(&b_vals) {
+      if ak == bk {
+        expected.push((*ak, *av, *bv));
+      }
+    }
+  }
+  let mut keys = vec![0; expected.len()];
+  let mut a = vec![0; expected.len()];
+  let mut b = vec![0; expected.len()];
+  let n = const_merge_join(
+    &a_keys, &a_vals, &b_keys, &b_vals, &mut keys, &mut a, &mut b,
+  );
+  assert_eq!(n, expected.len());
+  for (i, row) in expected.iter().enumerate() {
+    assert_eq!((keys[i], a[i], b[i]), *row);
+  }
+}
+#[test]
+fn const_table_merge_join_left() {
+  let (a_keys, a_vals) = gen_table(200, 3);
+  let (b_keys, b_vals) = gen_table(50, 4);
+  let mut expected = Vec::new();
+  for (ak, av) in a_keys.iter().zip(&a_vals) {
+    let len = expected.len();
+    for (bk, bv) in b_keys.iter().zip(&b_vals) {
+      if ak == bk {
+        expected.push((*ak, *av, Some(*bv)));
+      }
+    }
+    if len == expected.len() {
+      expected.push((*ak, *av, None));
+    }
+  }
+  let mut keys = vec![0; expected.len()];
+  let mut a = vec![0; expected.len()];
+  let mut b = vec![None; expected.len()];
+  let n = const_merge_join_left(
+    &a_keys, &a_vals, &b_keys, &b_vals, &mut keys, &mut a, &mut b,
+  );
+  assert_eq!(n, expected.len());
+  for (i, row) in expected.iter().enumerate() {
+    assert_eq!((keys[i], a[i], b[i]), *row);
+  }
+}
+#[test]
+#[should_panic(expected = "join output buffers are too short")]
+fn const_table_merge_join_short_output() {
+  const_merge_join(
+    &[1, 1],
+    &[(); 2],
+    &[1, 1],
+    &[(); 2],
+    &mut [0; 3],
+    &mut [(); 3],
+    &mut [(); 3],
+  );
+}
+#[test]
+fn const_table_group_reduce() {
+  const MAX: ([u8; 3], [i16; 3], usize) = {
+    let mut keys = [0; 3];
+    let mut aggs = [0; 3];
+    const fn max(a: i16, b: i16) -> i16 {
+      if a < b {
+        b
+      } else {
+        a
+      }
+    }
+    let n = const_group_reduce(
+      &[1, 1, 1, 4, 9, 9],
+      &[-3, 7, 2, 0, -8, -9],
+      &mut keys,
+      &mut aggs,
+      max,
+    );
+    (keys, aggs, n)
+  };
+  assert_eq!(MAX, ([1, 4, 9], [7, 0, -8], 3));
+
+  let (keys, vals) = gen_table(RAND_CNT, 5);
+  let vals: Vec<u64> = vals.into_iter().map(u64::from).collect();
+  let mut out_keys = [0; 64];
+  let mut out_aggs = [0; 64];
+  let n = const_group_reduce(&keys, &vals, &mut out_keys, &mut out_aggs, |a, b| a + b);
+  let mut expected_keys = keys.clone();
+  expected_keys.dedup();
+  assert_eq!(&out_keys[..n], &expected_keys[..]);
+  for (key, agg) in out_keys[..n].iter().zip(&out_aggs[..n]) {
+    let sum: u64 = keys
+      .iter()
+      .zip(&vals)
+      .filter(|(k, _)| *k == key)
+      .map(|(_, v)| v)
+      .sum();
+    assert_eq!(*agg, sum);
+  }
+}
+
+#[test]
+fn const_core_slice_insertion_index() {
+  const S: [u8; 7] = [1, 3, 3, 3, 3, 7, 9];
+  const FIRST: [usize; 4] = [
+    S.const_insertion_index(&0),
+    S.const_insertion_index(&3),
+    S.const_insertion_index(&4),
+    S.const_insertion_index(&10),
+  ];
+  assert_eq!(FIRST, [0, 1, 5, 7]);
+  const LAST: [usize; 4] = [
+    S.const_insertion_index_last(&0),
+    S.const_insertion_index_last(&3),
+    S.const_insertion_index_last(&4),
+    S.const_insertion_index_last(&10),
+  ];
+  assert_eq!(LAST, [0, 5, 5, 7]);
+
+  let mut v = gen_array(RAND_CNT);
+  v.iter_mut().for_each(|x| *x %= 1000);
+  v.sort_unstable();
+  for x in 0..1001 {
+    assert_eq!(v.const_insertion_index(&x), v.partition_point(|y| *y < x));
+    assert_eq!(
+      v.const_insertion_index_last(&x),
+      v.partition_point(|y| *y <= x)
+    );
+  }
+}
+
+#[test]
+fn const_array_sorted_dedup() {
+  const D: ([u8; 7], usize) = const_sorted_dedup([1, 1, 2, 5, 5, 5, 9]);
+  assert_eq!(D.0[..D.1], [1, 2, 5, 9]);
+  const P: [u8; 4] = const_prefix(D.0);
+  assert_eq!(P, [1, 2, 5, 9]);
+  const EMPTY: ([u8; 0], usize) = const_sorted_dedup([]);
+  assert_eq!(EMPTY.1, 0);
+
+  let mut v: [u32; RAND_CNT] = gen_array(RAND_CNT).try_into().unwrap();
+  v.iter_mut().for_each(|x| *x %= 100);
+  v.sort_unstable();
+  let mut expected = v.to_vec();
+  expected.dedup();
+  let (v, len) = const_sorted_dedup(v);
+  assert_eq!(v[..len], expected[..]);
+}
+#[test]
+#[should_panic(expected = "prefix is longer than the array")]
+fn const_prefix_too_long() {
+  let _: [u8; 3] = const_prefix(black_box([1, 2]));
+}
+
+#[test]
+fn const_core_slice_is_sorted_by_cmp() {
+  let empty: [i32; 0] = [];
+
+  assert!([1, 2, 2, 9].const_is_sorted_by_cmp(Ord::cmp));
+  assert!(![1, 3, 2].const_is_sorted_by_cmp(Ord::cmp));
+  assert!([9, 2, 2, 1].const_is_sorted_by_cmp(|a, b| b.cmp(a)));
+  assert!([0].const_is_sorted_by_cmp(|_, _| unreachable!()));
+  assert!(empty.const_is_sorted_by_cmp(|_, _| unreachable!()));
+  assert!(["c", "bb", "aaa"].const_is_sorted_by_cmp(|a, b| a.len().cmp(&b.len())));
+}
+
+#[test]
+fn const_core_slice_last_index_where() {
+  const S: [u8; 6] = [1, 3, 3, 5, 8, 9];
+  const I: Option<usize> = S.const_last_index_where(const |x: &u8| *x <= 4);
+  assert_eq!(I, Some(2));
+  const R: Option<&u8> = S.const_rfind_sorted(const |x: &u8| *x <= 4);
+  assert_eq!(R, Some(&3));
+
+  assert_eq!(S.const_last_index_where(|_| false), None);
+  assert_eq!(S.const_last_index_where(|_| true), Some(5));
+  assert_eq!([0u8; 0].const_last_index_where(|_| true), None);
+
+  let mut v = gen_array(RAND_CNT);
+  v.iter_mut().for_each(|x| *x %= 1000);
+  v.sort_unstable();
+  for x in 0..1001 {
+    let expected = v.partition_point(|y| *y <= x).checked_sub(1);
+    assert_eq!(v.const_last_index_where(|y| *y <= x), expected);
+  }
+}
+
+#[test]
+fn const_self_check() {
+  const _: () = assert!(self_check());
+  // Also run at runtime, where the sorts are covered by the coverage tools.
+  assert!(self_check());
+}
+
+#[test]
+fn const_core_slice_value_counts() {
+  const S: [u8; 7] = [2, 2, 2, 5, 6, 6, 9];
+  const UNIQUE: usize = S.const_unique_count();
+  assert_eq!(UNIQUE, 4);
+  const COUNTS: ([u8; 4], [usize; 4]) = {
+    let mut values = [0; 4];
+    let mut counts = [0; 4];
+    S.const_value_counts(&mut values, &mut counts);
+    (values, counts)
+  };
+  assert_eq!(COUNTS, ([2, 5, 6, 9], [3, 1, 2, 1]));
+  assert_eq!([0u8; 0].const_unique_count(), 0);
+
+  let mut v = gen_array(RAND_CNT);
+  v.iter_mut().for_each(|x| *x %= 100);
+  v.sort_unstable();
+  let mut values = [0; 100];
+  let mut counts = [0; 100];
+  let n = v.const_value_counts(&mut values, &mut counts);
+  assert_eq!(n, v.const_unique_count());
+  for (value, count) in values[..n].iter().zip(&counts[..n]) {
+    assert_eq!(v.iter().filter(|x| *x == value).count(), *count);
+  }
+  assert_eq!(counts[..n].iter().sum::<usize>(), RAND_CNT);
+}
+#[test]
+#[should_panic(expected = "value count buffers are too short")]
+fn const_core_slice_value_counts_short_buffer() {
+  [1, 2, 3].const_value_counts(&mut [0; 2], &mut [0; 3]);
+}
+
+#[test]
+fn const_core_slice_binary_insertion_sort() {
+  const ARR: [u8; 6] = {
+    let mut v = [2, 3, 5, 4, 1, 3];
+    const_binary_insertion_sort(&mut v, PartialOrd::lt);
+    v
+  };
+  assert_eq!(&ARR, &[1, 2, 3, 3, 4, 5]);
+
+  // Sort by the upper bits only and check that the order of equal keys is preserved.
+  let orig = gen_array(1000);
+  cross_check(&orig);
+  let mut v = orig.clone();
+  const_binary_insertion_sort(&mut v, |a, b| a >> 28 < b >> 28);
+  let mut expected = orig;
+  expected.sort_by_key(|x| x >> 28);
+  assert_eq!(v, expected);
+}
+
+#[test]
+fn const_core_slice_sort_by_external_key() {
+  const SORTED: ([&str; 4], [usize; 4]) = {
+    let mut values = ["ccc", "a", "dddd", "bb"];
+    let mut keys = [3, 1, 4, 2];
+    const_sort_by_external_key(&mut values, &mut keys);
+    (values, keys)
+  };
+  assert_eq!(SORTED, (["a", "bb", "ccc", "dddd"], [1, 2, 3, 4]));
+
+  let mut values = gen_array(RAND_CNT);
+  let mut keys: Vec<u32> = values.iter().map(|v| v.rotate_left(16)).collect();
+  cross_check(&keys);
+  const_sort_by_external_key(&mut values, &mut keys);
+  assert!(keys.is_sorted());
+  assert!(values
+    .iter()
+    .zip(&keys)
+    .all(|(v, k)| v.rotate_left(16) == *k));
+}
+#[test]
+#[should_panic(expected = "values and keys must have the same length")]
+fn const_core_slice_sort_by_external_key_length_mismatch() {
+  const_sort_by_external_key(&mut [1, 2, 3], &mut [1, 2]);
+}
+
+#[test]
+fn const_sorted_order_statistics() {
+  const S: ConstSorted<u8, 9> = ConstSorted::new([9, 1, 8, 2, 7, 3, 6, 4, 5]);
+  const NTH: (&[u8], &u8, &[u8]) = S.select_nth(2);
+  assert_eq!(NTH, (&[1, 2][..], &3, &[4, 5, 6, 7, 8, 9][..]));
+  const MIN_MAX_MEDIAN: [Option<&u8>; 3] = [S.min(), S.max(), S.median()];
+  assert_eq!(MIN_MAX_MEDIAN, [Some(&1), Some(&9), Some(&5)]);
+  const SUMMARY: OrderSummary<u8> = S.order_summary();
+  assert_eq!(SUMMARY, {
+    let mut v = S.into_inner();
+    v.const_order_summary()
+  });
+  assert_eq!(S.as_slice(), &*S);
+  const INNER: [u8; 9] = S.into_inner();
+  assert_eq!(INNER, [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+  const EMPTY: ConstSorted<u8, 0> = ConstSorted::new([]);
+  assert_eq!([EMPTY.min(), EMPTY.max(), EMPTY.median()], [None; 3]);
+
+  let v: [u32; RAND_CNT] = gen_array(RAND_CNT).try_into().unwrap();
+  let s = ConstSorted::new(v);
+  assert!(s.is_sorted());
+  for index in [0, 1, RAND_CNT / 2, RAND_CNT - 1] {
+    let mut expected = v;
+    assert_eq!(
+      s.select_nth(index).1,
+      expected.const_select_nth_unstable(index).1
+    );
+  }
+}
+
+#[test]
+fn const_sorted_drain_in_order() {
+  const DRAINED: ([u8; 6], Option<u8>) = {
+    let mut drain = ConstSortedDrain::new([5, 1, 4, 1, 3, 2]);
+    let mut out = [0; 6];
+    let mut i = 0;
+    while let Some(x) = drain.next_smallest() {
+      out[i] = x;
+      i += 1;
+    }
+    (out, drain.next_smallest())
+  };
+  assert_eq!(DRAINED, ([1, 1, 2, 3, 4, 5], None));
+
+  let v = gen_array(RAND_CNT);
+  let mut drain = ConstSortedDrain::<u32, RAND_CNT>::new(v.clone().try_into().unwrap());
+  let mut sorted = v;
+  sorted.sort_unstable();
+  for (i, x) in sorted.iter().enumerate() {
+    assert_eq!(drain.len(), RAND_CNT - i);
+    assert_eq!(drain.peek_smallest(), Some(x));
+    assert_eq!(drain.next_smallest(), Some(*x));
+  }
+  assert!(drain.is_empty());
+  assert_eq!(drain.peek_smallest(), None);
+}
+
+#[test]
+fn const_core_slice_kmerge_tree() {
+  const MERGED: ([(u8, char); 8], usize) = {
+    let mut out = [(0, '\0'); 8];
+    let n = const_kmerge_tree(
+      &[
+        &[(1, 'a'), (4, 'a')],
+        &[],
+        &[(1, 'c'), (2, 'c'), (9, 'c')],
+        &[(0, 'd'), (4, 'd'), (4, 'd')],
+      ],
+      &mut out,
+      const |a: &(u8, char), b: &(u8, char)| a.0 < b.0,
+    );
+    (out, n)
+  };
+  assert_eq!(
+    MERGED,
+    (
+      [
+        (0, 'd'),
+        (1, 'a'),
+        (1, 'c'),
+        (2, 'c'),
+        (4, 'a'),
+        (4, 'd'),
+        (4, 'd'),
+        (9, 'c')
+      ],
+      8
+    )
+  );
+
+  let v = gen_array(RAND_CNT);
+  let mut shards: Vec<Vec<u32>> = v.chunks(RAND_CNT / 13 + 1).map(<[u32]>::to_vec).collect();
+  for shard in &mut shards {
+    shard.sort_unstable();
+  }
+  let shards: [&[u32]; 13] = core::array::from_fn(|i| &shards[i][..]);
+  let mut out = vec![0; RAND_CNT];
+  assert_eq!(
+    const_kmerge_tree(&shards, &mut out, PartialOrd::lt),
+    RAND_CNT
+  );
+  let mut sorted = v;
+  sorted.sort_unstable();
+  assert_eq!(out, sorted);
+}
+
+#[test]
+#[should_panic(expected = "merge output buffer is too short")]
+fn const_core_slice_kmerge_tree_short_output() {
+  const_kmerge_tree(&[&[1, 2], &[3]], &mut [0; 2], PartialOrd::lt);
+}
+
+#[test]
+fn const_core_slice_is_sorted_chunks() {
+  let sorted: Vec<u32> = (0..20).collect();
+  for len in 0..sorted.len() {
+    let v = &sorted[..len];
+    assert!(v.const_is_sorted());
+    for i in 1..len {
+      let mut w = v.to_vec();
+      w.swap(i - 1, i);
+      assert!(!w.const_is_sorted());
+      assert!(!w.const_is_sorted_by(PartialOrd::partial_cmp));
+      assert!(!w.const_is_sorted_by_cmp(Ord::cmp));
+      assert!(!w.const_is_sorted_by_key(|x| *x));
+      // The scan stops at the first unsorted pair.
+      let mut calls = 0;
+      assert!(!w.const_is_sorted_by(|a, b| {
+        calls += 1;
+        a.partial_cmp(b)
+      }));
+      assert_eq!(calls, i);
+    }
+  }
+}
+
+#[test]
+fn const_core_slice_sort_block_lengths() {
+  // Elements of every size class use a different block length while partitioning.
+  let v = gen_array(RAND_CNT);
+  let mut small: Vec<u8> = v.iter().map(|x| u8::try_from(x % 256).unwrap()).collect();
+  let mut medium: Vec<(u64, u64)> = v.iter().map(|x| (u64::from(*x % 100), 0)).collect();
+  let mut large: Vec<[u64; 8]> = v
+    .iter()
+    .map(|x| [u64::from(*x), 0, 0, 0, 0, 0, 0, 0])
+    .collect();
+  small.const_sort_unstable();
+  medium.const_sort_unstable();
+  large.const_sort_unstable();
+  assert!(small.is_sorted());
+  assert!(medium.is_sorted());
+  assert!(large.is_sorted());
+}
+
+#[test]
+fn const_core_slice_sort_bool() {
+  const SORTED: [bool; 6] = {
+    let mut v = [true, false, true, true, false, false];
+    const_sort_bool(&mut v);
+    v
+  };
+  assert_eq!(SORTED, [false, false, false, true, true, true]);
+
+  let mut v: Vec<bool> = gen_array(RAND_CNT).iter().map(|x| x % 3 == 0).collect();
+  cross_check(&v);
+  let mut expected = v.clone();
+  expected.sort_unstable();
+  const_sort_bool(&mut v);
+  assert_eq!(v, expected);
+  const_sort_bool(&mut []);
+}
+
+#[test]
+fn const_core_slice_sort_byte_arrays() {
+  const SORTED: [[u8; 3]; 4] = {
+    let mut v = [[1, 2, 3], [1, 0, 9], [0, 255, 255], [1, 2, 2]];
+    const_sort_byte_arrays(&mut v);
+    v
+  };
+  assert_eq!(SORTED, [[0, 255, 255], [1, 0, 9], [1, 2, 2], [1, 2, 3]]);
+
+  let mut v: Vec<[u8; 4]> = gen_array(RAND_CNT)
+    .iter()
+    .map(|x| (x % 5000).to_be_bytes())
+    .collect();
+  cross_check(&v);
+  let mut expected = v.clone();
+  expected.sort_unstable();
+  const_sort_byte_arrays(&mut v);
+  assert_eq!(v, expected);
+}
+
+#[test]
+fn const_table_sorted_diff() {
+  const DIFF: ([u8; 4], [u8; 4], (usize, usize)) = {
+    let mut added = [0; 4];
+    let mut removed = [0; 4];
+    let counts = const_sorted_diff(&[1, 2, 2, 5, 7], &[0, 2, 5, 5, 9], &mut added, &mut removed);
+    (added, removed, counts)
+  };
+  assert_eq!(DIFF, ([0, 5, 9, 0], [1, 2, 7, 0], (3, 3)));
+
+  let old = gen_table(RAND_CNT, 1).0;
+  let new = gen_table(RAND_CNT, 2).0;
+  let mut added = vec![0; RAND_CNT];
+  let mut removed = vec![0; RAND_CNT];
+  let (n_added, n_removed) = const_sorted_diff(&old, &new, &mut added, &mut removed);
+  // Applying the diff to `old` yields `new`.
+  let mut patched = old;
+  for x in &removed[..n_removed] {
+    let pos = patched.binary_search(x).unwrap();
+    patched.remove(pos);
+  }
+  patched.extend_from_slice(&added[..n_added]);
+  patched.sort_unstable();
+  assert_eq!(patched, new);
+}
+
+#[test]
+#[should_panic(expected = "diff output buffers are too short")]
+fn const_table_sorted_diff_short_output() {
+  const_sorted_diff(&[1, 2], &[3], &mut [0; 1], &mut [0; 1]);
+}
+
+#[test]
+fn const_core_slice_nearest_sorted() {
+  const S: [u8; 4] = [10, 20, 20, 40];
+  const NEAREST: [usize; 7] = [
+    S.const_nearest_sorted(&0),
+    S.const_nearest_sorted(&14),
+    S.const_nearest_sorted(&15),
+    S.const_nearest_sorted(&16),
+    S.const_nearest_sorted(&20),
+    S.const_nearest_sorted(&31),
+    S.const_nearest_sorted(&255),
+  ];
+  assert_eq!(NEAREST, [0, 0, 0, 1, 1, 3, 3]);
+  const BY: usize = [(1, 'x'), (5, 'y'), (9, 'z')]
+    .const_nearest_sorted_by(&(4, 'a'), const |a: &(u8, char), b: &(u8, char)| {
+      a.0.abs_diff(b.0)
+    });
+  assert_eq!(BY, 1);
+
+  let mut v = gen_array(RAND_CNT);
+  v.sort_unstable();
+  for x in gen_array(100) {
+    let i = v.const_nearest_sorted(&x);
+    let best = v.iter().map(|y| y.abs_diff(x)).min().unwrap();
+    assert_eq!(v[i].abs_diff(x), best);
+    assert!(i == 0 || v[i - 1].abs_diff(x) > best);
+  }
+}
+
+#[test]
+fn const_core_slice_nearest_sorted_signed_bounds() {
+  const S: [i8; 2] = [-128, 127];
+  const NEAREST: [usize; 5] = [
+    S.const_nearest_sorted(&0),
+    S.const_nearest_sorted(&-1),
+    S.const_nearest_sorted(&-128),
+    S.const_nearest_sorted(&126),
+    [-128, -1].const_nearest_sorted(&-65),
+  ];
+  assert_eq!(NEAREST, [1, 0, 0, 1, 0]);
+
+  let v: Vec<i8> = vec![-128, -100, -3, 0, 5, 90, 127];
+  for x in i8::MIN..=i8::MAX {
+    for window in v.windows(2) {
+      let i = window.const_nearest_sorted(&x);
+      let best = window.iter().map(|y| y.abs_diff(x)).min().unwrap();
+      assert_eq!(window[i].abs_diff(x), best);
+      assert!(i == 0 || window[0].abs_diff(x) > best);
+    }
+    let i = v.const_nearest_sorted(&x);
+    assert_eq!(
+      v[i].abs_diff(x),
+      v.iter().map(|y| y.abs_diff(x)).min().unwrap()
+    );
+  }
+}
+
+#[test]
+#[should_panic(expected = "nearest element of an empty slice")]
+fn const_core_slice_nearest_sorted_empty() {
+  let _ = <[u8]>::const_nearest_sorted(&[], &1);
+}
+
+#[test]
+fn const_core_slice_interpolation_search() {
+  const S: [u64; 7] = [2, 4, 4, 4, 8, 16, u64::MAX];
+  const FOUND: [Result<usize, usize>; 6] = [
+    S.const_interpolation_search(&0),
+    S.const_interpolation_search(&2),
+    S.const_interpolation_search(&5),
+    S.const_interpolation_search(&16),
+    S.const_interpolation_search(&17),
+    S.const_interpolation_search(&u64::MAX),
+  ];
+  assert_eq!(FOUND, [Err(0), Ok(0), Err(4), Ok(5), Err(6), Ok(6)]);
+  assert!(matches!(S.const_interpolation_search(&4), Ok(1..=3)));
+  assert_eq!([0u32; 0].const_interpolation_search(&1), Err(0));
+
+  let mut v = gen_array(RAND_CNT);
+  v.sort_unstable();
+  // Skewed keys exhaust the interpolation probes.
+  let skewed: Vec<u64> = v.iter().map(|x| u64::from(*x).pow(2)).collect();
+  for x in gen_array(1000)
+    .into_iter()
+    .chain(v.iter().copied().step_by(7))
+  {
+    assert_eq!(
+      v.const_interpolation_search(&x).map(|i| v[i]),
+      v.binary_search(&x).map(|i| v[i])
+    );
+    let x = u64::from(x).pow(2);
+    assert_eq!(
+      skewed.const_interpolation_search(&x).map(|i| skewed[i]),
+      skewed.binary_search(&x).map(|i| skewed[i])
+    );
+  }
+}
+
+#[test]
+fn key_transform_preserves_order() {
+  const KEYS: [u8; 3] = [i8_to_key(i8::MIN), i8_to_key(-1), i8_to_key(i8::MAX)];
+  assert_eq!(KEYS, [0, 127, 255]);
+  const I8: i8 = key_to_i8(127);
+  assert_eq!(I8, -1);
+  const CHAR: Option<char> = key_to_char(char_to_key('x'));
+  assert_eq!(CHAR, Some('x'));
+  assert_eq!(key_to_char(0xD800), None);
+
+  let mut rng = StdRng::seed_from_u64(42);
+  let mut ints: Vec<i128> = (0..RAND_CNT).map(|_| rng.gen()).collect();
+  ints.extend([i128::MIN, -1, 0, 1, i128::MAX]);
+  let mut floats: Vec<f64> = (0..RAND_CNT).map(|_| f64::from_bits(rng.gen())).collect();
+  floats.extend([
+    f64::NEG_INFINITY,
+    -0.0,
+    0.0,
+    f64::INFINITY,
+    f64::NAN,
+    -f64::NAN,
+  ]);
+
+  for (a, b) in ints.iter().zip(ints.iter().rev()) {
+    assert_eq!(a.cmp(b), i128_to_key(*a).cmp(&i128_to_key(*b)));
+    assert_eq!(key_to_i128(i128_to_key(*a)), *a);
+  }
+  for (a, b) in floats.iter().zip(floats.iter().rev()) {
+    assert_eq!(a.total_cmp(b), f64_to_key(*a).cmp(&f64_to_key(*b)));
+    assert_eq!(key_to_f64(f64_to_key(*a)).to_bits(), a.to_bits());
+    // Rounding to the nearest `f32` is fine, only the narrowed values are compared below.
+    #[allow(clippy::cast_possible_truncation)]
+    let (a, b) = (*a as f32, *b as f32);
+    assert_eq!(a.total_cmp(&b), f32_to_key(a).cmp(&f32_to_key(b)));
+    assert_eq!(key_to_f32(f32_to_key(a)).to_bits(), a.to_bits());
+  }
+}
+
+#[test]
+fn stability_probe_detects_reordering() {
+  const PROBES: [StabilityProbe<u8>; 3] = StabilityProbe::probe_array([2, 1, 2]);
+  assert_eq!(PROBES.map(|p| p.index), [0, 1, 2]);
+  assert!(PROBES[0] == PROBES[2] && PROBES[1] < PROBES[0]);
+  const EMPTY: [StabilityProbe<u8>; 0] = StabilityProbe::probe_array([]);
+  assert!(StabilityProbe::is_stably_sorted(&EMPTY));
+  assert!(!StabilityProbe::is_stably_sorted(&PROBES));
+  let [a, b, c] = PROBES;
+  assert!(StabilityProbe::is_stably_sorted(&[b, a, c]));
+  assert!(!StabilityProbe::is_stably_sorted(&[b, c, a]));
+
+  let keys: [u8; RAND_CNT] = gen_table(RAND_CNT, 7).0.try_into().unwrap();
+  let mut shuffled = keys;
+  shuffled.reverse();
+  let mut probes = StabilityProbe::probe_array(shuffled);
+  const_binary_insertion_sort(&mut probes, PartialOrd::lt);
+  assert!(StabilityProbe::is_stably_sorted(&probes));
+}
+
+#[test]
+fn testgen_patterns() {
+  const ORGAN_PIPE: [usize; 7] = testgen::organ_pipe();
+  assert_eq!(ORGAN_PIPE, [0, 1, 2, 3, 2, 1, 0]);
+  const PUSH_FRONT: [usize; 4] = testgen::push_front();
+  assert_eq!(PUSH_FRONT, [3, 0, 1, 2]);
+  const SAWTOOTH: [usize; 5] = testgen::sawtooth(2);
+  assert_eq!(SAWTOOTH, [0, 1, 0, 1, 0]);
+  const KILLER: [usize; 8] = testgen::median_of_3_killer();
+  assert_eq!(KILLER, [0, 4, 2, 6, 1, 3, 5, 7]);
+  assert_eq!(testgen::descending::<3>(), [2, 1, 0]);
+  assert_eq!(testgen::ascending::<3>(), [0, 1, 2]);
+  assert_eq!(testgen::all_equal::<2>(9), [9, 9]);
+  assert_eq!(testgen::push_front::<0>(), []);
+  assert!(testgen::random::<RAND_CNT>(1, 10).iter().all(|x| *x < 10));
+  assert_eq!(testgen::random::<16>(5, 100), testgen::random::<16>(5, 100));
+
+  fn check<const N: usize>() {
+    let killer = testgen::median_of_3_killer::<N>();
+    assert!(killer.const_is_permutation_of_0_to_n());
+    let mut sorted = killer;
+    sorted.const_sort_unstable();
+    assert_eq!(sorted, testgen::ascending::<N>());
+  }
+  check::<0>();
+  check::<1>();
+  check::<6>();
+  check::<7>();
+  check::<RAND_CNT>();
+  check::<{ RAND_CNT + 3 }>();
+}
+
+#[test]
+fn const_core_slice_partition_by_median() {
+  const MEDIANS: [u8; 2] = [
+    *[3, 1, 2].const_partition_by_median().1,
+    *[4, 1, 3, 2].const_partition_by_median().1,
+  ];
+  assert_eq!(MEDIANS, [2, 3]);
+
+  let mut v = gen_array(RAND_CNT);
+  let mut sorted = v.clone();
+  sorted.sort_unstable();
+  let (left, median, right) = v.const_partition_by_median();
+  assert_eq!(*median, sorted[RAND_CNT / 2]);
+  assert_eq!(left.len(), RAND_CNT / 2);
+  assert!(left.iter().all(|x| x <= median) && right.iter().all(|x| x >= median));
+}
+
+#[test]
+#[should_panic]
+fn const_core_slice_partition_by_median_empty() {
+  <[u8]>::const_partition_by_median(&mut []);
+}
+
+#[test]
+#[cfg(not(any(feature = "force-heapsort", feature = "force-insertion")))]
+fn const_core_slice_quicksort_instrumented() {
+  const STATS: SortStats = {
+    let mut v = testgen::ascending::<1024>();
+    const_quicksort_instrumented(&mut v, PartialOrd::lt)
+  };
+  assert_eq!(STATS, SortStats::default());
+
+  let mut v = testgen::median_of_3_killer::<RAND_CNT>();
+  let stats = const_quicksort_instrumented(&mut v, PartialOrd::lt);
+  assert!(v.is_sorted());
+  assert!(stats.break_patterns > 0);
+
+  // An inconsistent comparator defeats every pivot, so heapsort has to take over.
+  let mut v = gen_array(RAND_CNT);
+  let stats = const_quicksort_instrumented(&mut v, |a, b| a % 7 < b % 5);
+  assert!(stats.heapsort_fallbacks > 0);
+}
+
+#[test]
+fn const_core_slice_binary_search_with_hint() {
+  const S: [u8; 6] = [1, 3, 3, 3, 7, 9];
+  const FOUND: [Result<usize, usize>; 6] = [
+    S.const_binary_search_with_hint(&0, 5),
+    S.const_binary_search_with_hint(&3, 0),
+    S.const_binary_search_with_hint(&8, 0),
+    S.const_binary_search_with_hint(&10, 2),
+    S.const_binary_search_with_hint(&9, 100),
+    S.const_binary_search_with_hint(&5, 4),
+  ];
+  assert_eq!(FOUND, [Err(0), Ok(1), Err(5), Err(6), Ok(5), Err(4)]);
+  assert_eq!([0u8; 0].const_binary_search_with_hint(&1, 3), Err(0));
+
+  let mut v = gen_array(RAND_CNT);
+  v.sort_unstable();
+  v.dedup();
+  let mut hint = 0;
+  // Walk the table in order, half of the queries are missing.
+  for x in v.iter().step_by(9).flat_map(|x| [*x, x + 1]) {
+    let found = v.const_binary_search_with_hint(&x, hint);
+    assert_eq!(found, v.binary_search(&x));
+    hint = found.unwrap_or_else(|i| i);
+  }
+}
+
+#[test]
+fn const_core_slice_min_swaps_to_sort() {
+  const SWAPS: [usize; 4] = [
+    const_min_swaps_to_sort(&[1, 2, 3]),
+    const_min_swaps_to_sort(&[2, 1, 3]),
+    const_min_swaps_to_sort(&[4, 3, 1, 2]),
+    const_min_swaps_to_sort::<u8, 0>(&[]),
+  ];
+  assert_eq!(SWAPS, [0, 1, 3, 0]);
+
+  // Performing the swaps along the cycles sorts the array in exactly that many swaps.
+  let mut v: [u32; RAND_CNT] = gen_array(RAND_CNT).try_into().unwrap();
+  let swaps = const_min_swaps_to_sort(&v);
+  let mut sorted = v;
+  sorted.sort_unstable();
+  let mut performed = 0;
+  for i in 0..RAND_CNT {
+    while v[i] != sorted[i] {
+      let target = sorted.binary_search(&v[i]).unwrap();
+      v.swap(i, target);
+      performed += 1;
+    }
+  }
+  assert_eq!(performed, swaps);
+}
+
+#[test]
+fn const_permutation_cycles() {
+  const CYCLES: ([usize; 6], [usize; 6], usize) = {
+    let mut out = [0; 6];
+    let mut cycle_lens = [0; 6];
+    let n = [1usize, 2, 0, 3, 5, 4].const_permutation_cycles(&mut out, &mut cycle_lens);
+    (out, cycle_lens, n)
+  };
+  assert_eq!(CYCLES, ([0, 1, 2, 3, 4, 5], [3, 1, 2, 0, 0, 0], 3));
+  assert_eq!(
+    <[usize]>::const_permutation_cycles(&[], &mut [], &mut []),
+    0
+  );
+
+  // Longer than the bitmap window, with cycles spanning several windows.
+  let perm: [usize; RAND_CNT] = {
+    let v = gen_array(RAND_CNT);
+    let mut perm: [usize; RAND_CNT] = core::array::from_fn(|i| i);
+    perm.sort_by_key(|i| v[*i]);
+    perm
+  };
+  let mut out = vec![0; RAND_CNT];
+  let mut cycle_lens = vec![0; RAND_CNT];
+  let n = perm.const_permutation_cycles(&mut out, &mut cycle_lens);
+  assert_eq!(cycle_lens[..n].iter().sum::<usize>(), RAND_CNT);
+  assert!(out.const_is_permutation_of_0_to_n());
+  let mut start = 0;
+  for len in &cycle_lens[..n] {
+    let cycle = &out[start..start + len];
+    assert!(cycle.iter().all(|i| *i >= cycle[0]));
+    for (k, i) in cycle.iter().enumerate() {
+      assert_eq!(perm[*i], cycle[(k + 1) % len]);
+    }
+    start += len;
+  }
+  // The number of cycles matches the minimum swap count of the sorted keys.
+  let keys: [u32; RAND_CNT] = gen_array(RAND_CNT).try_into().unwrap();
+  assert_eq!(const_min_swaps_to_sort(&keys), RAND_CNT - n);
+}
+
+#[test]
+#[should_panic(expected = "slice is not a permutation of 0..len")]
+fn const_permutation_cycles_invalid() {
+  [1usize, 1].const_permutation_cycles(&mut [0; 2], &mut [0; 2]);
+}
+
+#[test]
+fn const_key_newtype() {
+  #[derive(Debug, Clone, Copy, PartialEq)]
+  struct Id(u32, char);
+  impl const ConstKey for Id {
+    type Key = u32;
+
+    fn as_ord_key(&self) -> u32 {
+      self.0
+    }
+  }
+
+  const IDS: [Id; 4] = {
+    let mut ids = [Id(7, 'c'), Id(1, 'a'), Id(9, 'd'), Id(3, 'b')];
+    ids.const_sort_unstable_by_const_key();
+    ids
+  };
+  assert_eq!(IDS.map(|id| id.1), ['a', 'b', 'c', 'd']);
+  const FOUND: [Result<usize, usize>; 3] = [
+    IDS.const_search_by_const_key(&7),
+    IDS.const_search_by_const_key(&0),
+    IDS.const_search_by_const_key(&8),
+  ];
+  assert_eq!(FOUND, [Ok(2), Err(0), Err(3)]);
+
+  let mut ids: Vec<Id> = gen_array(RAND_CNT)
+    .into_iter()
+    .map(|x| Id(x, 'x'))
+    .collect();
+  ids.const_sort_unstable_by_const_key();
+  assert!(ids.is_sorted_by_key(|id| id.0));
+  for id in ids.iter().step_by(7) {
+    assert_eq!(ids[ids.const_search_by_const_key(&id.0).unwrap()].0, id.0);
+  }
+}
+
+#[test]
+fn const_core_bisect() {
+  const fn below_100(x: usize) -> bool {
+    x * x < 100
+  }
+  const FLIPS: [usize; 4] = [
+    const_bisect(0, 50, below_100),
+    const_bisect(12, 50, below_100),
+    const_bisect(0, 5, below_100),
+    const_bisect(7, 7, below_100),
+  ];
+  assert_eq!(FLIPS, [10, 12, 5, 7]);
+
+  let mut v = gen_array(RAND_CNT);
+  v.sort_unstable();
+  for x in v.iter().step_by(13).copied().chain([0, u32::MAX]) {
+    assert_eq!(
+      const_bisect(0, v.len(), |i| v[i] < x),
+      v.partition_point(|e| *e < x)
+    );
+  }
+  assert_eq!(
+    const_bisect(0, usize::MAX, |i| i < usize::MAX - 1),
+    usize::MAX - 1
+  );
+}
+
+#[test]
+#[should_panic(expected = "lo must not be greater than hi")]
+fn const_core_bisect_invalid_range() {
+  let _ = const_bisect(3, 2, |_| true);
+}
+
+#[test]
+fn const_core_search_sorted_matrix() {
+  const M: [[u8; 4]; 3] = [[1, 4, 7, 11], [2, 5, 8, 12], [3, 6, 9, 16]];
+  const FOUND: [Option<(usize, usize)>; 4] = [
+    const_search_sorted_matrix(&M, &5),
+    const_search_sorted_matrix(&M, &16),
+    const_search_sorted_matrix(&M, &10),
+    const_search_sorted_matrix(&M, &0),
+  ];
+  assert_eq!(FOUND, [Some((1, 1)), Some((2, 3)), None, None]);
+  assert_eq!(const_search_sorted_matrix::<u8, 2, 0>(&[[], []], &1), None);
+  assert_eq!(const_search_sorted_matrix::<u8, 0, 2>(&[], &1), None);
+
+  // A distance grid is sorted by rows and columns.
+  let grid: [[u32; 64]; 48] =
+    core::array::from_fn(|r| core::array::from_fn(|c| u32::try_from(r * r + c * c).unwrap()));
+  for x in 0..6000 {
+    let expected = grid.iter().flatten().any(|e| *e == x);
+    match const_search_sorted_matrix(&grid, &x) {
+      Some((r, c)) => assert_eq!(grid[r][c], x),
+      None => assert!(!expected),
+    }
+  }
+}
+
+#[test]
+fn const_core_argsort_rows_by() {
+  const M: [[u8; 3]; 4] = [[1, 9, 0], [0, 9, 1], [1, 2, 2], [0, 9, 3]];
+  const BY_1_0: [usize; 4] = const_argsort_rows_by(&M, &[1, 0]);
+  assert_eq!(BY_1_0, [2, 1, 3, 0]);
+  const UNSORTED: [usize; 4] = const_argsort_rows_by(&M, &[]);
+  assert_eq!(UNSORTED, [0, 1, 2, 3]);
+
+  let (keys, vals) = gen_table(RAND_CNT, 3);
+  let rows: Vec<[u32; 2]> = keys
+    .iter()
+    .zip(&vals)
+    .map(|(k, v)| [u32::from(*k) % 7, *v])
+    .collect();
+  let m: &[[u32; 2]; RAND_CNT] = rows.as_slice().try_into().unwrap();
+  let perm = const_argsort_rows_by(m, &[0, 1]);
+  let mut expected: Vec<usize> = (0..RAND_CNT).collect();
+  expected.sort_by_key(|i| rows[*i]);
+  assert_eq!(perm.to_vec(), expected);
+}
+
+#[test]
+#[should_panic(expected = "key column out of bounds")]
+fn const_core_argsort_rows_by_invalid_column() {
+  let _ = const_argsort_rows_by(&[[1, 2]], black_box(&[2]));
+}
+
+#[test]
+fn const_core_slice_min_max_by_key() {
+  const fn len(s: &&str) -> usize {
+    s.len()
+  }
+  const WORDS: [&str; 5] = ["bb", "a", "ccc", "d", "eee"];
+  const MIN_MAX: [Option<&&str>; 2] = [WORDS.const_min_by_key(len), WORDS.const_max_by_key(len)];
+  assert_eq!(MIN_MAX, [Some(&"a"), Some(&"eee")]);
+  const EMPTY: [&str; 0] = [];
+  assert_eq!(EMPTY.const_min_by_key(len), None);
+  assert_eq!(EMPTY.const_max_by_key(len), None);
+
+  let v = gen_array(RAND_CNT);
+  let key = |x: &u32| x % 1000;
+  assert!(core::ptr::eq(
+    v.const_min_by_key(key).unwrap(),
+    v.iter().min_by_key(|x| key(x)).unwrap()
+  ));
+  assert!(core::ptr::eq(
+    v.const_max_by_key(key).unwrap(),
+    v.iter().max_by_key(|x| key(x)).unwrap()
+  ));
+}
+
+#[test]
+fn const_core_slice_contains_position() {
+  const S: [char; 4] = ['d', 'a', 'c', 'a'];
+  const FOUND: [Option<usize>; 2] = [S.const_position(&'a'), S.const_position(&'z')];
+  assert_eq!(FOUND, [Some(1), None]);
+  const CONTAINS: [bool; 2] = [S.const_contains(&'c'), S.const_contains(&'z')];
+  assert_eq!(CONTAINS, [true, false]);
+
+  let v = gen_array(RAND_CNT);
+  for x in v.iter().step_by(97).chain(&[0, 1, u32::MAX]) {
+    assert_eq!(v.const_position(x), v.iter().position(|y| y == x));
+    assert_eq!(v.const_contains(x), v.contains(x));
+  }
+}
+
+#[test]
+fn const_core_check_total_order() {
+  const CHECKS: [Option<(usize, usize)>; 3] = [
+    const_check_total_order(&[3, 1, 2, 1], Ord::cmp),
+    // Not reflexive.
+    const_check_total_order(&[3, 1, 2, 1], const |a: &u8, b: &u8| {
+      if *a == 2 && *b == 2 {
+        core::cmp::Ordering::Less
+      } else {
+        a.cmp(b)
+      }
+    }),
+    // Rock paper scissors is not transitive.
+    const_check_total_order(&[0, 1, 2], const |a: &u8, b: &u8| match (a, b) {
+      (0, 1) | (1, 2) | (2, 0) => core::cmp::Ordering::Less,
+      (1, 0) | (2, 1) | (0, 2) => core::cmp::Ordering::Greater,
+      _ => core::cmp::Ordering::Equal,
+    }),
+  ];
+  assert_eq!(CHECKS, [None, Some((2, 2)), Some((0, 2))]);
+
+  // Comparing the absolute difference to a threshold is not transitive for equality.
+  let sample = [0.0, 0.6, 1.2];
+  let approx = |a: &f64, b: &f64| {
+    if (a - b).abs() < 1.0 {
+      core::cmp::Ordering::Equal
+    } else {
+      a.total_cmp(b)
+    }
+  };
+  assert_eq!(const_check_total_order(&sample, approx), Some((0, 2)));
+  // An asymmetric comparator is caught before transitivity is checked.
+  assert_eq!(
+    const_check_total_order(&[1, 2], |a: &u8, _: &u8| a.cmp(&1)),
+    Some((0, 1))
+  );
+  let v = gen_array(50);
+  assert_eq!(const_check_total_order(&v, Ord::cmp), None);
+}
+
+#[test]
+fn const_core_same_order() {
+  // Comparators take their elements by reference.
+  #[allow(clippy::trivially_copy_pass_by_ref)]
+  const fn by_value(a: &u8, b: &u8) -> core::cmp::Ordering {
+    a.cmp(b)
+  }
+  #[allow(clippy::trivially_copy_pass_by_ref)]
+  const fn by_second(a: &(char, i16), b: &(char, i16)) -> core::cmp::Ordering {
+    a.1.cmp(&b.1)
+  }
+  const CHECKS: [bool; 4] = [
+    const_same_order(
+      &[3, 1, 2, 1],
+      &[('a', 9), ('b', -1), ('c', 4), ('d', -1)],
+      by_value,
+      by_second,
+    ),
+    const_same_order(
+      &[3, 1, 2, 1],
+      &[('a', 9), ('b', -1), ('c', 4), ('d', 0)],
+      by_value,
+      by_second,
+    ),
+    const_same_order(&[3, 1, 2], &[('a', 9), ('b', -1)], by_value, by_second),
+    const_same_order(&[], &[], by_value, by_second),
+  ];
+  assert_eq!(CHECKS, [true, false, false, true]);
+
+  let v = gen_array(300);
+  let names: Vec<String> = v.iter().map(|x| alloc::format!("{x:010}")).collect();
+  assert!(const_same_order(&v, &names, Ord::cmp, Ord::cmp));
+  let mut shuffled = names;
+  shuffled.swap(0, 1);
+  assert!(!const_same_order(&v, &shuffled, Ord::cmp, Ord::cmp));
+}
+
+#[test]
+fn const_with_index() {
+  const SORTED: ([u8; 6], [usize; 6]) = {
+    let values = [3, 1, 3, 2, 1, 3];
+    let mut pairs = [WithIndex::new(0, 0); 6];
+    WithIndex::attach_indices(&values, &mut pairs);
+    pairs.const_sort_unstable();
+    let mut sorted = [0; 6];
+    WithIndex::detach_indices(&pairs, &mut sorted);
+    let mut indices = [0; 6];
+    let mut i = 0;
+    while i < pairs.len() {
+      indices[i] = pairs[i].index;
+      i += 1;
+    }
+    (sorted, indices)
+  };
+  assert_eq!(SORTED, ([1, 1, 2, 3, 3, 3], [1, 4, 3, 0, 2, 5]));
+  assert!(WithIndex::new(1, 0) < WithIndex::new(1, 1));
+  assert!(WithIndex::new(1, 5) < WithIndex::new(2, 0));
+  assert_ne!(WithIndex::new(1, 0), WithIndex::new(1, 1));
+
+  let v = gen_array(RAND_CNT);
+  let mut pairs = vec![WithIndex::new(0, 0); v.len()];
+  WithIndex::attach_indices(&v, &mut pairs);
+  pairs.const_sort_unstable();
+  let mut expected: Vec<_> = v.iter().copied().enumerate().collect();
+  expected.sort_by_key(|&(_, x)| x);
+  assert!(pairs
+    .iter()
+    .zip(&expected)
+    .all(|(p, &(i, x))| p.index == i && p.value == x));
+  let mut sorted = vec![0; v.len()];
+  WithIndex::detach_indices(&pairs, &mut sorted);
+  assert!(sorted.is_sorted());
+}
+
+#[test]
+fn const_core_selection_sort() {
+  // Arrays have no const `PartialOrd`, so the rows are compared element by element.
+  const ROWS: [[u8; 64]; 4] = {
+    let mut v = [[3; 64], [1; 64], [2; 64], [1; 64]];
+    const_selection_sort(&mut v, const |a: &[u8; 64], b: &[u8; 64]| {
+      let mut i = 0;
+      while i < a.len() && a[i] == b[i] {
+        i += 1;
+      }
+      i < a.len() && a[i] < b[i]
+    });
+    v
+  };
+  assert_eq!(ROWS, [[1; 64], [1; 64], [2; 64], [3; 64]]);
+
+  let mut comparisons = 0;
+  let mut v = [4, 3, 2, 1, 0];
+  const_selection_sort(&mut v, |a, b| {
+    comparisons += 1;
+    a < b
+  });
+  assert_eq!(v, [0, 1, 2, 3, 4]);
+  assert_eq!(comparisons, 10);
+
+  let mut empty: [u8; 0] = [];
+  const_selection_sort(&mut empty, PartialOrd::lt);
+
+  let mut v = gen_array(RAND_CNT);
+  cross_check(&v);
+  let mut expected = v.clone();
+  expected.sort_unstable();
+  const_selection_sort(&mut v, PartialOrd::lt);
+  assert_eq!(v, expected);
+}
+
+#[test]
+fn const_core_cocktail_sort() {
+  const SORTED: [[u8; 6]; 3] = {
+    let mut nearly_sorted = [0, 1, 3, 2, 4, 5];
+    const_cocktail_sort(&mut nearly_sorted, PartialOrd::lt);
+    let mut push_front = [5, 0, 1, 2, 3, 4];
+    const_cocktail_sort(&mut push_front, PartialOrd::lt);
+    let mut reversed = [5, 4, 3, 2, 1, 0];
+    const_cocktail_sort(&mut reversed, PartialOrd::lt);
+    [nearly_sorted, push_front, reversed]
+  };
+  assert_eq!(SORTED, [[0, 1, 2, 3, 4, 5]; 3]);
+
+  let mut comparisons = 0;
+  let mut v = [1, 0, 2, 3, 4, 5, 6, 7];
+  const_cocktail_sort(&mut v, |a, b| {
+    comparisons += 1;
+    a < b
+  });
+  assert_eq!(v, [0, 1, 2, 3, 4, 5, 6, 7]);
+  assert_eq!(comparisons, 7);
+
+  let mut probes = StabilityProbe::probe_array(testgen::random::<200>(7, 10));
+  const_cocktail_sort(&mut probes, PartialOrd::lt);
+  assert!(StabilityProbe::is_stably_sorted(&probes));
+
+  let mut v = gen_array(RAND_CNT);
+  cross_check(&v);
+  let mut expected = v.clone();
+  expected.sort_unstable();
+  const_cocktail_sort(&mut v, PartialOrd::lt);
+  assert_eq!(v, expected);
+}
+
+#[test]
+fn const_core_merge_sort() {
+  const SORTED: [[u8; 24]; 2] = {
+    let mut reversed = [0; 24];
+    let mut i = 0;
+    while i < 24 {
+      reversed[usize::from(i)] = 23 - i;
+      i += 1;
+    }
+    const_merge_sort(&mut reversed, PartialOrd::lt);
+    let mut interleaved = [0; 24];
+    let mut i = 0;
+    while i < 24 {
+      interleaved[usize::from(i)] = if i % 2 == 0 { i / 2 } else { 12 + i / 2 };
+      i += 1;
+    }
+    const_merge_sort(&mut interleaved, PartialOrd::lt);
+    [reversed, interleaved]
+  };
+  let expected: [u8; 24] = core::array::from_fn(|i| u8::try_from(i).unwrap());
+  assert_eq!(SORTED, [expected; 2]);
+
+  for len in [0, 1, 2, 21, 22, 41, 100, 257] {
+    let mut probes =
+      StabilityProbe::probe_array(testgen::random::<300>(u32::try_from(len).unwrap() + 1, 8));
+    let probes = &mut probes[..len];
+    const_merge_sort(probes, PartialOrd::lt);
+    assert!(StabilityProbe::is_stably_sorted(probes), "{len}");
+  }
+
+  let mut v = gen_array(RAND_CNT);
+  cross_check(&v);
+  let mut expected = v.clone();
+  expected.sort_unstable();
+  const_merge_sort(&mut v, PartialOrd::lt);
+  assert_eq!(v, expected);
+}
+
+#[test]
+fn const_core_sort_adaptive() {
+  const SORTED: [[u8; 30]; 2] = {
+    // Three sorted segments and a descending one.
+    let mut segments = [0; 30];
+    let mut i = 0;
+    while i < 30 {
+      segments[usize::from(i)] = match i {
+        0..=9 => i * 3,
+        10..=19 => i * 3 - 29,
+        20..=24 => i * 3 - 58,
+        _ => 29 - (i - 25) * 3,
+      };
+      i += 1;
+    }
+    const_sort_adaptive(&mut segments, PartialOrd::lt);
+    let mut reversed = [0; 30];
+    let mut i = 0;
+    while i < 30 {
+      reversed[usize::from(i)] = 29 - i;
+      i += 1;
+    }
+    const_sort_adaptive(&mut reversed, PartialOrd::lt);
+    [segments, reversed]
+  };
+  let expected: [u8; 30] = core::array::from_fn(|i| u8::try_from(i).unwrap());
+  assert_eq!(SORTED[1], expected);
+  let mut segments = SORTED[0];
+  segments.sort_unstable();
+  assert_eq!(SORTED[0], segments);
+
+  // Sorted and reversed slices take a linear number of comparisons.
+  for reverse in [false, true] {
+    let mut v: Vec<u32> = (0..1000).collect();
+    if reverse {
+      v.reverse();
+    }
+    let mut comparisons = 0;
+    const_sort_adaptive(&mut v, |a, b| {
+      comparisons += 1;
+      a < b
+    });
+    assert!(v.windows(2).all(|w| w[0] < w[1]));
+    assert!(comparisons < 2000, "{comparisons}");
+  }
+
+  let mut v = gen_array(RAND_CNT);
+  v.sort_unstable();
+  let mut expected = v.clone();
+  // Concatenate rotated sorted chunks, i.e. eight sorted runs.
+  let mut segmented = Vec::new();
+  for chunk in v.chunks(RAND_CNT / 4 + 1) {
+    let mut chunk = chunk.to_vec();
+    let mid = chunk.len() / 3;
+    chunk.rotate_left(mid);
+    segmented.extend(chunk);
+  }
+  cross_check(&segmented);
+  const_sort_adaptive(&mut segmented, PartialOrd::lt);
+  assert_eq!(segmented, expected);
+
+  let mut probes = StabilityProbe::probe_array(testgen::random::<500>(3, 16));
+  const_sort_adaptive(&mut probes, PartialOrd::lt);
+  assert!(StabilityProbe::is_stably_sorted(&probes));
+
+  let mut v = gen_array(RAND_CNT);
+  cross_check(&v);
+  expected = v.clone();
+  expected.sort_unstable();
+  const_sort_adaptive(&mut v, PartialOrd::lt);
+  assert_eq!(v, expected);
+}
+
+#[test]
+fn const_sortable_bound() {
+  const fn sorted_median<T, const N: usize>(mut arr: [T; N]) -> T
+  where
+    T: ~const ConstSortable + Copy,
+  {
+    arr.const_sort_unstable();
+    arr[N / 2]
+  }
+  const MEDIANS: (u8, char, (i32, bool)) = (
+    sorted_median([3, 9, 1, 7, 5]),
+    sorted_median(['d', 'a', 'c']),
+    sorted_median([(1, true), (-4, false), (1, false)]),
+  );
+  assert_eq!(MEDIANS, (5, 'c', (1, false)));
+
+  let v = gen_array(RAND_CNT);
+  let mut expected = v.clone();
+  expected.sort_unstable();
+  assert_eq!(
+    sorted_median::<_, RAND_CNT>(v.try_into().unwrap()),
+    expected[RAND_CNT / 2]
+  );
+}
+
+#[test]
+fn const_select_range_unstable() {
+  const SELECTED: ([u8; 10], [u8; 10]) = {
+    let mut v = [9, 3, 0, 7, 2, 5, 1, 8, 6, 4];
+    v.const_select_range_unstable(3, 6);
+    let mut single = [9, 3, 0, 7, 2, 5, 1, 8, 6, 4];
+    single.const_select_range_unstable(9, 9);
+    (v, single)
+  };
+  assert_eq!(SELECTED.0[3..=6], [3, 4, 5, 6]);
+  assert!(SELECTED.0[..3].iter().all(|&x| x < 3));
+  assert!(SELECTED.0[7..].iter().all(|&x| x > 6));
+  assert_eq!(SELECTED.1[9], 9);
+
+  let mut v = gen_array(RAND_CNT);
+  let mut expected = v.clone();
+  expected.sort_unstable();
+  let (lo, hi) = (RAND_CNT / 10, RAND_CNT - RAND_CNT / 10 - 1);
+  assert_eq!(v.const_select_range_unstable(lo, hi), &expected[lo..=hi]);
+  assert!(v[..lo].iter().all(|x| *x <= expected[lo]));
+  assert!(v[hi + 1..].iter().all(|x| *x >= expected[hi]));
+  assert_eq!(
+    v.const_select_range_unstable(0, RAND_CNT - 1),
+    &expected[..]
+  );
+}
+
+#[test]
+#[should_panic(expected = "selection range out of bounds")]
+fn const_select_range_unstable_out_of_bounds() {
+  [1, 2, 3].const_select_range_unstable(1, 3);
+}
+
+#[test]
+fn const_trimmed_statistics() {
+  const TRIMMED: ([i8; 9], (i8, i8), (i8, i8)) = {
+    let mut v = [5, -90, 3, 2, 100, 4, 1, 6, 7];
+    v.const_trim_unstable(2);
+    let mut bounds = [5, -90, 3, 2, 100, 4, 1, 6, 7];
+    let (low, high) = bounds.const_trimmed_mean_bounds(2);
+    let mut single = [2, 1, 3];
+    let (only_low, only_high) = single.const_trimmed_mean_bounds(1);
+    (v, (*low, *high), (*only_low, *only_high))
+  };
+  let mut kept = TRIMMED.0[2..7].to_vec();
+  kept.sort_unstable();
+  assert_eq!(kept, [2, 3, 4, 5, 6]);
+  assert_eq!((TRIMMED.0[2], TRIMMED.0[6]), (2, 6));
+  assert!(TRIMMED.0[..2].iter().all(|&x| x < 2));
+  assert!(TRIMMED.0[7..].iter().all(|&x| x > 6));
+  assert_eq!(TRIMMED.1, (2, 6));
+  assert_eq!(TRIMMED.2, (2, 2));
+
+  let mut v = gen_array(RAND_CNT);
+  let mut expected = v.clone();
+  expected.sort_unstable();
+  let trim = RAND_CNT / 20;
+  let (low, high) = v.const_trimmed_mean_bounds(trim);
+  assert_eq!(
+    (*low, *high),
+    (expected[trim], expected[RAND_CNT - trim - 1])
+  );
+  let mut kept = v.const_trim_unstable(trim).to_vec();
+  kept.sort_unstable();
+  assert_eq!(kept, &expected[trim..RAND_CNT - trim]);
+}
+
+#[test]
+#[should_panic(expected = "trimming removes all elements")]
+fn const_trim_unstable_all() {
+  [1, 2, 3, 4].const_trim_unstable(2);
+}
+
+#[test]
+fn float_cmp_with_epsilon() {
+  const SORTED: ([f64; 6], usize) = {
+    let mut v = [2.0, 1.0005, 3.0, 0.9995, 2.0004, 1.0];
+    let cmp = cmp_f64_with_epsilon(0.001);
+    v.const_sort_unstable_by(cmp);
+    let mut clusters = 1;
+    let mut i = 1;
+    while i < v.len() {
+      if !matches!(cmp.compare(v[i - 1], v[i]), core::cmp::Ordering::Equal) {
+        clusters += 1;
+      }
+      i += 1;
+    }
+    (v, clusters)
+  };
+  assert!(SORTED.0[..3].iter().all(|x| (x - 1.0).abs() <= 0.001));
+  assert!(SORTED.0[3..5].iter().all(|x| (x - 2.0).abs() <= 0.001));
+  assert_eq!(SORTED.0[5].to_bits(), 3.0f64.to_bits());
+  assert_eq!(SORTED.1, 3);
+
+  let cmp = cmp_f64_with_epsilon(0.5);
+  assert_eq!(cmp(&1.0, &1.5), core::cmp::Ordering::Equal);
+  assert_eq!(cmp(&1.0, &1.6), core::cmp::Ordering::Less);
+  assert_eq!(cmp(&-0.0, &0.0), core::cmp::Ordering::Equal);
+  assert_eq!(cmp(&f64::NAN, &f64::NAN), core::cmp::Ordering::Equal);
+  assert_eq!(cmp(&1.0, &f64::NAN), core::cmp::Ordering::Less);
+  assert_eq!(
+    cmp(&f64::NEG_INFINITY, &f64::MIN),
+    core::cmp::Ordering::Less
+  );
+  assert_eq!(const_check_total_order(&[0.0, 0.4, 0.8], cmp), Some((0, 2)));
+  assert_eq!(const_check_total_order(&[0.0, 0.4, 2.0, 2.1], cmp), None);
+  assert_eq!(
+    cmp_f64_with_epsilon(0.0).compare(1.0, 1.0),
+    core::cmp::Ordering::Equal
+  );
+
+  let mut v: Vec<f64> = gen_array(RAND_CNT)
+    .iter()
+    .map(|&x| f64::from(x) / 7.0)
+    .collect();
+  let mut expected = v.clone();
+  expected.sort_by(f64::total_cmp);
+  v.const_sort_unstable_by(cmp_f64_with_epsilon(0.0));
+  assert_eq!(v, expected);
+}
+
+#[test]
+#[should_panic(expected = "epsilon must not be negative or NaN")]
+fn float_cmp_with_nan_epsilon() {
+  let _ = cmp_f64_with_epsilon(black_box(f64::NAN));
+}
+
+#[test]
+fn const_core_sort_by_weight_table() {
+  #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+  enum Op {
+    Load,
+    Store,
+    Add,
+    Jump,
+  }
+  const FREQUENCY_RANK: [u32; 4] = [0, 2, 1, 3];
+  const OPS: [Op; 6] = {
+    let mut ops = [Op::Jump, Op::Add, Op::Store, Op::Load, Op::Add, Op::Load];
+    const_sort_by_weight_table(&mut ops, const |op: &Op| *op as usize, &FREQUENCY_RANK);
+    ops
+  };
+  assert_eq!(
+    OPS,
+    [Op::Load, Op::Load, Op::Add, Op::Add, Op::Store, Op::Jump]
+  );
+
+  let weights: Vec<u32> = (0..256).map(|i| i * 37 % 256).collect();
+  let mut sorted = gen_array(RAND_CNT);
+  const_sort_by_weight_table(&mut sorted, |x| (*x as usize) % 256, &weights);
+  assert!(sorted
+    .windows(2)
+    .all(|w| weights[w[0] as usize % 256] <= weights[w[1] as usize % 256]));
+}
+
+#[test]
+fn const_core_sort_by_interned() {
+  const fn symbol(entry: &(usize, u8)) -> usize {
+    entry.0
+  }
+  const UNSORTED: [&str; 4] = ["width", "align", "height", "alignment"];
+  const SORTED: [&str; 4] = ["align", "alignment", "height", "width"];
+  const ENTRIES: [[(usize, u8); 4]; 2] = {
+    let mut by_unsorted = [(0, 1), (1, 2), (2, 3), (3, 4)];
+    const_sort_by_interned(&mut by_unsorted, symbol, &UNSORTED);
+    let mut by_sorted = [(3, 1), (1, 2), (0, 3), (2, 4)];
+    const_sort_by_interned(&mut by_sorted, symbol, &SORTED);
+    [by_unsorted, by_sorted]
+  };
+  assert_eq!(
+    ENTRIES,
+    [
+      [(1, 2), (3, 4), (2, 3), (0, 1)],
+      [(0, 3), (1, 2), (2, 4), (3, 1)]
+    ]
+  );
+
+  let names: Vec<String> = gen_array(200)
+    .iter()
+    .map(|x| (x % 1000).to_string())
+    .collect();
+  let strings: Vec<&str> = names.iter().map(String::as_str).collect();
+  let mut ids: Vec<usize> = gen_array(RAND_CNT)
+    .iter()
+    .map(|x| *x as usize % 200)
+    .collect();
+  const_sort_by_interned(&mut ids, |id| *id, &strings);
+  assert!(ids.windows(2).all(|w| strings[w[0]] <= strings[w[1]]));
+}
+
+#[test]
+#[should_panic(expected = "interned id is out of bounds")]
+fn const_core_sort_by_interned_out_of_bounds() {
+  const_sort_by_interned(&mut [0, 2], |id| *id, &["a", "b"]);
+}
+
+#[test]
+fn const_array_dedup_by_key() {
+  const TABLE: [(u8, char); 7] = [
+    (1, 'a'),
+    (1, 'b'),
+    (2, 'c'),
+    (3, 'd'),
+    (3, 'e'),
+    (3, 'f'),
+    (4, 'g'),
+  ];
+  const FIRST: ([(u8, char); 7], usize) =
+    const_dedup_by_key_keep_first(TABLE, const |entry: &(u8, char)| entry.0);
+  const LAST: ([(u8, char); 7], usize) =
+    const_dedup_by_key_keep_last(TABLE, const |entry: &(u8, char)| entry.0);
+  assert_eq!(FIRST.0[..FIRST.1], [(1, 'a'), (2, 'c'), (3, 'd'), (4, 'g')]);
+  assert_eq!(LAST.0[..LAST.1], [(1, 'b'), (2, 'c'), (3, 'f'), (4, 'g')]);
+  const EMPTY: ([u8; 0], usize) = const_dedup_by_key_keep_last([], const |x: &u8| *x);
+  assert_eq!(EMPTY.1, 0);
+
+  let mut v = gen_array(RAND_CNT);
+  v.sort_by_key(|x| x % 100);
+  let arr: [u32; RAND_CNT] = v.clone().try_into().unwrap();
+  let (first, len) = const_dedup_by_key_keep_first(arr, |x: &u32| x % 100);
+  let mut expected = v.clone();
+  expected.dedup_by_key(|x| *x % 100);
+  assert_eq!(first[..len], expected);
+  let (last, len) = const_dedup_by_key_keep_last(arr, |x: &u32| x % 100);
+  let expected: Vec<u32> = (0..v.len())
+    .filter(|&i| i + 1 == v.len() || v[i] % 100 != v[i + 1] % 100)
+    .map(|i| v[i])
+    .collect();
+  assert_eq!(last[..len], expected);
+}
+
+#[test]
+fn const_core_sorted_insert_into_prefix() {
+  const SMALLEST: ([u8; 4], [usize; 3]) = {
+    let mut buf = [0; 4];
+    let mut indices = [0; 3];
+    let mut len = 0;
+    let values = [5, 2, 8, 2, 9, 1];
+    let mut i = 0;
+    while i < values.len() {
+      let index = if len < buf.len() {
+        len += 1;
+        const_sorted_insert_into_prefix(&mut buf, len - 1, values[i], PartialOrd::lt)
+      } else if values[i] < buf[len - 1] {
+        // Replace the largest of the 4 smallest elements.
+        const_sorted_insert_into_prefix(&mut buf, len - 1, values[i], PartialOrd::lt)
+      } else {
+        usize::MAX
+      };
+      if i >= 3 {
+        indices[i - 3] = index;
+      }
+      i += 1;
+    }
+    (buf, indices)
+  };
+  assert_eq!(SMALLEST, ([1, 2, 2, 5], [1, usize::MAX, 0]));
+
+  let mut probes = [StabilityProbe { value: 0, index: 0 }; 100];
+  let values = testgen::random::<100>(3, 10);
+  let mut i = 0;
+  while i < values.len() {
+    const_sorted_insert_into_prefix(
+      &mut probes,
+      i,
+      StabilityProbe {
+        value: values[i],
+        index: i,
+      },
+      PartialOrd::lt,
+    );
+    i += 1;
+  }
+  assert!(StabilityProbe::is_stably_sorted(&probes));
+
+  let v = gen_array(RAND_CNT / 10);
+  let mut buf = vec![0; v.len()];
+  for (len, &x) in v.iter().enumerate() {
+    let index = const_sorted_insert_into_prefix(&mut buf, len, x, PartialOrd::lt);
+    assert_eq!(buf[index], x);
+  }
+  let mut expected = v;
+  expected.sort_unstable();
+  assert_eq!(buf, expected);
+}
+
+#[test]
+#[should_panic(expected = "sorted prefix fills the whole buffer")]
+fn const_core_sorted_insert_into_full_prefix() {
+  const_sorted_insert_into_prefix(&mut [1, 2], 2, 3, PartialOrd::lt);
+}
+
+/// Generates a test sorting, selecting and searching `$t` in a const item and at runtime.
+///
+/// `INPUT` holds eight hand-picked values including the extremes of `$t`, `$map` converts the
+/// random `u32`s of `gen_array` to `$t` for the runtime check.
+macro_rules! const_element_type_tests {
+  ($($name:ident: $t:ty = [$($x:expr),* $(,)?], $map:expr;)*) => {$(
+    #[test]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap, clippy::cast_lossless)]
+    fn $name() {
+      const INPUT: [$t; 8] = [$($x),*];
+      const RESULT: ([$t; 8], bool, $t, usize, bool) = {
+        let mut sorted = INPUT;
+        sorted.const_sort_unstable();
+        let mut selected = INPUT;
+        let median = *selected.const_select_nth_unstable(4).1;
+        (
+          sorted,
+          sorted.const_is_sorted(),
+          median,
+          sorted.const_insertion_index(&INPUT[3]),
+          sorted.const_contains(&INPUT[5]),
+        )
+      };
+      let mut expected = INPUT;
+      expected.sort();
+      assert_eq!(RESULT.0, expected);
+      assert!(RESULT.1);
+      assert_eq!(RESULT.2, expected[4]);
+      assert_eq!(RESULT.3, expected.partition_point(|x| *x < INPUT[3]));
+      assert!(RESULT.4);
+
+      let map: fn(u32) -> $t = $map;
+      let mut v: Vec<$t> = gen_array(RAND_CNT).into_iter().map(map).collect();
+      cross_check(&INPUT);
+      cross_check(&v);
+      let mut expected = v.clone();
+      expected.sort();
+      v.const_sort_unstable();
+      assert_eq!(v, expected);
+    }
+  )*};
+}
+
+const_element_type_tests! {
+  element_type_u8: u8 = [7, u8::MAX, 0, 7, 200, 1, 128, 3], |x| x as u8;
+  element_type_u16: u16 = [7, u16::MAX, 0, 7, 40_000, 1, 256, 3], |x| x as u16;
+  element_type_u32: u32 = [7, u32::MAX, 0, 7, 1 << 31, 1, 65_536, 3], |x| x;
+  element_type_u64: u64 = [7, u64::MAX, 0, 7, 1 << 63, 1, 1 << 32, 3], |x| x as u64 * 0x9E37;
+  element_type_u128: u128 = [7, u128::MAX, 0, 7, 1 << 127, 1, 1 << 64, 3],
+    |x| ((x as u128) << 70) | x as u128;
+  element_type_usize: usize = [7, usize::MAX, 0, 7, usize::MAX / 2, 1, 1 << 16, 3], |x| x as usize;
+  element_type_i8: i8 = [-7, i8::MAX, 0, -7, i8::MIN, 1, -1, 3], |x| x as i8;
+  element_type_i16: i16 = [-7, i16::MAX, 0, -7, i16::MIN, 1, -1, 3], |x| x as i16;
+  element_type_i32: i32 = [-7, i32::MAX, 0, -7, i32::MIN, 1, -1, 3], |x| x as i32;
+  element_type_i64: i64 = [-7, i64::MAX, 0, -7, i64::MIN, 1, -1, 3], |x| (x as i32 as i64) << 20;
+  element_type_i128: i128 = [-7, i128::MAX, 0, -7, i128::MIN, 1, -1, 3],
+    |x| (x as i32 as i128) << 90;
+  element_type_isize: isize = [-7, isize::MAX, 0, -7, isize::MIN, 1, -1, 3], |x| x as i32 as isize;
+  element_type_char: char = ['z', char::MAX, '\0', 'z', '\u{e9}', 'A', '\u{1f980}', 'a'],
+    |x| char::from_u32(x % 0x11_0000).unwrap_or(char::REPLACEMENT_CHARACTER);
+  element_type_bool: bool = [true, false, true, true, false, true, false, true], |x| x % 2 == 0;
+}
+
+/// Generates a test sorting, selecting and searching `$t` at runtime.
+///
+/// Slices and arrays have no const `Ord` implementation on the supported nightly, so elements
+/// like `&str` and `[u8; K]` can only be sorted by these functions outside of const items.
+/// [`const_sort_byte_arrays`] covers byte arrays in const items.
+macro_rules! runtime_element_type_tests {
+  ($($name:ident: $t:ty = [$($x:expr),* $(,)?], $map:expr;)*) => {$(
+    #[test]
+    fn $name() {
+      let input: [$t; 8] = [$($x),*];
+      let mut expected = input;
+      expected.sort();
+      let mut sorted = input;
+      sorted.const_sort_unstable();
+      assert_eq!(sorted, expected);
+      assert!(sorted.const_is_sorted());
+      let mut selected = input;
+      assert_eq!(*selected.const_select_nth_unstable(4).1, expected[4]);
+      assert_eq!(
+        sorted.const_insertion_index(&input[3]),
+        expected.partition_point(|x| *x < input[3])
+      );
+      assert!(sorted.const_contains(&input[5]));
+
+      let strings: Vec<String> = gen_array(RAND_CNT).iter().map(u32::to_string).collect();
+      let map: fn(&String) -> $t = $map;
+      let mut v: Vec<$t> = strings.iter().map(map).collect();
+      cross_check(&input);
+      cross_check(&v);
+      let mut expected = v.clone();
+      expected.sort();
+      v.const_sort_unstable();
+      assert_eq!(v, expected);
+    }
+  )*};
+}
+
+runtime_element_type_tests! {
+  element_type_str: &str = ["b", "", "ab", "b", "\u{1f980}", "a", "ba", "B"], |s| s.as_str();
+  element_type_byte_array: [u8; 4] =
+    [
+      [1, 2, 3, 4],
+      [0; 4],
+      [255; 4],
+      [1, 2, 3, 4],
+      [1, 2, 4, 0],
+      [0, 0, 0, 1],
+      [9, 0, 0, 0],
+      [1, 2, 3, 3],
+    ],
+    |s| s.as_bytes()[..4].try_into().unwrap_or([0; 4]);
+}
+
+#[test]
+fn distance_cmp_by_distance_to() {
+  const SORTED: ([i8; 7], [u8; 4], u64) = {
+    let mut signed = [i8::MIN, -3, 4, 0, 2, i8::MAX, -2];
+    signed.const_sort_unstable_by(cmp_by_distance_to(0));
+    let mut unsigned = [0, 255, 10, 12];
+    unsigned.const_sort_unstable_by(cmp_by_distance_to(11));
+    let closest = match [3u64, 17, 8, 12].const_min_by_key(const |x: &u64| x.abs_diff(10)) {
+      Some(x) => *x,
+      None => 0,
+    };
+    (signed, unsigned, closest)
+  };
+  assert_eq!(SORTED.0, [0, -2, 2, -3, 4, i8::MAX, i8::MIN]);
+  assert_eq!(SORTED.1, [10, 12, 0, 255]);
+  assert_eq!(SORTED.2, 8);
+
+  let cmp = cmp_by_distance_to(i128::MIN);
+  assert_eq!(cmp(&i128::MAX, &0), core::cmp::Ordering::Greater);
+  assert_eq!(cmp(&7, &7), core::cmp::Ordering::Equal);
+  assert_eq!(
+    const_check_total_order(&[-4, 4, 0, 8, -8, 1], cmp_by_distance_to(0)),
+    None
+  );
+
+  let mut v = gen_array(RAND_CNT);
+  let target = u32::MAX / 3;
+  v.const_sort_unstable_by(cmp_by_distance_to(target));
+  assert!(v
+    .windows(2)
+    .all(|w| (w[0].abs_diff(target), w[0]) <= (w[1].abs_diff(target), w[1])));
+}
+
+#[test]
+fn api_v1_reexports() {
+  use crate::api::v1;
+
+  const SORTED: ([u16; 4], bool) = {
+    let mut v = [4, 1, 3, 2];
+    v1::ConstSliceSortExt::const_sort_unstable(&mut v[..]);
+    (v, v1::self_check())
+  };
+  assert_eq!(SORTED, ([1, 2, 3, 4], true));
+  assert_eq!(v1::key_transform::i8_to_key(-128), 0);
+  let mut v = gen_array(RAND_CNT);
+  v1::const_sort::const_heapsort(&mut v, PartialOrd::lt);
+  assert!(v1::ConstSliceSortExt::const_is_sorted(&v[..]));
+}
+
+#[test]
+fn const_index_map() {
+  const CODE_POINTS: ConstIndexMap<char, 5> =
+    ConstIndexMap::new(['$', '\u{a3}', '\u{a5}', '\u{20ac}', '\u{20bf}']);
+  const LOOKUPS: [Option<usize>; 4] = [
+    CODE_POINTS.dense_index(&'$'),
+    CODE_POINTS.dense_index(&'\u{20ac}'),
+    CODE_POINTS.dense_index(&'\u{20bf}'),
+    CODE_POINTS.dense_index(&'a'),
+  ];
+  assert_eq!(LOOKUPS, [Some(0), Some(3), Some(4), None]);
+  assert_eq!(CODE_POINTS.key(3), Some(&'\u{20ac}'));
+  assert_eq!(CODE_POINTS.key(5), None);
+  assert_eq!(CODE_POINTS.len(), 5);
+  const EMPTY: ConstIndexMap<u8, 0> = ConstIndexMap::new([]);
+  assert!(EMPTY.is_empty());
+  assert_eq!(EMPTY.dense_index(&0), None);
+
+  let mut keys = gen_array(RAND_CNT);
+  keys.sort_unstable();
+  keys.dedup();
+  keys.truncate(1000);
+  let keys: [u32; 1000] = keys.try_into().unwrap();
+  let map = ConstIndexMap::new(keys);
+  for (i, key) in keys.iter().enumerate() {
+    assert_eq!(map.dense_index(key), Some(i));
+    let absent = key.wrapping_add(1);
+    assert_eq!(map.dense_index(&absent), keys.binary_search(&absent).ok());
+  }
+}
+
+#[test]
+#[should_panic(expected = "index map keys are not sorted and unique")]
+fn const_index_map_duplicate_keys() {
+  let _ = ConstIndexMap::new(black_box([1, 2, 2, 3]));
+}
+
+#[test]
+fn const_sorted_from_sorted() {
+  const ARR: [u16; 5] = [1, 2, 2, 40, 500];
+  const CHECKED: ConstSorted<u16, 5> = ConstSorted::from_sorted_checked(ARR);
+  const UNCHECKED: ConstSorted<u16, 5> = {
+    // SAFETY: `ARR` is sorted.
+    unsafe { ConstSorted::from_sorted_unchecked(ARR) }
+  };
+  assert_eq!(CHECKED, ConstSorted::new([500, 2, 40, 1, 2]));
+  assert_eq!(CHECKED, UNCHECKED);
+  assert_eq!(CHECKED.median(), Some(&2));
+
+  let mut v = gen_array(RAND_CNT);
+  v.sort_unstable();
+  let arr: [u32; RAND_CNT] = v.try_into().unwrap();
+  assert_eq!(ConstSorted::from_sorted_checked(arr).into_inner(), arr);
+}
+
+#[test]
+#[should_panic(expected = "array is not sorted")]
+fn const_sorted_from_unsorted() {
+  let _ = ConstSorted::from_sorted_checked(black_box([1, 3, 2]));
+}
+
+#[test]
+fn const_array_duplicate_key_message() {
+  extern crate std;
+  for index in [0, 7, 1207, usize::MAX] {
+    let payload = std::panic::catch_unwind(|| panic_duplicate_key(black_box(index))).unwrap_err();
+    assert_eq!(
+      payload.downcast_ref::<String>().unwrap(),
+      &alloc::format!("duplicate key at index {index}")
+    );
+  }
+}
+
+#[test]
+fn const_array_find_duplicate_key() {
+  // Key functions take their element by reference.
+  #[allow(clippy::trivially_copy_pass_by_ref)]
+  const fn id(entry: &(u8, char)) -> u8 {
+    entry.0
+  }
+  const DUPLICATES: [Option<(usize, usize)>; 4] = [
+    const_find_duplicate_key(&[(1, 'a'), (2, 'b'), (3, 'c')], id),
+    const_find_duplicate_key(
+      &[(5, 'a'), (1, 'b'), (3, 'c'), (1, 'd'), (5, 'e'), (3, 'f')],
+      id,
+    ),
+    const_find_duplicate_key(&[(4, 'a'), (4, 'b'), (4, 'c')], id),
+    const_find_duplicate_key(&[], id),
+  ];
+  assert_eq!(DUPLICATES, [None, Some((1, 3)), Some((0, 1)), None]);
+
+  let mut v = gen_array(RAND_CNT);
+  v.sort_unstable();
+  v.dedup();
+  v.truncate(1000);
+  let mut arr: [u32; 1000] = v.try_into().unwrap();
+  arr.reverse();
+  assert_eq!(const_find_duplicate_key(&arr, |x| *x), None);
+  arr[700] = arr[300];
+  arr[900] = arr[100];
+  assert_eq!(const_find_duplicate_key(&arr, |x| *x), Some((300, 700)));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn alloc_sorted_vecs() {
+  use crate::SliceSortVecExt;
+
+  const fn by_len(a: &&str, b: &&str) -> core::cmp::Ordering {
+    if a.len() < b.len() {
+      core::cmp::Ordering::Less
+    } else if a.len() > b.len() {
+      core::cmp::Ordering::Greater
+    } else {
+      core::cmp::Ordering::Equal
+    }
+  }
+  // The comparator sorts in a const item and at runtime.
+  const BY_LEN: [&str; 4] = {
+    let mut words = ["ccc", "a", "bb", "dd"];
+    words.const_sort_unstable_by(by_len);
+    words
+  };
+  let words = ["ccc", "a", "bb", "dd"];
+  assert_eq!(words.to_sorted_vec_by(by_len)[..2], BY_LEN[..2]);
+  assert_eq!(words.argsort_vec_by(by_len), [1, 2, 3, 0]);
+  assert_eq!(words.sorted_dedup_vec_by(by_len).len(), 3);
+  assert_eq!(words.sorted_dedup_vec(), ["a", "bb", "ccc", "dd"]);
+
+  let v = gen_array(RAND_CNT);
+  let mut expected = v.clone();
+  expected.sort_unstable();
+  assert_eq!(v.to_sorted_vec(), expected);
+  let argsorted: Vec<u32> = v.argsort_vec().iter().map(|&i| v[i]).collect();
+  assert_eq!(argsorted, expected);
+  expected.dedup();
+  assert_eq!(v.sorted_dedup_vec(), expected);
+}
+
+#[test]
+fn const_sort_unstable_by_key_counted() {
+  // Key functions take their element by reference.
+  #[allow(clippy::trivially_copy_pass_by_ref)]
+  const fn abs(x: &i32) -> i32 {
+    x.abs()
+  }
+  const COUNTED: ([i32; 5], usize, usize) = {
+    let mut v = [-5, 4, 1, -3, 2];
+    let calls = v.const_sort_unstable_by_key_counted(abs);
+    let mut sorted = [1, 2, 3];
+    (v, calls, sorted.const_sort_unstable_by_key_counted(abs))
+  };
+  assert_eq!(COUNTED.0, [1, 2, -3, 4, -5]);
+  assert!(COUNTED.1 >= 2 * 4 && COUNTED.1 % 2 == 0);
+  // The number of comparisons depends on the algorithm selected by the features.
+  assert!(COUNTED.2 >= 2 * 2 && COUNTED.2 % 2 == 0);
+
+  let mut v = gen_array(RAND_CNT);
+  cross_check(&v);
+  let mut calls = 0;
+  let mut expected = v.clone();
+  expected.const_sort_unstable_by_key(|x| {
+    calls += 1;
+    *x
+  });
+  assert_eq!(v.const_sort_unstable_by_key_counted(|x| *x), calls);
+  assert_eq!(v, expected);
+}
+
+#[test]
+fn const_core_sort_each() {
+  const RECORDS: [[u8; 4]; 3] = {
+    let mut records = [[4, 3, 2, 1], [0, 9, 0, 9], [7, 7, 7, 7]];
+    const_sort_each(&mut records, PartialOrd::lt);
+    records
+  };
+  assert_eq!(RECORDS, [[1, 2, 3, 4], [0, 0, 9, 9], [7; 4]]);
+  const EMPTY: [[u8; 0]; 2] = {
+    let mut records = [[], []];
+    const_sort_each(&mut records, PartialOrd::lt);
+    records
+  };
+  assert_eq!(EMPTY, [[], []]);
+
+  let v = gen_array(RAND_CNT);
+  cross_check(&v);
+  let mut small: [[u32; 5]; RAND_CNT / 5] = [[0; 5]; RAND_CNT / 5];
+  let mut large: [[u32; 100]; RAND_CNT / 100] = [[0; 100]; RAND_CNT / 100];
+  for (i, x) in v.iter().enumerate() {
+    small[i / 5][i % 5] = *x;
+    large[i / 100][i % 100] = *x;
+  }
+  const_sort_each(&mut small, |a, b| a > b);
+  const_sort_each(&mut large, PartialOrd::lt);
+  assert!(small.iter().all(|a| a.windows(2).all(|w| w[0] >= w[1])));
+  assert!(large.iter().all(|a| a.is_sorted()));
+}
+
+#[test]
+fn const_sort_segments() {
+  const SEGMENTED: [i32; 9] = {
+    let mut v = [9, 8, 7, 3, 1, 2, -1, 6, 5];
+    v.const_sort_segments(&[1, 3, 6, 6, 8]);
+    v
+  };
+  assert_eq!(SEGMENTED, [9, 7, 8, 1, 2, 3, -1, 6, 5]);
+
+  let mut v = gen_array(RAND_CNT);
+  let boundaries = [0, 10, 500, 501, 4000, RAND_CNT];
+  let mut expected = v.clone();
+  for w in boundaries.windows(2) {
+    expected[w[0]..w[1]].sort_unstable();
+  }
+  v.const_sort_segments(&boundaries);
+  assert_eq!(v, expected);
+}
+
+#[test]
+#[should_panic(expected = "segment boundaries are out of order or out of bounds")]
+fn const_sort_segments_out_of_order() {
+  [1, 2, 3, 4].const_sort_segments(&[0, 3, 2, 4]);
+}
+
+#[test]
+fn const_retain() {
+  const RETAINED: ([char; 7], usize, usize) = {
+    let mut v = ['a', 'B', 'c', 'D', 'E', 'f', 'g'];
+    let len = v.const_retain(char::is_ascii_lowercase);
+    let mut none = ['A', 'B'];
+    (v, len, none.const_retain(char::is_ascii_lowercase))
+  };
+  assert_eq!(RETAINED.0[..RETAINED.1], ['a', 'c', 'f', 'g']);
+  let mut removed = RETAINED.0[RETAINED.1..].to_vec();
+  removed.sort_unstable();
+  assert_eq!(removed, ['B', 'D', 'E']);
+  assert_eq!(RETAINED.2, 0);
+
+  let mut v = gen_array(RAND_CNT);
+  let mut expected = v.clone();
+  expected.retain(|x| x % 3 == 0);
+  let len = v.const_retain(|x| x % 3 == 0);
+  assert_eq!(v[..len], expected);
+  assert!(v[len..].iter().all(|x| x % 3 != 0));
+}
+
+#[test]
+#[cfg(feature = "cross-check")]
+fn cross_check_patterns() {
+  macro_rules! cross_check_lengths {
+    ($($n:literal)*) => {$(
+      cross_check(&testgen::ascending::<$n>());
+      cross_check(&testgen::descending::<$n>());
+      cross_check(&testgen::all_equal::<$n>(1));
+      cross_check(&testgen::sawtooth::<$n>(3));
+      cross_check(&testgen::organ_pipe::<$n>());
+      cross_check(&testgen::push_front::<$n>());
+      cross_check(&testgen::random::<$n>(7, 5));
+      cross_check(&testgen::median_of_3_killer::<$n>());
+    )*};
+  }
+  cross_check_lengths!(0 1 2 3 4 5 6 7 8 9 10 11 12 13 16 20 21 24 31 32 33 64 100 257 1000);
+}
+
+#[test]
+#[cfg(feature = "const-tests")]
+// The whole test runs while it is compiled.
+#[allow(clippy::missing_const_for_fn)]
+fn const_fixture_sorts() {
+  /// Fixtures longer than this are not sorted with the *O*(*n*^2) algorithms, which would exceed
+  /// the step limit of const evaluation.
+  const MAX_QUADRATIC_LEN: usize = 100;
+  const fn is_quadratic(algorithm: Algorithm) -> bool {
+    matches!(
+      algorithm,
+      Algorithm::Insertion | Algorithm::Selection | Algorithm::Cocktail
+    )
+  }
+  const ALGORITHMS: [Algorithm; 7] = [
+    Algorithm::Pdq,
+    Algorithm::Heap,
+    Algorithm::Insertion,
+    Algorithm::Selection,
+    Algorithm::Cocktail,
+    Algorithm::Merge,
+    Algorithm::Adaptive,
+  ];
+  // Every `const` block is evaluated when the test is compiled, so a sort that does not
+  // terminate or panics during const evaluation fails the build.
+  macro_rules! assert_sorts_in_const {
+    ($($n:literal)*) => {$(
+      const {
+        let fixtures: [[usize; $n]; 8] = [
+          testgen::ascending(),
+          testgen::descending(),
+          testgen::all_equal(1),
+          testgen::sawtooth(3),
+          testgen::organ_pipe(),
+          testgen::push_front(),
+          testgen::random(7, 5),
+          testgen::median_of_3_killer(),
+        ];
+        let mut f = 0;
+        while f < fixtures.len() {
+          let short = fixtures[f].len() <= MAX_QUADRATIC_LEN;
+          let mut a = 0;
+          while a < ALGORITHMS.len() {
+            if short || !is_quadratic(ALGORITHMS[a]) {
+              let mut v = fixtures[f];
+              const_sort_with(ALGORITHMS[a], &mut v, PartialOrd::lt);
+              assert!(v.const_is_sorted(), "fixture is not sorted");
+            }
+            a += 1;
+          }
+          if short || !is_quadratic(Algorithm::current()) {
+            let mut v = fixtures[f];
+            v.const_sort_unstable();
+            assert!(v.const_is_sorted(), "fixture is not sorted");
+          }
+          let mut v = fixtures[f];
+          v.const_sort();
+          assert!(v.const_is_sorted(), "fixture is not sorted");
+          f += 1;
+        }
+      };
+    )*};
+  }
+  assert_sorts_in_const!(0 1 2 3 4 13 20 21 33 64 100 257);
+}
+
+/// Runtime panics of the comparator must leave every element in the slice exactly once.
+#[test]
+fn panicking_comparator_keeps_elements() {
+  extern crate std;
+  use std::panic::{catch_unwind, AssertUnwindSafe};
+
+  let v: Vec<String> = gen_array(300)
+    .iter()
+    .map(|x| (x % 50).to_string())
+    .collect();
+  let mut expected = v.clone();
+  expected.sort();
+  for algorithm in [
+    Algorithm::Pdq,
+    Algorithm::Heap,
+    Algorithm::Insertion,
+    Algorithm::Selection,
+    Algorithm::Cocktail,
+    Algorithm::Merge,
+    Algorithm::Adaptive,
+  ] {
+    for panic_after in [0, 1, 10, 100, 1000] {
+      let mut sorted = v.clone();
+      let mut comparisons = 0;
+      let result = catch_unwind(AssertUnwindSafe(|| {
+        const_sort_with(algorithm, &mut sorted, |a: &String, b: &String| {
+          assert!(comparisons != panic_after, "comparator panicked");
+          comparisons += 1;
+          a < b
+        });
+      }));
+      assert!(result.is_err(), "{algorithm:?} {panic_after}");
+      sorted.sort();
+      assert_eq!(sorted, expected, "{algorithm:?} {panic_after}");
+    }
+  }
+}
+
+/// A zero-sized type that is less than every value, so searches always move to the right.
+#[derive(PartialEq, Eq)]
+struct AlwaysLess;
+
+impl PartialOrd for AlwaysLess {
+  fn partial_cmp(&self, _: &Self) -> Option<core::cmp::Ordering> {
+    Some(core::cmp::Ordering::Less)
+  }
+}
+
+impl Ord for AlwaysLess {
+  fn cmp(&self, _: &Self) -> core::cmp::Ordering {
+    core::cmp::Ordering::Less
+  }
+}
+
+/// Slices of zero-sized types can be `usize::MAX` long, which is the length every 16 bit target
+/// has to handle as well.
+#[test]
+fn max_length_zst_slices() {
+  let ptr = core::ptr::NonNull::<AlwaysLess>::dangling().as_ptr();
+  // SAFETY: `AlwaysLess` is zero-sized, so a dangling pointer is valid for any length.
+  let v = unsafe { core::slice::from_raw_parts_mut(ptr, usize::MAX) };
+
+  v.const_sort_unstable();
+  const_heapsort(v, PartialOrd::lt);
+  const_binary_insertion_sort(v, PartialOrd::lt);
+  const_selection_sort(v, PartialOrd::lt);
+  const_cocktail_sort(v, PartialOrd::lt);
+
+  assert_eq!(v.const_insertion_index(&AlwaysLess), usize::MAX);
+  assert_eq!(v.const_insertion_index_last(&AlwaysLess), 0);
+  assert_eq!(v.const_last_index_where(|_| true), Some(usize::MAX - 1));
+  assert_eq!(v.const_last_index_where(|_| false), None);
+  assert_eq!(
+    v.const_binary_search_with_hint(&AlwaysLess, 0),
+    Err(usize::MAX)
+  );
+  assert_eq!(
+    v.const_binary_search_with_hint(&AlwaysLess, usize::MAX / 2 + 1),
+    Err(usize::MAX)
+  );
+}
+
+/// Zero-sized types return before the heap is built, so the check of `sift_down` that stops at
+/// the last parent node is covered with real elements: every length up to 64 and one slice with
+/// more elements than `u16::MAX`.
+#[test]
+fn const_heapsort_sift_down_bounds() {
+  let random = gen_array(1 << 16 | 1);
+  for len in (0..=64).chain([random.len()]) {
+    let n = u32::try_from(len).unwrap();
+    let mut descending: Vec<u32> = (0..n).rev().collect();
+    const_heapsort(&mut descending, PartialOrd::lt);
+    assert!(descending.iter().copied().eq(0..n));
+
+    let mut v = random[..len].to_vec();
+    const_heapsort(&mut v, PartialOrd::lt);
+    let mut expected = random[..len].to_vec();
+    expected.sort_unstable();
+    assert_eq!(v, expected);
+  }
+}
+
+#[test]
+fn const_chunked_map() {
+  type Builder = ConstChunkedMapBuilder<u32, char, 4, 3>;
+  const FIRST: Builder = Builder::new().push_chunk([70, 3, 41, 12], ['h', 'a', 'f', 'c']);
+  const SECOND: Builder = FIRST.push_chunk([99, 8, 25, 55], ['l', 'b', 'e', 'g']);
+  const MAP: ConstChunkedMap<u32, char, 4, 3> = SECOND
+    .push_chunk([15, 80, 90, 71], ['d', 'j', 'k', 'i'])
+    .build();
+  const LOOKUPS: [Option<&char>; 5] = [
+    MAP.get(&3),
+    MAP.get(&25),
+    MAP.get(&99),
+    MAP.get(&2),
+    MAP.get(&56),
+  ];
+  assert_eq!(LOOKUPS, [Some(&'a'), Some(&'e'), Some(&'l'), None, None]);
+  assert_eq!(MAP.chunk_index(), &[3, 25, 71]);
+  assert_eq!(
+    MAP.chunk(1),
+    Some((&[25, 41, 55, 70], &['e', 'f', 'g', 'h']))
+  );
+  assert_eq!(MAP.chunk(3), None);
+  assert_eq!(MAP.len(), 12);
+  assert!(MAP.contains_key(&90));
+  assert!(!MAP.contains_key(&100));
+
+  let mut keys = gen_array(RAND_CNT);
+  keys.sort_unstable();
+  keys.dedup();
+  keys.truncate(1000);
+  let mut rng = StdRng::seed_from_u64(0);
+  for i in (1..keys.len()).rev() {
+    keys.swap(i, rng.gen_range(0..=i));
+  }
+  let mut builder = ConstChunkedMapBuilder::<u32, u64, 100, 10>::new();
+  for chunk in keys.chunks(100) {
+    let chunk: [u32; 100] = chunk.try_into().unwrap();
+    builder = builder.push_chunk(chunk, chunk.map(|key| u64::from(key) * 2));
+  }
+  let map = builder.build();
+  for key in &keys {
+    assert_eq!(map.get(key), Some(&(u64::from(*key) * 2)));
+    let absent = key.wrapping_add(1);
+    assert_eq!(map.contains_key(&absent), keys.contains(&absent));
+  }
+}
+
 #[test]
-fn const_core_slice_heapsort() {
-  const ARR: [u8; 4] = {
-    let mut v = [2, 3, 5, 4];
-    const_heapsort(&mut v, PartialOrd::lt);
-    v
+#[should_panic(expected = "chunked map keys are not sorted and unique")]
+fn const_chunked_map_duplicate_keys() {
+  let _ = ConstChunkedMapBuilder::<u8, (), 2, 2>::new()
+    .push_chunk([1, 4], [(); 2])
+    .push_chunk(black_box([4, 2]), [(); 2])
+    .build();
+}
+
+#[test]
+#[should_panic(expected = "not all chunks were pushed")]
+fn const_chunked_map_missing_chunk() {
+  let _ = ConstChunkedMapBuilder::<u8, (), 2, 2>::new()
+    .push_chunk(black_box([1, 4]), [(); 2])
+    .build();
+}
+
+#[test]
+fn const_sorted_desc() {
+  const PRIORITIES: ConstSortedDesc<u8, 6> = ConstSortedDesc::new([30, 200, 5, 120, 120, 60]);
+  const SEARCHES: [Result<usize, usize>; 4] = [
+    PRIORITIES.binary_search(&200),
+    PRIORITIES.binary_search(&120),
+    PRIORITIES.binary_search(&100),
+    PRIORITIES.binary_search(&0),
+  ];
+  assert_eq!(SEARCHES, [Ok(0), Ok(1), Err(3), Err(6)]);
+  assert_eq!(PRIORITIES.insertion_index(&255), 0);
+  assert_eq!(PRIORITIES.max(), Some(&200));
+  assert_eq!(PRIORITIES.min(), Some(&5));
+  assert_eq!(PRIORITIES.median(), Some(&60));
+  assert_eq!(
+    ConstSorted::new(PRIORITIES.into_inner()).median(),
+    PRIORITIES.median()
+  );
+  const CHECKED: ConstSortedDesc<u8, 6> =
+    ConstSortedDesc::from_sorted_checked([200, 120, 120, 60, 30, 5]);
+  assert_eq!(CHECKED, PRIORITIES);
+  const INNER: [u8; 6] = PRIORITIES.into_inner();
+  assert_eq!(INNER, [200, 120, 120, 60, 30, 5]);
+  const EMPTY: ConstSortedDesc<u8, 0> = ConstSortedDesc::new([]);
+  assert_eq!(EMPTY.median(), None);
+  assert_eq!(EMPTY.binary_search(&1), Err(0));
+
+  let mut v = gen_array(RAND_CNT);
+  v.sort_unstable_by(|a, b| b.cmp(a));
+  assert!(v.const_is_sorted_desc());
+  for x in v.iter().step_by(7) {
+    let index = v.const_binary_search_desc(x).unwrap();
+    assert_eq!(v[index], *x);
+    assert!(index == 0 || v[index - 1] > *x);
+    let absent = x.wrapping_add(1);
+    assert_eq!(
+      v.const_insertion_index_desc(&absent),
+      v.partition_point(|e| *e > absent)
+    );
+  }
+  let arr: [u32; RAND_CNT] = v.try_into().unwrap();
+  let sorted = ConstSortedDesc::new(gen_array(RAND_CNT).try_into().unwrap());
+  assert_eq!(sorted.into_inner(), arr);
+}
+
+#[test]
+#[should_panic(expected = "array is not sorted in descending order")]
+fn const_sorted_desc_from_unsorted() {
+  let _ = ConstSortedDesc::from_sorted_checked(black_box([3, 1, 2]));
+}
+
+#[cfg(feature = "trace")]
+#[test]
+fn const_core_slice_quicksort_traced() {
+  use crate::const_sort::{const_quicksort_traced, TraceEvent};
+
+  const TRACE: ([Option<TraceEvent>; 4], usize) = {
+    let mut v = testgen::median_of_3_killer::<256>();
+    let mut trace = [None; 4];
+    let events = const_quicksort_traced(&mut v, PartialOrd::lt, &mut trace);
+    assert!(v.const_is_sorted());
+    (trace, events)
   };
-  assert_eq!(&ARR, &[2, 3, 4, 5]);
+  assert!(TRACE.1 > 4);
+  assert!(TRACE.0.iter().all(Option::is_some));
+
+  let mut v = gen_array(RAND_CNT);
+  let mut sorted = v.clone();
+  sorted.sort_unstable();
+  let mut trace = vec![None; 1000];
+  let events = const_quicksort_traced(&mut v, PartialOrd::lt, &mut trace);
+  assert_eq!(v, sorted);
+  assert!(events > 0);
+  for event in trace.iter().take(events).flatten() {
+    match *event {
+      TraceEvent::Partition { pivot, left, right } => {
+        assert!(left <= pivot && pivot + right < RAND_CNT);
+      },
+      TraceEvent::PartitionEqual { start, equal } => assert!(start + equal <= RAND_CNT),
+      TraceEvent::Presorted { start, len }
+      | TraceEvent::BreakPatterns { start, len }
+      | TraceEvent::HeapsortFallback { start, len } => assert!(start + len <= RAND_CNT),
+    }
+  }
+
+  let mut sorted = sorted;
+  let mut trace = [None; 1];
+  let events = const_quicksort_traced(&mut sorted, PartialOrd::lt, &mut trace);
+  assert_eq!(events, 1);
+  assert_eq!(
+    trace[0],
+    Some(TraceEvent::Presorted {
+      start: 0,
+      len: RAND_CNT
+    })
+  );
 }
+
 #[test]
-fn const_core_slice_heapsort_rng() {
+fn const_table_parallel_key() {
+  const KEYS: [u32; 6] = [3, 8, 8, 8, 20, 41];
+  const VALUES: [char; 6] = ['a', 'b', 'c', 'd', 'e', 'f'];
+  const SEARCHES: [Result<usize, usize>; 4] = [
+    const_binary_search_parallel_key(&KEYS, &3),
+    const_binary_search_parallel_key(&KEYS, &8),
+    const_binary_search_parallel_key(&KEYS, &9),
+    const_binary_search_parallel_key(&KEYS, &42),
+  ];
+  assert_eq!(SEARCHES, [Ok(0), Ok(1), Err(4), Err(6)]);
+  const LOOKUPS: [Option<&char>; 3] = [
+    const_lookup_by_parallel_key(&KEYS, &VALUES, &8),
+    const_lookup_by_parallel_key(&KEYS, &VALUES, &41),
+    const_lookup_by_parallel_key(&KEYS, &VALUES, &0),
+  ];
+  assert_eq!(LOOKUPS, [Some(&'b'), Some(&'f'), None]);
+
+  let mut keys = gen_array(RAND_CNT);
+  keys.sort_unstable();
+  let values: Vec<u64> = keys.iter().map(|key| u64::from(*key) * 3).collect();
+  for key in keys.iter().step_by(7) {
+    let probe = key.wrapping_add(1);
+    let index = keys.partition_point(|k| *k < probe);
+    let expected = if keys.get(index) == Some(&probe) {
+      Ok(index)
+    } else {
+      Err(index)
+    };
+    assert_eq!(const_binary_search_parallel_key(&keys, &probe), expected);
+    assert_eq!(
+      const_lookup_by_parallel_key(&keys, &values, key),
+      Some(&(u64::from(*key) * 3))
+    );
+  }
+}
+
+#[test]
+#[should_panic(expected = "keys and values must have the same length")]
+fn const_table_parallel_key_length_mismatch() {
+  let _ = const_lookup_by_parallel_key(black_box(&[1, 2, 3]), &['a', 'b'], &2);
+}
+
+#[test]
+fn const_array_sorted_pairs() {
+  // Pair callbacks take their elements by reference.
+  #[allow(clippy::trivially_copy_pass_by_ref)]
+  const fn push_gap(mut gaps: ([i32; 4], usize), a: &i32, b: &i32) -> ([i32; 4], usize) {
+    gaps.0[gaps.1] = *b - *a;
+    gaps.1 += 1;
+    gaps
+  }
+  const VALUES: [i32; 5] = [12, -3, 40, 12, 0];
+  const GAPS: ([i32; 4], usize) = const_fold_sorted_pairs(&VALUES, ([0; 4], 0), push_gap);
+  assert_eq!(GAPS, ([3, 12, 0, 28], 4));
+  const EMPTY: [i32; 0] = [];
+  const NONE: ([i32; 4], usize) = const_fold_sorted_pairs(&EMPTY, ([0; 4], 0), push_gap);
+  assert_eq!(NONE.1, 0);
+
+  let v: [u32; RAND_CNT] = gen_array(RAND_CNT).try_into().unwrap();
+  let mut sorted = v;
+  sorted.sort_unstable();
+  let mut pairs = Vec::new();
+  const_for_each_sorted_pair(&v, |a: &u32, b: &u32| pairs.push((*a, *b)));
+  assert_eq!(pairs.len(), RAND_CNT - 1);
+  assert!(pairs
+    .iter()
+    .zip(sorted.windows(2))
+    .all(|(p, w)| *p == (w[0], w[1])));
+  let min_gap = const_fold_sorted_pairs(&v, u32::MAX, |min: u32, a: &u32, b: &u32| min.min(b - a));
+  assert_eq!(
+    min_gap,
+    sorted.windows(2).map(|w| w[1] - w[0]).min().unwrap()
+  );
+}
+
+#[test]
+fn const_slice_min_adjacent_gap() {
+  const BREAKPOINTS: [i32; 6] = [-40, -10, 0, 5, 30, 35];
+  const CLOSEST: Option<(usize, i32)> = BREAKPOINTS.const_min_adjacent_gap();
+  assert_eq!(CLOSEST, Some((2, 5)));
+  const SINGLE: Option<(usize, i32)> = [7].const_min_adjacent_gap();
+  assert_eq!(SINGLE, None);
+  const BY: Option<(usize, u8)> = [(1, 'a'), (9, 'b'), (9, 'c'), (20, 'd')]
+    .const_min_adjacent_gap_by(const |a: &(u8, char), b: &(u8, char)| b.0 - a.0);
+  assert_eq!(BY, Some((1, 0)));
+
   let mut v = gen_array(RAND_CNT);
-  const_heapsort(&mut v, PartialOrd::lt);
-  assert!(v.is_sorted());
+  v.sort_unstable();
+  let (index, gap) = v.const_min_adjacent_gap().unwrap();
+  let expected = v.windows(2).map(|w| w[1] - w[0]).min().unwrap();
+  assert_eq!(gap, expected);
+  assert_eq!(v[index + 1] - v[index], expected);
+  assert!(v[..=index].windows(2).all(|w| w[1] - w[0] > expected));
 }
 
 #[test]
-fn const_core_slice_quicksort() {
-  const ARR: [u8; 4] = {
-    let mut v = [2, 3, 5, 4];
-    const_quicksort(&mut v, PartialOrd::lt);
+fn const_core_sort_with() {
+  const ALGORITHMS: [Algorithm; 7] = [
+    Algorithm::Pdq,
+    Algorithm::Heap,
+    Algorithm::Insertion,
+    Algorithm::Selection,
+    Algorithm::Cocktail,
+    Algorithm::Merge,
+    Algorithm::Adaptive,
+  ];
+  const SORTED: [[u8; 6]; 7] = {
+    let mut sorted = [[4, 1, 5, 9, 2, 6]; 7];
+    let mut i = 0;
+    while i < 7 {
+      const_sort_with(ALGORITHMS[i], &mut sorted[i], PartialOrd::lt);
+      i += 1;
+    }
+    sorted
+  };
+  assert_eq!(SORTED, [[1, 2, 4, 5, 6, 9]; 7]);
+  const STABLE: [bool; 7] = [
+    ALGORITHMS[0].is_stable(),
+    ALGORITHMS[1].is_stable(),
+    ALGORITHMS[2].is_stable(),
+    ALGORITHMS[3].is_stable(),
+    ALGORITHMS[4].is_stable(),
+    ALGORITHMS[5].is_stable(),
+    ALGORITHMS[6].is_stable(),
+  ];
+  assert_eq!(STABLE, [false, false, true, false, true, true, true]);
+  let expected_current = if cfg!(feature = "force-heapsort") {
+    Algorithm::Heap
+  } else if cfg!(feature = "force-insertion") {
+    Algorithm::Insertion
+  } else {
+    Algorithm::Pdq
+  };
+  assert_eq!(Algorithm::current(), expected_current);
+
+  let v = gen_array(1000);
+  cross_check(&v);
+  let mut expected = v.clone();
+  expected.sort_unstable();
+  for algorithm in ALGORITHMS {
+    let mut sorted = v.clone();
+    sorted.const_sort_unstable_with(algorithm);
+    assert_eq!(sorted, expected, "{algorithm:?}");
+    if algorithm.is_stable() {
+      let mut probes: Vec<_> = v
+        .iter()
+        .enumerate()
+        .map(|(index, x)| StabilityProbe {
+          value: *x % 16,
+          index,
+        })
+        .collect();
+      const_sort_with(algorithm, &mut probes, PartialOrd::lt);
+      assert!(StabilityProbe::is_stably_sorted(&probes), "{algorithm:?}");
+    }
+  }
+}
+
+#[test]
+fn const_core_sort_with_estimates() {
+  const ALGORITHMS: [Algorithm; 7] = [
+    Algorithm::Pdq,
+    Algorithm::Heap,
+    Algorithm::Insertion,
+    Algorithm::Selection,
+    Algorithm::Cocktail,
+    Algorithm::Merge,
+    Algorithm::Adaptive,
+  ];
+  // The estimates bound the comparisons on random, sorted, reversed and sawtooth inputs.
+  for len in [0, 1, 2, 20, 21, 100, 1000, RAND_CNT] {
+    let random = gen_array(len);
+    let mut sorted = random.clone();
+    sorted.sort_unstable();
+    let reversed: Vec<u32> = sorted.iter().rev().copied().collect();
+    let sawtooth: Vec<u32> = (0..u32::try_from(len).unwrap()).map(|i| i % 7).collect();
+    for algorithm in ALGORITHMS {
+      if len > 1000 && algorithm.estimated_comparisons(len) > 10_000_000 {
+        continue;
+      }
+      for input in [&random, &sorted, &reversed, &sawtooth] {
+        let mut comparisons = 0;
+        let mut v = input.clone();
+        const_sort_with(algorithm, &mut v, |a, b| {
+          comparisons += 1;
+          a < b
+        });
+        assert!(
+          comparisons <= algorithm.estimated_comparisons(len),
+          "{algorithm:?} {len}: {comparisons}"
+        );
+      }
+    }
+    // The estimate of `Algorithm::current` also bounds the default sort, whatever the features.
+    for input in [&random, &sorted, &reversed, &sawtooth] {
+      let mut comparisons = 0;
+      let mut v = input.clone();
+      const_quicksort(&mut v, |a, b| {
+        comparisons += 1;
+        a < b
+      });
+      assert!(
+        comparisons <= Algorithm::current().estimated_comparisons(len),
+        "{len}: {comparisons}"
+      );
+    }
+  }
+  const MOVES: [usize; 3] = [
+    Algorithm::Selection.estimated_moves(10, 4),
+    Algorithm::Pdq.estimated_moves(1000, 0),
+    Algorithm::Merge.estimated_moves(0, 8),
+  ];
+  assert_eq!(MOVES, [108, 0, 0]);
+  for algorithm in ALGORITHMS {
+    assert_eq!(algorithm.estimated_comparisons(usize::MAX), usize::MAX);
+    assert_eq!(algorithm.estimated_moves(usize::MAX, 1), usize::MAX);
+    assert!(algorithm.estimated_moves(100, 1) * 16 == algorithm.estimated_moves(100, 16));
+  }
+}
+
+#[test]
+fn const_slice_apply_and_scatter_permutation() {
+  const PERM: [usize; 6] = [3, 0, 4, 1, 5, 2];
+  const GATHERED: [u8; 6] = {
+    let mut v = [10, 11, 12, 13, 14, 15];
+    v.const_apply_permutation(&PERM);
     v
   };
-  assert_eq!(&ARR, &[2, 3, 4, 5]);
+  assert_eq!(GATHERED, [13, 10, 14, 11, 15, 12]);
+  const SCATTERED: [u8; 6] = {
+    let mut v = GATHERED;
+    v.const_scatter_permutation(&PERM);
+    v
+  };
+  assert_eq!(SCATTERED, [10, 11, 12, 13, 14, 15]);
+
+  let v = gen_array(RAND_CNT);
+  let mut perm: Vec<usize> = (0..RAND_CNT).collect();
+  perm.sort_unstable_by_key(|&i| v[i]);
+  let mut gathered = v.clone();
+  gathered.const_apply_permutation(&perm);
+  let mut sorted = v.clone();
+  sorted.sort_unstable();
+  assert_eq!(gathered, sorted);
+  for (i, &p) in perm.iter().enumerate() {
+    assert_eq!(gathered[i], v[p]);
+  }
+  let mut scattered = v.clone();
+  scattered.const_scatter_permutation(&perm);
+  for (i, &p) in perm.iter().enumerate() {
+    assert_eq!(scattered[p], v[i]);
+  }
+  gathered.const_scatter_permutation(&perm);
+  assert_eq!(gathered, v);
 }
+
 #[test]
-fn const_core_slice_quicksort_rng() {
+#[should_panic(expected = "perm is not a permutation of 0..len")]
+fn const_slice_apply_permutation_invalid() {
+  [1, 2, 3].const_apply_permutation(&[0, 2, 2]);
+}
+
+#[test]
+fn const_slice_is_sorted_rotated() {
+  const A: Option<usize> = [3, 4, 5, 1, 2].const_is_sorted_rotated();
+  assert_eq!(A, Some(3));
+  const B: Option<usize> = [1, 1, 0, 1].const_is_sorted_rotated();
+  assert_eq!(B, Some(2));
+  const C: Option<usize> = [2, 3, 1, 3].const_is_sorted_rotated();
+  assert_eq!(C, None);
+  const D: Option<usize> = [3, 1, 2, 4].const_is_sorted_rotated();
+  assert_eq!(D, None);
+  const E: Option<usize> = ([] as [u8; 0]).const_is_sorted_rotated();
+  assert_eq!(E, Some(0));
+
+  let mut sorted = gen_array(RAND_CNT);
+  sorted.sort_unstable();
+  assert_eq!(sorted.const_is_sorted_rotated(), Some(0));
+  let mut rotated = sorted.clone();
+  rotated.rotate_left(RAND_CNT / 3);
+  let rotation = rotated.const_is_sorted_rotated().unwrap();
+  let mut unrotated = rotated.clone();
+  unrotated.rotate_left(rotation);
+  assert_eq!(unrotated, sorted);
+}
+
+#[test]
+fn const_slice_binary_search_rotated() {
+  const RING: [u8; 6] = [7, 9, 9, 1, 3, 7];
+  const A: Result<usize, usize> = RING.const_binary_search_rotated(3, &7);
+  assert_eq!(A, Ok(5));
+  const B: Result<usize, usize> = RING.const_binary_search_rotated(3, &9);
+  assert_eq!(B, Ok(1));
+  const C: Result<usize, usize> = RING.const_binary_search_rotated(3, &2);
+  assert_eq!(C, Err(4));
+  const D: Result<usize, usize> = RING.const_binary_search_rotated(3, &8);
+  assert_eq!(D, Err(1));
+  const E: Result<usize, usize> = RING.const_binary_search_rotated(3, &10);
+  assert_eq!(E, Err(3));
+
+  let mut rotated = gen_array(RAND_CNT);
+  rotated.sort_unstable();
+  rotated.rotate_right(RAND_CNT / 3);
+  let rotation = rotated.const_is_sorted_rotated().unwrap();
+  for x in rotated.iter().step_by(7) {
+    let index = rotated.const_binary_search_rotated(rotation, x).unwrap();
+    assert_eq!(rotated[index], *x);
+  }
+  for x in [0, u32::MAX / 2, u32::MAX] {
+    if let Err(index) = rotated.const_binary_search_rotated(rotation, &x) {
+      let mut inserted = rotated.clone();
+      inserted.insert(index, x);
+      assert!(inserted.const_is_sorted_rotated().is_some());
+    }
+  }
+}
+
+#[test]
+fn const_slice_sort_unstable_by_cached_key() {
+  const V: ([i16; 7], usize) = {
+    let mut calls = 0;
+    let mut v = [-5, 3, 0, -2, 7, 1, -6];
+    let mut buffer = [MaybeUninit::uninit(); 8];
+    v.const_sort_unstable_by_cached_key(
+      const |x: &i16| {
+        calls += 1;
+        x.unsigned_abs()
+      },
+      &mut buffer,
+    );
+    (v, calls)
+  };
+  assert_eq!(V, ([0, 1, -2, 3, -5, -6, 7], 7));
+
+  let v = gen_array(RAND_CNT);
+  cross_check(&v);
+  let mut cached = v.clone();
+  let mut buffer = vec![MaybeUninit::uninit(); RAND_CNT];
+  cached.const_sort_unstable_by_cached_key(|x| x.reverse_bits(), &mut buffer);
+  let mut expected = v;
+  expected.sort_unstable_by_key(|x| x.reverse_bits());
+  assert_eq!(cached, expected);
+}
+
+#[test]
+#[should_panic(expected = "buffer is shorter than the slice")]
+fn const_slice_sort_unstable_by_cached_key_short_buffer() {
+  let mut buffer = [MaybeUninit::uninit(); 2];
+  [3, 2, 1].const_sort_unstable_by_cached_key(|x: &u8| *x, &mut buffer);
+}
+
+#[test]
+fn const_slice_sort_stable() {
+  const V: [u8; 6] = {
+    let mut v = [3, 1, 2, 3, 0, 1];
+    v.const_sort();
+    v
+  };
+  assert_eq!(V, [0, 1, 1, 2, 3, 3]);
+  const PAIRS: [(u8, char); 6] = {
+    let mut pairs = [(2, 'a'), (1, 'b'), (2, 'c'), (0, 'd'), (1, 'e'), (2, 'f')];
+    pairs.const_sort_by(const |a: &(u8, char), b: &(u8, char)| a.0.cmp(&b.0));
+    pairs
+  };
+  assert_eq!(
+    PAIRS,
+    [(0, 'd'), (1, 'b'), (1, 'e'), (2, 'a'), (2, 'c'), (2, 'f')]
+  );
+
+  let v: Vec<(u32, usize)> = gen_array(RAND_CNT)
+    .into_iter()
+    .map(|x| x % 64)
+    .zip(0..)
+    .collect();
+  cross_check(&v);
+  let mut sorted = v.clone();
+  sorted.const_sort_by(|a, b| a.0.cmp(&b.0));
+  let mut expected = v;
+  expected.sort_by(|a, b| a.0.cmp(&b.0));
+  assert_eq!(sorted, expected);
+  sorted.const_sort();
+  assert_eq!(sorted, expected);
+}
+
+#[test]
+fn const_slice_binary_search() {
+  const S: [u8; 7] = [1, 3, 3, 3, 8, 9, 12];
+  const RESULTS: [Result<usize, usize>; 5] = [
+    S.const_binary_search(&8),
+    S.const_binary_search(&0),
+    S.const_binary_search(&5),
+    S.const_binary_search(&13),
+    ([] as [u8; 0]).const_binary_search(&1),
+  ];
+  assert_eq!(RESULTS, [Ok(4), Err(0), Err(4), Err(7), Err(0)]);
+  const DUPLICATE: Result<usize, usize> = S.const_binary_search(&3);
+  assert!(matches!(DUPLICATE, Ok(1..=3)));
+
   let mut v = gen_array(RAND_CNT);
-  const_quicksort(&mut v, PartialOrd::lt);
-  assert!(v.is_sorted());
+  v.sort_unstable();
+  for x in v.iter().step_by(7).copied().chain([0, 1, u32::MAX]) {
+    match v.const_binary_search(&x) {
+      Ok(index) => assert_eq!(v[index], x),
+      Err(index) => {
+        assert_eq!(v.binary_search(&x), Err(index));
+      },
+    }
+  }
 }
 
 #[test]
-fn const_core_slice_sort_unstable() {
+fn const_array_pad_sorted_to_pow2() {
+  const PADDED: [i8; 8] = const_pad_sorted_to_pow2([-3, 0, 0, 7, 9], 9);
+  assert_eq!(PADDED, [-3, 0, 0, 7, 9, 9, 9, 9]);
+  const RESULTS: [Result<usize, usize>; 5] = [
+    const_binary_search_padded(&PADDED, 5, &0),
+    const_binary_search_padded(&PADDED, 5, &9),
+    const_binary_search_padded(&PADDED, 5, &8),
+    const_binary_search_padded(&PADDED, 5, &10),
+    const_binary_search_padded(&PADDED, 5, &-4),
+  ];
+  assert_eq!(RESULTS, [Ok(1), Ok(4), Err(4), Err(5), Err(0)]);
+  const EMPTY: [u8; 1] = const_pad_sorted_to_pow2([], 0);
+  assert_eq!(const_binary_search_padded(&EMPTY, 0, &0), Err(0));
+
   let mut v = gen_array(RAND_CNT);
-  v.const_sort_unstable();
-  assert!(v.is_sorted());
+  v.sort_unstable();
+  let mut padded = v.clone();
+  padded.resize(RAND_CNT.next_power_of_two(), u32::MAX);
+  for x in v.iter().step_by(5).copied().chain([0, 1, u32::MAX]) {
+    let expected = v.partition_point(|e| *e < x);
+    let result = const_binary_search_padded(&padded, v.len(), &x);
+    if v.get(expected) == Some(&x) {
+      assert_eq!(result, Ok(expected));
+    } else {
+      assert_eq!(result, Err(expected));
+    }
+  }
 }
+
 #[test]
-fn const_core_slice_sort_unstable_by() {
+#[should_panic(expected = "sentinel is less than the largest element")]
+fn const_array_pad_sorted_to_pow2_small_sentinel() {
+  let _: [u8; 4] = const_pad_sorted_to_pow2(black_box([1, 2, 3]), 2);
+}
+
+#[test]
+fn const_slice_binary_search_by() {
+  use core::cmp::Ordering;
+
+  const fn by_len(a: &&str, b: &&str) -> Ordering {
+    b.len().cmp(&a.len())
+  }
+  const WORDS: [&str; 5] = {
+    let mut words = ["rs", "const", "a", "sort", "abc"];
+    words.const_sort_unstable_by(by_len);
+    words
+  };
+  const RESULTS: [Result<usize, usize>; 3] = [
+    WORDS.const_binary_search_by(const |w: &&str| by_len(w, &"xyz")),
+    WORDS.const_binary_search_by(const |w: &&str| by_len(w, &"xyzxyz")),
+    WORDS.const_binary_search_by(const |w: &&str| by_len(w, &"")),
+  ];
+  assert_eq!(RESULTS, [Ok(2), Err(0), Err(5)]);
+
   let mut v = gen_array(RAND_CNT);
-  v.const_sort_unstable_by(Ord::cmp);
-  assert!(v.is_sorted());
+  v.sort_unstable_by(|a, b| b.cmp(a));
+  for x in v.iter().step_by(7).copied().chain([0, u32::MAX]) {
+    let result = v.const_binary_search_by(|e| x.cmp(e));
+    assert_eq!(result.is_ok(), v.contains(&x));
+    match result {
+      Ok(index) => assert_eq!(v[index], x),
+      Err(index) => assert_eq!(index, v.partition_point(|e| *e > x)),
+    }
+  }
+}
+
+#[test]
+fn const_slice_binary_search_branchless() {
+  const S: [u8; 6] = [2, 2, 5, 7, 7, 7];
+  const RESULTS: [Result<usize, usize>; 6] = [
+    S.const_binary_search_branchless(&2),
+    S.const_binary_search_branchless(&7),
+    S.const_binary_search_branchless(&6),
+    S.const_binary_search_branchless(&1),
+    S.const_binary_search_branchless(&8),
+    ([] as [u8; 0]).const_binary_search_branchless(&8),
+  ];
+  assert_eq!(RESULTS, [Ok(0), Ok(3), Err(3), Err(0), Err(6), Err(0)]);
+
+  for len in [1, 2, 3, 64, 100, RAND_CNT] {
+    let mut v = gen_array(len);
+    v.iter_mut().for_each(|x| *x %= 500);
+    v.sort_unstable();
+    for x in 0..=500 {
+      let expected = v.partition_point(|e| *e < x);
+      let result = v.const_binary_search_branchless(&x);
+      if v.get(expected) == Some(&x) {
+        assert_eq!(result, Ok(expected));
+      } else {
+        assert_eq!(result, Err(expected));
+      }
+    }
+  }
+}
+
+#[test]
+fn sorted_cursor() {
+  const TABLE: [u8; 9] = [1, 3, 3, 3, 6, 8, 10, 10, 15];
+  const WALK: ([Option<usize>; 6], usize, usize) = {
+    let queries = [0, 3, 3, 7, 10, 16];
+    let mut cursor = SortedCursor::new(&TABLE);
+    let mut found = [None; 6];
+    let mut i = 0;
+    while i < queries.len() {
+      found[i] = cursor.advance_to(&queries[i]);
+      i += 1;
+    }
+    (found, cursor.position(), cursor.remaining().len())
+  };
+  assert_eq!(WALK, ([None, Some(1), Some(1), None, Some(6), None], 9, 0));
+  assert_eq!(SortedCursor::<u8>::new(&[]).advance_to(&1), None);
+
+  let mut table = gen_array(RAND_CNT);
+  table.sort_unstable();
+  let mut queries = gen_array(RAND_CNT / 10);
+  queries.extend(table.iter().step_by(17));
+  queries.sort_unstable();
+  let mut cursor = SortedCursor::new(&table);
+  for x in &queries {
+    let expected = table.partition_point(|e| e < x);
+    let found = cursor.advance_to(x);
+    assert_eq!(cursor.position(), expected);
+    assert_eq!(found, (table.get(expected) == Some(x)).then_some(expected));
+    assert_eq!(cursor.remaining(), &table[expected..]);
+  }
+}
+
+#[test]
+fn const_slice_partition_point() {
+  // Predicates take their element by reference.
+  #[allow(clippy::trivially_copy_pass_by_ref)]
+  const fn is_even(x: &u8) -> bool {
+    *x % 2 == 0
+  }
+  const POINTS: [usize; 4] = [
+    [2, 4, 6, 1, 3].const_partition_point(is_even),
+    [1, 3].const_partition_point(is_even),
+    [2, 4].const_partition_point(is_even),
+    [].const_partition_point(is_even),
+  ];
+  assert_eq!(POINTS, [3, 0, 2, 0]);
+
+  let mut v = gen_array(RAND_CNT);
+  v.sort_unstable();
+  for x in v.iter().step_by(11).copied().chain([0, u32::MAX]) {
+    assert_eq!(
+      v.const_partition_point(|e| *e <= x),
+      v.partition_point(|e| *e <= x)
+    );
+  }
+}
+#[test]
+fn const_column_table() {
+  const TABLE: ConstTable2<u8, 4, char, u16> =
+    ConstTable2::new([30, 10, 40, 20], ['c', 'a', 'd', 'b'], [300, 100, 400, 200]);
+  const HITS: [Option<(&char, &u16)>; 3] = [TABLE.get(&10), TABLE.get(&40), TABLE.get(&25)];
+  assert_eq!(HITS, [Some((&'a', &100)), Some((&'d', &400)), None]);
+  assert_eq!(TABLE.keys(), &[10, 20, 30, 40]);
+  assert_eq!(TABLE.column1(), &['a', 'b', 'c', 'd']);
+  assert_eq!(TABLE.column2(), &[100, 200, 300, 400]);
+  assert_eq!(TABLE.position(&30), Some(2));
+  assert_eq!(TABLE.len(), 4);
+
+  const EMPTY: ConstTable1<u8, 0, u8> = ConstTable1::new([], []);
+  assert!(EMPTY.is_empty());
+  assert_eq!(EMPTY.get(&0), None);
+
+  let keys: [u32; 256] = gen_array(256).try_into().unwrap();
+  let rows: [usize; 256] = core::array::from_fn(|i| i);
+  let table = ConstTable4::new(
+    keys,
+    rows,
+    keys.map(|k| k / 3),
+    keys.map(u64::from),
+    [(); 256],
+  );
+  for (row, key) in keys.iter().enumerate() {
+    assert_eq!(
+      table.get(key),
+      Some((&row, &(key / 3), &u64::from(*key), &()))
+    );
+  }
+  assert!(table.keys().windows(2).all(|w| w[0] < w[1]));
+  assert_eq!(table.get(&(table.keys()[0] - 1)), None);
+  let table = ConstTable3::new(keys, rows, rows, rows);
+  assert_eq!(table.column1(), table.column3());
+}
+
+#[test]
+#[should_panic(expected = "table keys are not unique")]
+fn const_column_table_duplicate_keys() {
+  let _ = ConstTable1::new(black_box([2, 1, 2]), ['a', 'b', 'c']);
+}
+#[test]
+fn const_slice_exponential_search() {
+  use core::cmp::Ordering;
+  const S: [u8; 9] = [1, 1, 2, 7, 7, 7, 9, 12, 30];
+  const RESULTS: [Result<usize, usize>; 6] = [
+    S.const_exponential_search(&1),
+    S.const_exponential_search(&7),
+    S.const_exponential_search(&8),
+    S.const_exponential_search(&31),
+    [].const_exponential_search(&0),
+    S.const_exponential_search_by(const |x: &u8| -> Ordering { x.cmp(&7) }),
+  ];
+  assert_eq!(RESULTS, [Ok(0), Ok(3), Err(6), Err(9), Err(0), Ok(3)]);
+
+  let mut v = gen_array(RAND_CNT);
+  v.sort_unstable();
+  for x in v.iter().step_by(7).copied().chain([0, u32::MAX]) {
+    let first = v.partition_point(|e| *e < x);
+    let expected = if v.get(first) == Some(&x) {
+      Ok(first)
+    } else {
+      Err(first)
+    };
+    assert_eq!(v.const_exponential_search(&x), expected);
+    assert_eq!(v.const_exponential_search_by(|e| e.cmp(&x)), expected);
+  }
+}
+#[test]
+fn const_table_lookup_many() {
+  const OUT: [Option<usize>; 6] = {
+    let mut out = [None; 6];
+    const_lookup_many(&[2, 4, 4, 8, 16], &[0, 4, 4, 5, 16, 17], &mut out);
+    out
+  };
+  assert_eq!(OUT, [None, Some(1), Some(1), None, Some(4), None]);
+  const_lookup_many::<u8>(&[], &[], &mut []);
+
+  let mut table = gen_array(RAND_CNT);
+  table.sort_unstable();
+  let mut queries = gen_array(RAND_CNT / 10);
+  queries.extend(table.iter().step_by(13));
+  queries.sort_unstable();
+  let mut out = vec![None; queries.len()];
+  const_lookup_many(&table, &queries, &mut out);
+  for (x, row) in queries.iter().zip(out) {
+    let first = table.partition_point(|e| e < x);
+    assert_eq!(row, (table.get(first) == Some(x)).then_some(first));
+  }
+}
+
+#[test]
+#[should_panic(expected = "queries are not sorted")]
+fn const_table_lookup_many_unsorted() {
+  const_lookup_many(&[1, 2, 3], &[2, 1], &mut [None; 2]);
+}
+#[test]
+fn const_sorted_slice() {
+  const S: ConstSortedSlice<u8> = ConstSortedSlice::new(&[1, 3, 3, 3, 7]);
+  const RANGES: [core::ops::Range<usize>; 4] = [
+    S.equal_range(&3),
+    S.equal_range(&0),
+    S.equal_range(&5),
+    S.equal_range(&8),
+  ];
+  assert_eq!(RANGES, [1..4, 0..0, 4..4, 5..5]);
+  assert_eq!(S.binary_search(&7), Ok(4));
+  assert_eq!(S.binary_search(&2), Err(1));
+  assert!(S.contains(&1));
+  assert_eq!(S.len(), 5);
+  assert!(ConstSortedSlice::<u8>::new(&[]).equal_range(&1).is_empty());
+  assert_eq!(
+    ConstSorted::new([2, 1]).as_sorted_slice().as_slice(),
+    &[1, 2]
+  );
+
+  let mut v = gen_array(RAND_CNT);
+  v.sort_unstable();
+  let sorted = ConstSortedSlice::new(&v);
+  for x in v.iter().step_by(9).copied().chain([0, u32::MAX]) {
+    let range = sorted.equal_range(&x);
+    assert_eq!(range.start, v.partition_point(|e| *e < x));
+    assert_eq!(range.end, v.partition_point(|e| *e <= x));
+    assert_eq!(sorted.contains(&x), v.binary_search(&x).is_ok());
+  }
+}
+
+#[test]
+#[should_panic(expected = "slice is not sorted")]
+fn const_sorted_slice_unsorted() {
+  let _ = ConstSortedSlice::new(black_box(&[2, 1]));
+}
+#[test]
+fn const_array_map_keys_monotonic() {
+  const KEYS: ConstSorted<i32, 5> = ConstSorted::new([9, -4, 3, 0, 2]);
+  const HALVED: ConstSorted<i32, 5> = const_map_keys_monotonic(&KEYS, const |x: &i32| *x / 2);
+  assert_eq!(HALVED.into_inner(), [-2, 0, 1, 1, 4]);
+
+  let sorted = ConstSorted::<u32, 0>::new([]);
+  assert!(const_map_keys_monotonic(&sorted, |x| u64::from(*x)).is_empty());
+  let keys: [u32; 512] = gen_array(512).try_into().unwrap();
+  let sorted = ConstSorted::new(keys);
+  let mapped = const_map_keys_monotonic(&sorted, |x| u64::from(*x) * 3 + 1);
+  assert!(mapped.is_sorted());
+  assert_eq!(mapped[0], u64::from(sorted[0]) * 3 + 1);
+}
+
+#[test]
+#[should_panic(expected = "f is not monotonic")]
+fn const_array_map_keys_not_monotonic() {
+  let _ = const_map_keys_monotonic(&ConstSorted::new([1, 2]), |x: &i32| -x);
+}
+#[test]
+fn const_sorted_map() {
+  const MAP: ConstSortedMap<u8, &str, 4> =
+    ConstSortedMap::new([(7, "seven"), (1, "one"), (42, "answer"), (3, "three")]);
+  const HITS: [Option<&&str>; 3] = [MAP.get(&1), MAP.get(&42), MAP.get(&2)];
+  assert_eq!(HITS, [Some(&"one"), Some(&"answer"), None]);
+  assert_eq!(MAP.get_key_value(&7), Some((&7, &"seven")));
+  assert!(MAP.contains_key(&3));
+  assert!(!MAP.contains_key(&43));
+  assert_eq!(MAP.position(&42), Some(3));
+  assert_eq!(MAP.entries().map(|(k, _)| k), [1, 3, 7, 42]);
+  assert_eq!(MAP.len(), 4);
+  assert!(ConstSortedMap::<u8, (), 0>::new([]).is_empty());
+
+  let keys: [u32; 512] = gen_array(512).try_into().unwrap();
+  let map = ConstSortedMap::new(keys.map(|k| (k, u64::from(k) * 2)));
+  for key in keys {
+    assert_eq!(map.get(&key), Some(&(u64::from(key) * 2)));
+    assert_eq!(
+      map.get(&key.wrapping_add(1)).is_some(),
+      keys.contains(&key.wrapping_add(1))
+    );
+  }
+}
+
+#[test]
+#[should_panic(expected = "map keys are not unique")]
+fn const_sorted_map_duplicate_keys() {
+  let _ = ConstSortedMap::new(black_box([(2, 'a'), (1, 'b'), (2, 'c')]));
+}
+#[test]
+fn const_core_slice_partition_at_index_instrumented() {
+  const STATS: SelectStats = {
+    let mut v = testgen::ascending::<1024>();
+    const_partition_at_index_instrumented(&mut v, 0, PartialOrd::lt)
+  };
+  // The minimum is found by a linear scan.
+  assert_eq!(STATS, SelectStats::default());
+
+  let mut v = gen_array(RAND_CNT);
+  let stats = const_partition_at_index_instrumented(&mut v, RAND_CNT / 2, PartialOrd::lt);
+  let mut sorted = v.clone();
+  sorted.sort_unstable();
+  assert_eq!(v[RAND_CNT / 2], sorted[RAND_CNT / 2]);
+  assert!(stats.partition_rounds > 0);
+  assert!(stats.depth <= stats.partition_rounds);
+  assert!(stats.partition_rounds < 64);
+}
+#[test]
+fn const_sorted_set() {
+  const KEYWORDS: ConstSortedSet<u8, 5> = ConstSortedSet::new([b'w', b'f', b'i', b'e', b'l']);
+  const SOME: ConstSortedSet<u8, 3> = ConstSortedSet::new([b'i', b'x', b'f']);
+  const CHECKS: (bool, bool, usize, bool) = (
+    KEYWORDS.contains(&b'l'),
+    SOME.is_subset(&KEYWORDS),
+    SOME.intersection_count(&KEYWORDS),
+    ConstSortedSet::<u8, 0>::new([]).is_subset(&SOME),
+  );
+  assert_eq!(CHECKS, (true, false, 2, true));
+  assert_eq!(KEYWORDS.as_slice(), b"efilw");
+  assert!(!KEYWORDS.contains(&b'x'));
+  assert!(KEYWORDS.is_subset(&KEYWORDS));
+  assert_eq!(KEYWORDS.len(), 5);
+
+  let mut elements = gen_array(512);
+  elements.sort_unstable();
+  elements.dedup();
+  let all: [u32; 256] = elements[..256].try_into().unwrap();
+  let even: [u32; 128] = core::array::from_fn(|i| all[2 * i]);
+  let (all, even) = (ConstSortedSet::new(all), ConstSortedSet::new(even));
+  assert!(even.is_subset(&all));
+  assert!(!all.is_subset(&even));
+  assert_eq!(all.intersection_count(&even), 128);
+  assert!(all.as_slice().iter().all(|x| all.contains(x)));
+}
+
+#[test]
+#[should_panic(expected = "set elements are not unique")]
+fn const_sorted_set_duplicates() {
+  let _ = ConstSortedSet::new(black_box([3, 1, 3]));
+}
+#[test]
+fn const_generic_arguments() {
+  struct Capacity<const N: usize>;
+  impl<const N: usize> Capacity<N> {
+    const VALUE: usize = N;
+  }
+
+  const SIZES: [usize; 5] = [32, 4, 128, 16, 64];
+  type Smallest = Capacity<{ const_sorted(SIZES)[0] }>;
+  type Median = Capacity<{ *ConstSorted::new(SIZES).select_nth(2).1 }>;
+  type Unique = Capacity<{ const_sorted_dedup(const_sorted([3, 1, 3, 2, 1])).1 }>;
+  assert_eq!(Smallest::VALUE, 4);
+  assert_eq!(Median::VALUE, 32);
+  assert_eq!(Unique::VALUE, 3);
+  let largest: [u8; const_sorted(SIZES)[4]] = [0; 128];
+  assert_eq!(largest.len(), 128);
+}
+
+#[cfg(feature = "const-closure-compat")]
+#[test]
+fn const_closure_compat() {
+  use crate::const_closure::ConstFnMutClosure;
+  use core::cmp::Ordering;
+
+  const SORTED: ([i32; 5], usize) = {
+    const fn cmp(count: &mut usize, (a, b): (&i32, &i32)) -> Ordering {
+      *count += 1;
+      a.cmp(b)
+    }
+    let mut count = 0;
+    let mut v = [5, 4, 1, 3, 2];
+    v.const_sort_unstable_by(ConstFnMutClosure::new(&mut count, cmp));
+    (v, count)
+  };
+  assert_eq!(SORTED.0, [1, 2, 3, 4, 5]);
+  assert!(SORTED.1 >= 4);
+
+  const BY_KEY: ([i32; 5], usize) = {
+    const fn key(calls: &mut usize, (x,): (&i32,)) -> i32 {
+      *calls += 1;
+      x.abs()
+    }
+    let mut calls = 0;
+    let mut v = [-5, 4, 1, -3, 2];
+    v.const_sort_unstable_by_key(ConstFnMutClosure::new(&mut calls, key));
+    (v, calls)
+  };
+  assert_eq!(BY_KEY.0, [1, 2, -3, 4, -5]);
+  assert!(BY_KEY.1 >= 8);
 }
 
 mod from_rustc {
@@ -90,6 +3486,8 @@ mod from_rustc {
             *item = rng.gen::<i32>() % modulus;
           }
 
+          cross_check(v);
+
           // Sort in default order.
           tmp.copy_from_slice(v);
           tmp.const_sort_unstable();
@@ -279,3 +3677,150 @@ mod from_rustc {
 mod const_rustc {
   // TODO: port tinyrand to const
 }
+
+mod macros {
+  use super::{gen_array, Vec, RAND_CNT};
+  use crate::const_array::{const_find_duplicate_key, const_sorted_dedup};
+  use crate::{
+    const_assert_unique_keys, const_dedup_array, const_priority_sort, const_shrink_to_count,
+    const_table, ConstSliceSortExt,
+  };
+
+  #[test]
+  fn const_dedup_array() {
+    const_dedup_array! {
+      const UNIQUE: [i32] = {
+        let mut v = [5, -1, 3, 5, 5, -1, 0];
+        v.const_sort_unstable();
+        v
+      };
+    }
+    assert_eq!(UNIQUE, [-1, 0, 3, 5]);
+  }
+
+  #[test]
+  fn const_shrink_to_count() {
+    const_shrink_to_count! {
+      /// Shrinks the prefix written by [`const_sorted_dedup`].
+      const UNIQUE: [char] = const_sorted_dedup(['a', 'a', 'b', 'z', 'z']);
+    }
+    assert_eq!(UNIQUE, ['a', 'b', 'z']);
+
+    const_shrink_to_count! {
+      const NONE: [u8] = ([1, 2, 3], 0);
+    }
+    assert_eq!(NONE, []);
+  }
+
+  #[test]
+  fn const_priority_sort() {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Event {
+      Tick,
+      Key(char),
+      Resize { width: u16, height: u16 },
+      Quit,
+    }
+    const EVENTS: [Event; 5] = {
+      let mut events = [
+        Event::Tick,
+        Event::Key('a'),
+        Event::Quit,
+        Event::Resize {
+          width: 80,
+          height: 24,
+        },
+        Event::Tick,
+      ];
+      const_priority_sort!(
+        &mut events,
+        Event {
+          Quit => -1,
+          Resize => 0,
+          Key => 5,
+          Tick => 10,
+        }
+      );
+      events
+    };
+    assert_eq!(
+      EVENTS,
+      [
+        Event::Quit,
+        Event::Resize {
+          width: 80,
+          height: 24
+        },
+        Event::Key('a'),
+        Event::Tick,
+        Event::Tick
+      ]
+    );
+
+    let mut events: Vec<Event> = gen_array(RAND_CNT)
+      .iter()
+      .map(|x| match x % 3 {
+        0 => Event::Tick,
+        1 => Event::Key('x'),
+        _ => Event::Quit,
+      })
+      .collect();
+    const_priority_sort!(&mut events, Event { Tick => 2, Key => 1, Quit => 0, Resize => 3 });
+    assert!(events.windows(2).all(|w| !matches!(
+      (w[0], w[1]),
+      (Event::Tick, Event::Key(_) | Event::Quit) | (Event::Key(_), Event::Quit)
+    )));
+  }
+
+  #[test]
+  fn const_assert_unique_keys() {
+    const REGISTRY: [(u32, &str); 4] = [(10, "a"), (3, "b"), (7, "c"), (1, "d")];
+    const_assert_unique_keys!(REGISTRY, |entry| entry.0);
+    const_assert_unique_keys!(["x", "yy", "zzz"], |s| s.len());
+    assert_eq!(const_find_duplicate_key(&REGISTRY, |entry| entry.0), None);
+  }
+
+  #[test]
+  fn const_table() {
+    // Key functions take their element by reference.
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    const fn id(row: &(u32, char)) -> u32 {
+      row.0
+    }
+    const_table! {
+      const HANDLERS: [(u32, char); 5] sorted_by id = [
+        (9, 'a'),
+        (2, 'b'),
+        (7, 'c'),
+        (2, 'd'),
+        (40, 'e'),
+      ];
+      const SORTED: [i16; 4] sorted = [5, -3, 5, 0];
+      const MANUAL: [u8; 4] = [1, 2, 2, 3];
+      const UNSORTED: [u8; 3] = [3, 1, 1];
+      const EMPTY: [u8; 0] = [];
+    }
+    assert_eq!(HANDLERS.map(|row| row.0), [2, 2, 7, 9, 40]);
+    assert_eq!(
+      (HANDLERS::LEN, HANDLERS::IS_SORTED, HANDLERS::UNIQUE_KEYS),
+      (5, true, 4)
+    );
+    assert_eq!(SORTED, [-3, 0, 5, 5]);
+    assert_eq!(
+      (SORTED::LEN, SORTED::IS_SORTED, SORTED::UNIQUE_KEYS),
+      (4, true, 3)
+    );
+    assert_eq!(
+      (MANUAL::LEN, MANUAL::IS_SORTED, MANUAL::UNIQUE_KEYS),
+      (4, true, 3)
+    );
+    assert_eq!(
+      (UNSORTED::LEN, UNSORTED::IS_SORTED, UNSORTED::UNIQUE_KEYS),
+      (3, false, 2)
+    );
+    assert_eq!(
+      (EMPTY::LEN, EMPTY::IS_SORTED, EMPTY::UNIQUE_KEYS),
+      (0, true, 0)
+    );
+  }
+}
@@ -0,0 +1,152 @@
+//! Generators for classic and adversarial sorting inputs.
+//!
+//! Every generator returns a `[usize; N]` array built entirely at compile time, so worst cases
+//! of sorting and selection functions can be exercised in const items without embedding data
+//! files. Map the elements to another type if needed.
+//!
+//! # Examples
+//!
+//! ```
+//! #![feature(const_mut_refs)]
+//! #![feature(const_trait_impl)]
+//! use const_sort::{testgen, ConstSliceSortExt};
+//!
+//! const SORTED: bool = {
+//!   let mut v = testgen::median_of_3_killer::<64>();
+//!   v.const_sort_unstable();
+//!   v.const_is_sorted()
+//! };
+//! assert!(SORTED);
+//! ```
+
+/// Returns `0, 1, ..., N - 1`.
+#[must_use]
+pub const fn ascending<const N: usize>() -> [usize; N] {
+  let mut v = [0; N];
+  let mut i = 0;
+  while i < N {
+    v[i] = i;
+    i += 1;
+  }
+  v
+}
+
+/// Returns `N - 1, N - 2, ..., 0`.
+#[must_use]
+pub const fn descending<const N: usize>() -> [usize; N] {
+  let mut v = [0; N];
+  let mut i = 0;
+  while i < N {
+    v[i] = N - 1 - i;
+    i += 1;
+  }
+  v
+}
+
+/// Returns `N` copies of `value`.
+#[must_use]
+pub const fn all_equal<const N: usize>(value: usize) -> [usize; N] {
+  [value; N]
+}
+
+/// Returns ascending runs of length `period`, i.e. `i % period` at index `i`.
+///
+/// # Panics
+///
+/// Panics if `period` is 0.
+#[must_use]
+pub const fn sawtooth<const N: usize>(period: usize) -> [usize; N] {
+  assert!(period != 0, "sawtooth period must not be 0");
+  let mut v = [0; N];
+  let mut i = 0;
+  while i < N {
+    v[i] = i % period;
+    i += 1;
+  }
+  v
+}
+
+/// Returns an ascending first half followed by a descending second half, e.g.
+/// `0, 1, 2, 2, 1, 0`.
+#[must_use]
+pub const fn organ_pipe<const N: usize>() -> [usize; N] {
+  let mut v = [0; N];
+  let mut i = 0;
+  while i < N {
+    v[i] = if i < N - 1 - i { i } else { N - 1 - i };
+    i += 1;
+  }
+  v
+}
+
+/// Returns `0, 1, ..., N - 1` with the largest element pushed to the front, i.e.
+/// `N - 1, 0, 1, ..., N - 2`.
+///
+/// Sorts that only check whether the input is already sorted from the front miss this nearly
+/// sorted input, and insertion sorts have to shift the first element across the whole array.
+#[must_use]
+pub const fn push_front<const N: usize>() -> [usize; N] {
+  let mut v = [0; N];
+  let mut i = 1;
+  while i < N {
+    v[i] = i - 1;
+    i += 1;
+  }
+  if N > 0 {
+    v[0] = N - 1;
+  }
+  v
+}
+
+/// Returns pseudorandom elements in `0..bound`.
+///
+/// The same `seed` always produces the same array. The elements are generated with the 32 bit
+/// xorshift generator from the "Xorshift RNGs" paper by George Marsaglia.
+///
+/// # Panics
+///
+/// Panics if `seed` or `bound` is 0.
+#[must_use]
+pub const fn random<const N: usize>(seed: u32, bound: usize) -> [usize; N] {
+  assert!(seed != 0, "xorshift seed must not be 0");
+  assert!(bound != 0, "random bound must not be 0");
+  let mut random = seed;
+  let mut v = [0; N];
+  let mut i = 0;
+  while i < N {
+    random ^= random << 13;
+    random ^= random >> 17;
+    random ^= random << 5;
+    v[i] = random as usize % bound;
+    i += 1;
+  }
+  v
+}
+
+/// Returns a permutation of `0..N` on which quicksort with a median-of-three pivot degrades to
+/// quadratic time.
+///
+/// This is the sequence from "Introspective Sorting and Selection Algorithms" by David Musser.
+/// If `N` is not a multiple of 4, the last `N % 4` elements are appended in ascending order.
+#[must_use]
+pub const fn median_of_3_killer<const N: usize>() -> [usize; N] {
+  let mut v = [0; N];
+  let len = N - N % 4;
+  let k = len / 2;
+  // for i in 1..=k {
+  let mut i = 1;
+  while i <= k {
+    if i % 2 == 1 {
+      v[i - 1] = i - 1;
+      v[i] = k + i - 1;
+    }
+    v[k + i - 1] = 2 * i - 1;
+    i += 1;
+  }
+  let mut i = len;
+  while i < N {
+    v[i] = i;
+    i += 1;
+  }
+  v
+}
@@ -0,0 +1,127 @@
+use core::cmp::Ordering;
+
+/// A value paired with its original position that breaks ties by that position.
+///
+/// Values compare first by their [`value`](Self::value) and then by their
+/// [`index`](Self::index), so no two elements with distinct indices compare equal. Sorting such
+/// elements with an unstable sort therefore gives the same order as a stable sort of the values.
+/// This is the way to get a stable sort out of
+/// [`const_sort_unstable`](crate::ConstSliceSortExt::const_sort_unstable):
+/// [`attach_indices`](Self::attach_indices), sort, [`detach_indices`](Self::detach_indices).
+///
+/// Unlike [`StabilityProbe`](crate::StabilityProbe), which compares only by value to detect
+/// unstable sorts, `WithIndex` makes the sort order unambiguous.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort::{ConstSliceSortExt, WithIndex};
+///
+/// const SORTED: [(u8, char); 4] = {
+///   let v = [(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')];
+///   let mut keys = [0; 4];
+///   let mut i = 0;
+///   while i < v.len() {
+///     keys[i] = v[i].0;
+///     i += 1;
+///   }
+///   let mut with_index = [WithIndex::new(0, 0); 4];
+///   WithIndex::attach_indices(&keys, &mut with_index);
+///   with_index.const_sort_unstable();
+///   let mut sorted = v;
+///   let mut i = 0;
+///   while i < v.len() {
+///     sorted[i] = v[with_index[i].index];
+///     i += 1;
+///   }
+///   sorted
+/// };
+/// assert_eq!(SORTED, [(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct WithIndex<T> {
+  /// The value that is compared first.
+  pub value: T,
+  /// The original position of the value, which breaks ties.
+  pub index: usize,
+}
+
+impl<T> WithIndex<T> {
+  /// Pairs `value` with `index`.
+  #[must_use]
+  pub const fn new(value: T, index: usize) -> Self {
+    Self { value, index }
+  }
+
+  /// Pairs every element of `values` with its index and writes the pairs to the front of `out`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `out` is shorter than `values`.
+  pub const fn attach_indices(values: &[T], out: &mut [Self])
+  where
+    T: Copy,
+  {
+    assert!(out.len() >= values.len(), "output buffer is too short");
+    let mut i = 0;
+    while i < values.len() {
+      out[i] = Self::new(values[i], i);
+      i += 1;
+    }
+  }
+
+  /// Writes the values of `pairs` to the front of `out`, dropping their indices.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `out` is shorter than `pairs`.
+  pub const fn detach_indices(pairs: &[Self], out: &mut [T])
+  where
+    T: Copy,
+  {
+    assert!(out.len() >= pairs.len(), "output buffer is too short");
+    let mut i = 0;
+    while i < pairs.len() {
+      out[i] = pairs[i].value;
+      i += 1;
+    }
+  }
+}
+
+impl<T> const PartialEq for WithIndex<T>
+where
+  T: ~const PartialEq,
+{
+  fn eq(&self, other: &Self) -> bool {
+    self.index == other.index && self.value == other.value
+  }
+}
+
+impl<T> Eq for WithIndex<T> where T: Eq {}
+
+impl<T> const PartialOrd for WithIndex<T>
+where
+  T: ~const PartialOrd + ~const Ord,
+{
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+
+  fn lt(&self, other: &Self) -> bool {
+    self.value.lt(&other.value) || (!other.value.lt(&self.value) && self.index < other.index)
+  }
+}
+
+impl<T> const Ord for WithIndex<T>
+where
+  T: ~const PartialOrd + ~const Ord,
+{
+  fn cmp(&self, other: &Self) -> Ordering {
+    match self.value.cmp(&other.value) {
+      Ordering::Equal => self.index.cmp(&other.index),
+      ordering => ordering,
+    }
+  }
+}
@@ -0,0 +1,104 @@
+#[cfg(not(feature = "trace"))]
+use core::marker::PhantomData;
+
+/// A major decision of `const_quicksort_traced`.
+///
+/// Indices are positions in the slice passed to `const_quicksort_traced`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TraceEvent {
+  /// The subslice was partitioned around a pivot, which ended up at index `pivot`. The `left`
+  /// elements before it are less than the pivot and the `right` elements after it are greater
+  /// than or equal to it.
+  Partition {
+    /// The final index of the pivot.
+    pivot: usize,
+    /// The length of the subslice before the pivot.
+    left: usize,
+    /// The length of the subslice after the pivot.
+    right: usize,
+  },
+  /// The `equal` elements starting at index `start` were equal to the pivot of the enclosing
+  /// partition and moved to the front of their subslice, which is sorted with them.
+  PartitionEqual {
+    /// The index of the first element of the subslice.
+    start: usize,
+    /// The number of elements equal to the enclosing pivot.
+    equal: usize,
+  },
+  /// The `len` elements starting at index `start` looked sorted and were sorted by moving a few
+  /// out of order elements.
+  Presorted {
+    /// The index of the first element of the subslice.
+    start: usize,
+    /// The length of the subslice.
+    len: usize,
+  },
+  /// The `len` elements starting at index `start` were shuffled after an imbalanced partition.
+  BreakPatterns {
+    /// The index of the first element of the subslice.
+    start: usize,
+    /// The length of the subslice.
+    len: usize,
+  },
+  /// The `len` elements starting at index `start` were sorted with heapsort after too many
+  /// imbalanced partitions.
+  HeapsortFallback {
+    /// The index of the first element of the subslice.
+    start: usize,
+    /// The length of the subslice.
+    len: usize,
+  },
+}
+
+/// Records [`TraceEvent`]s into a caller-supplied buffer.
+///
+/// Without the `trace` feature, this is a zero-sized type whose [`record`](Self::record) does
+/// nothing, so untraced sorts pay nothing for it.
+pub struct Trace<'t> {
+  #[cfg(feature = "trace")]
+  buffer: &'t mut [Option<TraceEvent>],
+  #[cfg(feature = "trace")]
+  events: usize,
+  #[cfg(not(feature = "trace"))]
+  buffer: PhantomData<&'t mut [Option<TraceEvent>]>,
+}
+
+impl<'t> Trace<'t> {
+  /// Returns a trace that does not record any events.
+  pub const fn disabled() -> Self {
+    Self {
+      #[cfg(feature = "trace")]
+      buffer: &mut [],
+      #[cfg(feature = "trace")]
+      events: 0,
+      #[cfg(not(feature = "trace"))]
+      buffer: PhantomData,
+    }
+  }
+
+  /// Returns a trace that records into `buffer`.
+  #[cfg(feature = "trace")]
+  pub const fn new(buffer: &'t mut [Option<TraceEvent>]) -> Self {
+    Self { buffer, events: 0 }
+  }
+
+  /// Writes `event` to the buffer if it is not full yet, and counts it either way.
+  #[cfg(feature = "trace")]
+  pub const fn record(&mut self, event: TraceEvent) {
+    if self.events < self.buffer.len() {
+      self.buffer[self.events] = Some(event);
+    }
+    self.events += 1;
+  }
+
+  /// Does nothing, as tracing is disabled.
+  #[cfg(not(feature = "trace"))]
+  #[allow(clippy::unused_self)]
+  pub const fn record(&mut self, _event: TraceEvent) {}
+
+  /// Returns the number of recorded events, including those that did not fit into the buffer.
+  #[cfg(feature = "trace")]
+  pub const fn events(&self) -> usize {
+    self.events
+  }
+}
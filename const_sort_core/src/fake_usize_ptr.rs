@@ -7,14 +7,17 @@ impl FakeUsizePtr {
   pub const fn null_mut() -> Self {
     Self(0)
   }
-  // pub const fn offset(self, count: isize) -> Self {
-  //   Self(self.0.checked_add_signed(count).unwrap())
-  // }
+  // `add` and `sub` are called in the innermost loop of `partition_in_blocks`, so they use plain
+  // arithmetic instead of checked operations with an `unwrap`. The offsets always stay within
+  // the partitioned slice or the offset buffers. Overflows are still caught by the arithmetic
+  // overflow checks of debug builds and const evaluation, and out of bounds offsets by indexing.
   pub const fn add(self, count: usize) -> Self {
-    Self(self.0.checked_add(count).unwrap())
+    debug_assert!(count <= usize::MAX - self.0, "fake pointer overflow");
+    Self(self.0 + count)
   }
   pub const fn sub(self, count: usize) -> Self {
-    Self(self.0.checked_sub(count).unwrap())
+    debug_assert!(count <= self.0, "fake pointer underflow");
+    Self(self.0 - count)
   }
   pub const fn addr(self) -> usize {
     self.0
@@ -0,0 +1,2676 @@
+// https://doc.rust-lang.org/src/core/slice/sort.rs.html
+
+//! Slice sorting
+//!
+//! This crate contains a sorting algorithm based on Orson Peters' pattern-defeating quicksort,
+//! published at: <https://github.com/orlp/pdqsort>
+//!
+//! Unstable sorting is compatible with libcore because it doesn't allocate memory, unlike our
+//! stable sorting implementation.
+//!
+//! All algorithms are plain const functions taking an `is_less` comparator. The
+//! [`const_sort`](https://docs.rs/const_sort) crate re-exports this crate as its `const_sort`
+//! module and builds the `ConstSliceSortExt` extension trait on top of it. Depend on this crate
+//! directly to avoid the additional feature gates required by the extension trait.
+//!
+//! # Panicking comparators
+//!
+//! Comparators and key functions may panic, e.g. to reject invalid table entries while sorting
+//! them. During const evaluation, such a panic aborts the evaluation of the whole const item
+//! with a compile error that shows the panic message. The const item never gets a value, so no
+//! partially sorted state can be observed. At runtime, the panic unwinds out of the sort
+//! function. The slice then still contains every one of its original elements exactly once, but
+//! in an unspecified order.
+
+#![no_std]
+#![deny(missing_docs)]
+#![deny(unsafe_op_in_unsafe_fn)]
+#![warn(clippy::undocumented_unsafe_blocks, clippy::pedantic, clippy::nursery)]
+#![allow(
+  clippy::items_after_statements,
+  clippy::undocumented_unsafe_blocks,
+  clippy::identity_op,
+  clippy::unnecessary_mut_passed,
+  clippy::too_many_lines,
+  clippy::doc_markdown,
+  clippy::cognitive_complexity,
+  clippy::cast_possible_truncation
+)]
+#![allow(incomplete_features)] // const_closures
+#![feature(const_refs_to_cell)]
+#![feature(const_trait_impl)]
+#![feature(const_num_from_num)]
+#![feature(const_option)]
+#![feature(const_mut_refs)]
+#![feature(const_swap)]
+#![feature(maybe_uninit_slice)]
+#![feature(strict_provenance)]
+#![feature(const_ptr_read)]
+#![feature(const_deref)]
+#![feature(const_reverse)]
+#![feature(const_slice_split_at_mut)]
+#![feature(const_maybe_uninit_write)]
+#![feature(core_intrinsics)]
+#![feature(const_eval_select)]
+#![feature(const_closures)]
+#![feature(const_slice_index)]
+#![feature(const_cmp)]
+
+#[cfg(all(feature = "force-heapsort", feature = "force-insertion"))]
+compile_error!("the `force-heapsort` and `force-insertion` features are mutually exclusive");
+
+use core::cmp::{self};
+use core::intrinsics::const_eval_select;
+use core::marker::Destruct;
+use core::mem::{self, MaybeUninit};
+use core::ptr;
+
+mod fake_usize_ptr;
+use fake_usize_ptr::FakeUsizePtr;
+
+mod decisions;
+use decisions::Trace;
+#[cfg(feature = "trace")]
+pub use decisions::TraceEvent;
+#[cfg(not(feature = "trace"))]
+use decisions::TraceEvent;
+
+/// Returns `true` if slices of `T` are sorted by doing nothing.
+///
+/// Sorting has no meaningful behaviour on zero-sized types, so every sort returns early for them.
+const fn sorts_trivially<T>() -> bool {
+  mem::size_of::<T>() == 0
+}
+
+/// When dropped, copies from `src` into `dest`.
+struct CopyOnDrop<T> {
+  src: *const T,
+  dest: *mut T,
+}
+
+impl<T> const Drop for CopyOnDrop<T> {
+  fn drop(&mut self) {
+    // SAFETY:  This is a helper class.
+    //          Please refer to its usage for correctness.
+    //          Namely, one must be sure that `src` and `dst` does not overlap as required by `ptr::copy_nonoverlapping`.
+    unsafe {
+      ptr::copy_nonoverlapping(self.src, self.dest, 1);
+    }
+  }
+}
+
+/// Shifts the first element to the right until it encounters a greater or equal element.
+const fn shift_head<T, F>(v: &mut [T], is_less: &mut F)
+where
+  F: ~const FnMut(&T, &T) -> bool,
+{
+  let len = v.len();
+  // SAFETY: The unsafe operations below involves indexing without a bounds check (by offsetting a
+  // pointer) and copying memory (`ptr::copy_nonoverlapping`).
+  //
+  // a. Indexing:
+  //  1. We checked the size of the array to >=2.
+  //  2. All the indexing that we will do is always between {0 <= index < len} at most.
+  //
+  // b. Memory copying
+  //  1. We are obtaining pointers to references which are guaranteed to be valid.
+  //  2. They cannot overlap because we obtain pointers to difference indices of the slice.
+  //     Namely, `i` and `i-1`.
+  //  3. If the slice is properly aligned, the elements are properly aligned.
+  //     It is the caller's responsibility to make sure the slice is properly aligned.
+  //
+  // See comments below for further detail.
+  unsafe {
+    // If the first two elements are out-of-order...
+    if len >= 2 && is_less(v.get_unchecked(1), v.get_unchecked(0)) {
+      // Read the first element into a stack-allocated variable. If a following comparison
+      // operation panics, `hole` will get dropped and automatically write the element back
+      // into the slice.
+      let tmp = mem::ManuallyDrop::new(ptr::read(v.get_unchecked(0)));
+      let v = v.as_mut_ptr();
+      let mut hole = CopyOnDrop {
+        src: &*tmp,
+        dest: v.add(1),
+      };
+      ptr::copy_nonoverlapping(v.add(1), v.add(0), 1);
+
+      // for i in 2..len {
+      let mut i = 2;
+      while i < len {
+        if !is_less(&*v.add(i), &*tmp) {
+          break;
+        }
+
+        // Move `i`-th element one place to the left, thus shifting the hole to the right.
+        ptr::copy_nonoverlapping(v.add(i), v.add(i - 1), 1);
+        hole.dest = v.add(i);
+        i += 1;
+      }
+      // `hole` gets dropped and thus copies `tmp` into the remaining hole in `v`.
+    }
+  }
+}
+
+/// Shifts the last element to the left until it encounters a smaller or equal element.
+const fn shift_tail<T, F>(v: &mut [T], is_less: &mut F)
+where
+  F: ~const FnMut(&T, &T) -> bool,
+{
+  let len = v.len();
+  // SAFETY: The unsafe operations below involves indexing without a bound check (by offsetting a
+  // pointer) and copying memory (`ptr::copy_nonoverlapping`).
+  //
+  // a. Indexing:
+  //  1. We checked the size of the array to >= 2.
+  //  2. All the indexing that we will do is always between `0 <= index < len-1` at most.
+  //
+  // b. Memory copying
+  //  1. We are obtaining pointers to references which are guaranteed to be valid.
+  //  2. They cannot overlap because we obtain pointers to difference indices of the slice.
+  //     Namely, `i` and `i+1`.
+  //  3. If the slice is properly aligned, the elements are properly aligned.
+  //     It is the caller's responsibility to make sure the slice is properly aligned.
+  //
+  // See comments below for further detail.
+  unsafe {
+    // If the last two elements are out-of-order...
+    if len >= 2 && is_less(v.get_unchecked(len - 1), v.get_unchecked(len - 2)) {
+      // Read the last element into a stack-allocated variable. If a following comparison
+      // operation panics, `hole` will get dropped and automatically write the element back
+      // into the slice.
+      let tmp = mem::ManuallyDrop::new(ptr::read(v.get_unchecked(len - 1)));
+      let v = v.as_mut_ptr();
+      let mut hole = CopyOnDrop {
+        src: &*tmp,
+        dest: v.add(len - 2),
+      };
+      ptr::copy_nonoverlapping(v.add(len - 2), v.add(len - 1), 1);
+
+      // for i in (0..len - 2).rev() {
+      let mut i = len - 2;
+      while i > 0 {
+        i -= 1;
+        if !is_less(&*tmp, &*v.add(i)) {
+          break;
+        }
+
+        // Move `i`-th element one place to the right, thus shifting the hole to the left.
+        ptr::copy_nonoverlapping(v.add(i), v.add(i + 1), 1);
+        hole.dest = v.add(i);
+      }
+      // `hole` gets dropped and thus copies `tmp` into the remaining hole in `v`.
+    }
+  }
+}
+
+/// Partially sorts a slice by shifting several out-of-order elements around.
+///
+/// Returns `true` if the slice is sorted at the end. This function is *O*(*n*) worst-case.
+#[cold]
+const fn partial_insertion_sort<T, F>(v: &mut [T], is_less: &mut F) -> bool
+where
+  F: ~const FnMut(&T, &T) -> bool,
+{
+  // Maximum number of adjacent out-of-order pairs that will get shifted.
+  const MAX_STEPS: usize = 5;
+  // If the slice is shorter than this, don't shift any elements.
+  const SHORTEST_SHIFTING: usize = 50;
+
+  let len = v.len();
+  let mut i = 1;
+
+  // for _ in 0..MAX_STEPS {
+  let mut oi = 0;
+  while oi < MAX_STEPS {
+    // SAFETY: We already explicitly did the bound checking with `i < len`.
+    // All our subsequent indexing is only in the range `0 <= index < len`
+    unsafe {
+      // Find the next pair of adjacent out-of-order elements.
+      while i < len && !is_less(v.get_unchecked(i), v.get_unchecked(i - 1)) {
+        i += 1;
+      }
+    }
+
+    // Are we done?
+    if i == len {
+      return true;
+    }
+
+    // Don't shift elements on short arrays, that has a performance cost.
+    if len < SHORTEST_SHIFTING {
+      return false;
+    }
+
+    // Swap the found pair of elements. This puts them in correct order.
+    v.swap(i - 1, i);
+
+    // Shift the smaller element to the left.
+    shift_tail(&mut v[..i], is_less);
+    // Shift the greater element to the right.
+    shift_head(&mut v[i..], is_less);
+
+    oi += 1;
+  }
+
+  // Didn't manage to sort the slice in the limited number of steps.
+  false
+}
+
+/// Sorts a slice using insertion sort, which is *O*(*n*^2) worst-case.
+const fn insertion_sort<T, F>(v: &mut [T], is_less: &mut F)
+where
+  F: ~const FnMut(&T, &T) -> bool,
+{
+  // for i in 1..v.len() {
+  let mut i = 1;
+  while i < v.len() {
+    shift_tail(&mut v[..=i], is_less);
+    i += 1;
+  }
+}
+
+/// Sorts `v` using heapsort, which guarantees *O*(*n* \* log(*n*)) worst-case.
+///
+/// Constified version of `core::slice::heapsort`.
+///
+/// Note: Unstable sort.
+#[cold]
+pub const fn const_heapsort<T, F>(v: &mut [T], mut is_less: F)
+where
+  F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+{
+  /// This binary heap respects the invariant `parent >= child`.
+  const fn sift_down<T, F>(v: &mut [T], mut node: usize, is_less: &mut F)
+  where
+    F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+  {
+    loop {
+      // Checking `node` before computing its children keeps `2 * node + 1` from overflowing on
+      // targets with a 16 bit `usize`, where slices of zero-sized types can be `usize::MAX` long.
+      if node >= v.len() / 2 {
+        break;
+      }
+      // Children of `node`.
+      let mut child = 2 * node + 1;
+
+      // Choose the greater child.
+      if child + 1 < v.len() && is_less(&v[child], &v[child + 1]) {
+        child += 1;
+      }
+
+      // Stop if the invariant holds at `node`.
+      if !is_less(&v[node], &v[child]) {
+        break;
+      }
+
+      // Swap `node` with the greater child, move one step down, and continue sifting.
+      v.swap(node, child);
+      node = child;
+    }
+  }
+  if sorts_trivially::<T>() {
+    return;
+  }
+  // Build the heap in linear time.
+  // for i in (0..v.len() / 2).rev() {
+  let mut i = v.len() / 2;
+  while i > 0 {
+    i -= 1;
+    sift_down(v, i, &mut is_less);
+  }
+
+  // Pop maximal elements from the heap.
+  // for i in (1..v.len()).rev() {
+  let mut i = v.len();
+  while i > 1 {
+    i -= 1;
+    v.swap(0, i);
+    sift_down(&mut v[..i], 0, &mut is_less);
+  }
+}
+
+/// Sorts `v` using binary insertion sort, which is *O*(*n* \* log(*n*)) comparisons and
+/// *O*(*n*^2) moves worst-case.
+///
+/// The insertion position of every element is found with a binary search, so this performs far
+/// fewer comparisons than a plain insertion sort. Prefer it over [`const_quicksort`] for small to
+/// medium slices whose comparator is expensive, e.g. when comparing strings.
+///
+/// Note: Stable sort.
+pub const fn const_binary_insertion_sort<T, F>(v: &mut [T], mut is_less: F)
+where
+  F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+{
+  if sorts_trivially::<T>() {
+    return;
+  }
+  let len = v.len();
+  // for i in 1..len {
+  let mut i = 1;
+  while i < len {
+    binary_insert_tail(&mut v[..=i], &mut is_less);
+    i += 1;
+  }
+}
+
+/// Moves the last element of `v` into the sorted prefix `v[..v.len() - 1]` and returns its new
+/// index.
+///
+/// The position is found with a binary search after all elements that are not greater, so equal
+/// elements keep their order.
+const fn binary_insert_tail<T, F>(v: &mut [T], is_less: &mut F) -> usize
+where
+  F: ~const FnMut(&T, &T) -> bool,
+{
+  let i = v.len() - 1;
+  // Find the first element greater than `v[i]`, so equal elements keep their order.
+  let mut low = 0;
+  let mut high = i;
+  while low < high {
+    let mid = low + (high - low) / 2;
+    if is_less(&v[i], &v[mid]) {
+      high = mid;
+    } else {
+      low = mid + 1;
+    }
+  }
+
+  if low < i {
+    // SAFETY: `low < i < v.len()`, so all offsets are in bounds. `v[i]` is read into `tmp` before
+    // its slot is overwritten. The elements in `low..i` are then moved one place to the right
+    // and `tmp` is written into the hole left at `low`. No comparison happens while the hole is
+    // open, so it is always filled.
+    unsafe {
+      let v = v.as_mut_ptr();
+      let tmp = mem::ManuallyDrop::new(ptr::read(v.add(i)));
+      let mut j = i;
+      while j > low {
+        ptr::copy_nonoverlapping(v.add(j - 1), v.add(j), 1);
+        j -= 1;
+      }
+      ptr::copy_nonoverlapping(&*tmp, v.add(low), 1);
+    }
+  }
+  low
+}
+
+/// Inserts `x` into the sorted prefix `buf[..len]`, such that `buf[..=len]` is sorted, and
+/// returns the index of `x`.
+///
+/// `x` is inserted after all elements that are not greater than it, so inserting elements one by
+/// one keeps equal elements in insertion order. The element at `buf[len]` is overwritten. Use
+/// this to maintain sorted buffers, e.g. the `k` smallest elements seen so far, without
+/// re-sorting them after every insertion.
+///
+/// Note: *O*(log(*len*)) comparisons and *O*(*len*) moves.
+///
+/// # Panics
+///
+/// Panics if `len >= buf.len()`.
+pub const fn const_sorted_insert_into_prefix<T, F>(
+  buf: &mut [T],
+  len: usize,
+  x: T,
+  mut is_less: F,
+) -> usize
+where
+  T: Copy,
+  F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+{
+  assert!(len < buf.len(), "sorted prefix fills the whole buffer");
+  buf[len] = x;
+  binary_insert_tail(&mut buf[..=len], &mut is_less)
+}
+
+/// Sorts `v` using selection sort, which is *O*(*n*^2) comparisons and at most *n* - 1 swaps.
+///
+/// Every position is swapped at most once, with the smallest of the remaining elements. Prefer
+/// it over [`const_quicksort`] for small slices of large elements whose comparisons are cheap, as
+/// moving the elements dominates the cost of sorting them.
+///
+/// Note: Unstable sort.
+pub const fn const_selection_sort<T, F>(v: &mut [T], mut is_less: F)
+where
+  F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+{
+  if sorts_trivially::<T>() {
+    return;
+  }
+  let len = v.len();
+  // for i in 0..len.saturating_sub(1) {
+  let mut i = 0;
+  while i + 1 < len {
+    let mut min = i;
+    let mut j = i + 1;
+    while j < len {
+      if is_less(&v[j], &v[min]) {
+        min = j;
+      }
+      j += 1;
+    }
+    if min != i {
+      v.swap(i, min);
+    }
+    i += 1;
+  }
+}
+
+/// Sorts `v` using cocktail shaker sort, which is *O*(*n*^2) worst-case and *O*(*n*) on sorted
+/// slices.
+///
+/// Adjacent elements are swapped in alternating forward and backward passes until a pass finds
+/// no elements out of order. Each pass only covers the range between the last swaps of the
+/// previous passes, so nearly sorted slices are sorted in a few short passes. The loop is tiny,
+/// which makes it cheap to evaluate for the many small, nearly sorted arrays where
+/// [`const_quicksort`] spends most of its time on setup.
+///
+/// Note: Stable sort.
+pub const fn const_cocktail_sort<T, F>(v: &mut [T], mut is_less: F)
+where
+  F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+{
+  if sorts_trivially::<T>() {
+    return;
+  }
+  // `v[..start]` and `v[end..]` are sorted and in their final place.
+  let mut start = 0;
+  let mut end = v.len();
+  while start + 1 < end {
+    let mut last_swap = start;
+    let mut i = start + 1;
+    while i < end {
+      if is_less(&v[i], &v[i - 1]) {
+        v.swap(i - 1, i);
+        last_swap = i;
+      }
+      i += 1;
+    }
+    end = last_swap;
+
+    let mut first_swap = end;
+    let mut i = end;
+    while i > start + 1 {
+      i -= 1;
+      if is_less(&v[i], &v[i - 1]) {
+        v.swap(i - 1, i);
+        first_swap = i;
+      }
+    }
+    start = first_swap;
+  }
+}
+
+/// Sorts `v` using an in-place merge sort, which is *O*(*n* \* log(*n*)) comparisons and
+/// *O*(*n* \* log(*n*)^2) moves worst-case.
+///
+/// Runs of up to 20 elements are insertion sorted and then merged with the SymMerge algorithm by
+/// Kim and Kutzner, which merges by rotating blocks instead of copying into a scratch buffer. Use
+/// it when equal elements must keep their order, e.g. when sorting `(key, payload)` pairs by
+/// their key only.
+///
+/// Note: Stable sort.
+pub const fn const_merge_sort<T, F>(v: &mut [T], mut is_less: F)
+where
+  F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+{
+  if sorts_trivially::<T>() {
+    return;
+  }
+  merge_sort(v, &mut is_less);
+}
+
+const fn merge_sort<T, F>(v: &mut [T], is_less: &mut F)
+where
+  F: ~const FnMut(&T, &T) -> bool,
+{
+  // Slices of up to this length get sorted using insertion sort.
+  const MAX_INSERTION: usize = 20;
+
+  let len = v.len();
+  if len <= MAX_INSERTION {
+    insertion_sort(v, is_less);
+    return;
+  }
+  let mid = len / 2;
+  merge_sort(&mut v[..mid], is_less);
+  merge_sort(&mut v[mid..], is_less);
+  // The halves are already in order if the first element of the right half is not less than the
+  // last element of the left half.
+  if is_less(&v[mid], &v[mid - 1]) {
+    sym_merge(v, 0, mid, len, is_less);
+  }
+}
+
+/// Sorts `v` using a natural merge sort, which is *O*(*n*) on slices made of few sorted runs and
+/// *O*(*n* \* log(*n*)) comparisons and *O*(*n* \* log(*n*)^2) moves worst-case.
+///
+/// Like timsort, the slice is first scanned for runs that are already in order, strictly
+/// descending runs are reversed and runs shorter than 20 elements are extended with insertion
+/// sort. Neighbouring runs are then merged in place in passes that halve the number of runs,
+/// like in [`const_merge_sort`]. Prefer it over [`const_quicksort`] for concatenations of sorted
+/// segments, e.g. tables assembled from several sorted sources, where it needs about *n* \*
+/// log(*r*) comparisons for *r* runs.
+///
+/// Note: Stable sort.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort_core::const_sort_adaptive;
+///
+/// const V: [u8; 8] = {
+///   let mut v = [10, 20, 30, 40, 1, 2, 3, 4];
+///   const_sort_adaptive(&mut v, PartialOrd::lt);
+///   v
+/// };
+/// assert_eq!(V, [1, 2, 3, 4, 10, 20, 30, 40]);
+/// ```
+pub const fn const_sort_adaptive<T, F>(v: &mut [T], mut is_less: F)
+where
+  F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+{
+  // Runs shorter than this are extended to this length using insertion sort.
+  const MIN_RUN: usize = 20;
+
+  if sorts_trivially::<T>() {
+    return;
+  }
+  let len = v.len();
+
+  // Turn the slice into ascending runs of at least `MIN_RUN` elements, except for the last one.
+  let mut start = 0;
+  while start < len {
+    let mut end = start + 1;
+    if end < len && is_less(&v[end], &v[start]) {
+      // Only strictly descending runs are reversed, so equal elements keep their order.
+      while end < len && is_less(&v[end], &v[end - 1]) {
+        end += 1;
+      }
+      v[start..end].reverse();
+    } else {
+      end = run_end(v, start, &mut is_less);
+    }
+    let min_end = if len - start < MIN_RUN {
+      len
+    } else {
+      start + MIN_RUN
+    };
+    while end < min_end {
+      shift_tail(&mut v[start..=end], &mut is_less);
+      end += 1;
+    }
+    start = end;
+  }
+
+  // Merge pairs of neighbouring runs until a single run is left.
+  loop {
+    let mut merged = false;
+    let mut start = 0;
+    while start < len {
+      let mid = run_end(v, start, &mut is_less);
+      if mid == len {
+        break;
+      }
+      let end = run_end(v, mid, &mut is_less);
+      sym_merge(
+        &mut v[start..end],
+        0,
+        mid - start,
+        end - start,
+        &mut is_less,
+      );
+      merged = true;
+      start = end;
+    }
+    if !merged {
+      break;
+    }
+  }
+}
+
+/// Returns the end of the ascending run of `v` that starts at `start`.
+const fn run_end<T, F>(v: &[T], start: usize, is_less: &mut F) -> usize
+where
+  F: ~const FnMut(&T, &T) -> bool,
+{
+  let mut end = start + 1;
+  while end < v.len() && !is_less(&v[end], &v[end - 1]) {
+    end += 1;
+  }
+  end
+}
+
+/// Merges the sorted ranges `v[lo..split]` and `v[split..hi]` in place, such that `v[lo..hi]` is
+/// sorted.
+///
+/// Equal elements of `v[lo..split]` are kept before those of `v[split..hi]`. Both ranges must
+/// not be empty.
+const fn sym_merge<T, F>(v: &mut [T], lo: usize, split: usize, hi: usize, is_less: &mut F)
+where
+  F: ~const FnMut(&T, &T) -> bool,
+{
+  if split - lo == 1 {
+    // Move `v[lo]` in front of the first element of `v[split..hi]` that is not less than it.
+    let mut low = split;
+    let mut high = hi;
+    while low < high {
+      let probe = low + (high - low) / 2;
+      if is_less(&v[probe], &v[lo]) {
+        low = probe + 1;
+      } else {
+        high = probe;
+      }
+    }
+    rotate_left(&mut v[lo..low], 1);
+    return;
+  }
+  if hi - split == 1 {
+    // Move `v[split]` in front of the first element of `v[lo..split]` that is greater than it.
+    let mut low = lo;
+    let mut high = split;
+    while low < high {
+      let probe = low + (high - low) / 2;
+      if is_less(&v[split], &v[probe]) {
+        high = probe;
+      } else {
+        low = probe + 1;
+      }
+    }
+    rotate_left(&mut v[low..=split], split - low);
+    return;
+  }
+
+  // Find the range `start..end` around `split` that is symmetric around `mid`, such that
+  // rotating it splits `v[lo..hi]` into two independent merges at `mid`.
+  let mid = lo + (hi - lo) / 2;
+  let mirror = mid + split;
+  let (mut start, mut limit) = if split > mid {
+    (mirror - hi, mid)
+  } else {
+    (lo, split)
+  };
+  while start < limit {
+    let probe = start + (limit - start) / 2;
+    if is_less(&v[mirror - 1 - probe], &v[probe]) {
+      limit = probe;
+    } else {
+      start = probe + 1;
+    }
+  }
+  let end = mirror - start;
+  if start < split && split < end {
+    rotate_left(&mut v[start..end], split - start);
+  }
+  if lo < start && start < mid {
+    sym_merge(v, lo, start, mid, is_less);
+  }
+  if mid < end && end < hi {
+    sym_merge(v, mid, end, hi, is_less);
+  }
+}
+
+/// Rotates `v` in place such that the element at `mid` becomes the first element.
+const fn rotate_left<T>(v: &mut [T], mid: usize) {
+  let (left, right) = v.split_at_mut(mid);
+  left.reverse();
+  right.reverse();
+  v.reverse();
+}
+
+/// Sorts `keys` using heapsort and applies every swap to `values` as well, which guarantees
+/// *O*(*n* \* log(*n*)) worst-case.
+///
+/// Use this when the sort keys of `values` are expensive to compute: precompute them once into
+/// `keys` instead of recomputing them in every comparison. Afterwards `keys[i]` still belongs to
+/// `values[i]`. No scratch buffer is needed.
+///
+/// Note: Unstable sort.
+///
+/// # Panics
+///
+/// Panics if `values` and `keys` differ in length.
+// The name is part of the public API and `const_sort` re-exports this crate as a module.
+#[allow(clippy::module_name_repetitions)]
+pub const fn const_sort_by_external_key<T, K>(values: &mut [T], keys: &mut [K])
+where
+  K: Ord + ~const PartialOrd,
+{
+  /// This binary heap respects the invariant `parent >= child`.
+  const fn sift_down<T, K>(values: &mut [T], keys: &mut [K], mut node: usize, len: usize)
+  where
+    K: ~const PartialOrd,
+  {
+    loop {
+      // `node` has no children, checked before computing them to avoid overflow.
+      if node >= len / 2 {
+        break;
+      }
+      // Children of `node`.
+      let mut child = 2 * node + 1;
+
+      // Choose the greater child.
+      if child + 1 < len && keys[child].lt(&keys[child + 1]) {
+        child += 1;
+      }
+
+      // Stop if the invariant holds at `node`.
+      if !keys[node].lt(&keys[child]) {
+        break;
+      }
+
+      // Swap `node` with the greater child, move one step down, and continue sifting.
+      keys.swap(node, child);
+      values.swap(node, child);
+      node = child;
+    }
+  }
+
+  assert!(
+    values.len() == keys.len(),
+    "values and keys must have the same length"
+  );
+  let len = keys.len();
+
+  // Build the heap in linear time.
+  let mut i = len / 2;
+  while i > 0 {
+    i -= 1;
+    sift_down(values, keys, i, len);
+  }
+
+  // Pop maximal elements from the heap.
+  let mut i = len;
+  while i > 1 {
+    i -= 1;
+    keys.swap(0, i);
+    values.swap(0, i);
+    sift_down(values, keys, 0, i);
+  }
+}
+
+/// Sorts `values` by the weights looked up in `weights`, in ascending order.
+///
+/// `weight_of` maps every value to an index into `weights`, e.g. an opcode to its position in a
+/// table of opcode frequencies, and values are ordered by the weight at that index. This saves
+/// writing the table lookup into a comparator by hand. Sort by `u32::MAX - weight` or reverse the
+/// slice afterwards to get the heaviest values first.
+///
+/// Note: Unstable sort, *O*(*n* \* log(*n*)) worst-case.
+///
+/// # Panics
+///
+/// Panics if `weight_of` returns an index that is out of bounds of `weights`.
+pub const fn const_sort_by_weight_table<T, F>(values: &mut [T], mut weight_of: F, weights: &[u32])
+where
+  F: ~const FnMut(&T) -> usize + ~const Destruct,
+{
+  const_quicksort(values, const |a: &T, b: &T| {
+    weights[weight_of(a)] < weights[weight_of(b)]
+  });
+}
+
+/// Sorts `values` by the strings of their interned ids, in ascending lexicographic byte order.
+///
+/// `id_of` maps every value to its id, i.e. an index into `strings`, so tables keyed by interned
+/// symbols are sorted by name without storing the names in the values. If `strings` is sorted
+/// itself, e.g. because the interner assigns ids in name order, the ids are compared instead of
+/// the strings they name.
+///
+/// Note: Unstable sort, *O*(*n* \* log(*n*)) comparisons worst-case.
+///
+/// # Panics
+///
+/// Panics if `id_of` returns an index that is out of bounds of `strings`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort_core::const_sort_by_interned;
+///
+/// const SYMBOLS: [&str; 3] = ["width", "align", "height"];
+///
+/// const fn symbol(field: &(usize, u32)) -> usize {
+///   field.0
+/// }
+///
+/// const FIELDS: [(usize, u32); 3] = {
+///   let mut fields = [(0, 640), (1, 8), (2, 480)];
+///   const_sort_by_interned(&mut fields, symbol, &SYMBOLS);
+///   fields
+/// };
+/// assert_eq!(FIELDS, [(1, 8), (2, 480), (0, 640)]);
+/// ```
+pub const fn const_sort_by_interned<T, F>(values: &mut [T], mut id_of: F, strings: &[&str])
+where
+  F: ~const FnMut(&T) -> usize + ~const Destruct,
+{
+  let mut i = 0;
+  while i < values.len() {
+    assert!(
+      id_of(&values[i]) < strings.len(),
+      "interned id is out of bounds"
+    );
+    i += 1;
+  }
+
+  let mut sorted = true;
+  let mut i = 1;
+  while sorted && i < strings.len() {
+    sorted = !str_lt(strings[i], strings[i - 1]);
+    i += 1;
+  }
+  if sorted {
+    const_quicksort(values, const |a: &T, b: &T| id_of(a) < id_of(b));
+  } else {
+    const_quicksort(values, const |a: &T, b: &T| {
+      str_lt(strings[id_of(a)], strings[id_of(b)])
+    });
+  }
+}
+
+/// Compares two strings lexicographically by their bytes.
+const fn str_lt(a: &str, b: &str) -> bool {
+  const fn const_lt(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut i = 0;
+    while i < a.len() && i < b.len() {
+      if a[i] != b[i] {
+        return a[i] < b[i];
+      }
+      i += 1;
+    }
+    a.len() < b.len()
+  }
+  fn rt_lt(a: &str, b: &str) -> bool {
+    a < b
+  }
+  // SAFETY: Both functions compare the strings the same way.
+  unsafe { const_eval_select((a, b), const_lt, rt_lt) }
+}
+
+/// Upper bound of the block length used by [`partition_in_blocks`].
+///
+/// Offsets within a block are stored as `u8`, so a block can not hold more than 256 elements.
+const MAX_BLOCK: usize = 256;
+
+/// Returns the number of elements in a typical block of [`partition_in_blocks`].
+///
+/// Small elements are cheap to compare and move, so tracing more of them per block amortizes the
+/// bookkeeping between blocks. Large elements use shorter blocks, so the traced elements of both
+/// blocks stay in cache.
+const fn block_len<T>() -> usize {
+  match mem::size_of::<T>() {
+    0..=8 => MAX_BLOCK,
+    9..=32 => 128,
+    _ => 64,
+  }
+}
+
+/// Partitions `v` into elements smaller than `pivot`, followed by elements greater than or equal
+/// to `pivot`.
+///
+/// Returns the number of elements smaller than `pivot`.
+///
+/// Partitioning is performed block-by-block in order to minimize the cost of branching operations.
+/// This idea is presented in the [BlockQuicksort][pdf] paper.
+///
+/// [pdf]: https://drops.dagstuhl.de/opus/volltexte/2016/6389/pdf/LIPIcs-ESA-2016-38.pdf
+const fn partition_in_blocks<T, F>(v: &mut [T], pivot: &T, is_less: &mut F) -> usize
+where
+  F: ~const FnMut(&T, &T) -> bool,
+{
+  // Number of elements in a typical block.
+  let block = block_len::<T>();
+
+  // The partitioning algorithm repeats the following steps until completion:
+  //
+  // 1. Trace a block from the left side to identify elements greater than or equal to the pivot.
+  // 2. Trace a block from the right side to identify elements smaller than the pivot.
+  // 3. Exchange the identified elements between the left and right side.
+  //
+  // We keep the following variables for a block of elements:
+  //
+  // 1. `block` - Number of elements in the block.
+  // 2. `start` - Start pointer into the `offsets` array.
+  // 3. `end` - End pointer into the `offsets` array.
+  // 4. `offsets - Indices of out-of-order elements within the block.
+
+  // The current block on the left side (from `l` to `l.add(block_l)`).
+  let mut l = FakeUsizePtr::null_mut(); // indexes v
+  let mut block_l = block;
+  let mut start_l = FakeUsizePtr::null_mut(); // indexes offsets_l
+  let mut end_l = FakeUsizePtr::null_mut(); // holds end of offsets_l
+  let mut offsets_l = [MaybeUninit::<u8>::uninit(); MAX_BLOCK];
+
+  // The current block on the right side (from `r.sub(block_r)` to `r`).
+  // SAFETY: The documentation for .add() specifically mention that `vec.as_ptr().add(vec.len())` is always safe`
+  let mut r = l.add(v.len()); // indexes v
+  let mut block_r = block;
+  let mut start_r = FakeUsizePtr::null_mut(); // indexes offsets_r
+  let mut end_r = FakeUsizePtr::null_mut(); // holds end of offsets_r
+  let mut offsets_r = [MaybeUninit::<u8>::uninit(); MAX_BLOCK];
+
+  // FIXME: When we get VLAs, try creating one array of length `min(v.len(), 2 * block)` rather
+  // than two fixed-size arrays of length `MAX_BLOCK`. VLAs might be more cache-efficient.
+
+  // Returns the number of elements between pointers `l` (inclusive) and `r` (exclusive).
+  const fn width(l: FakeUsizePtr, r: FakeUsizePtr) -> usize {
+    debug_assert!(l.addr() <= r.addr(), "partition pointers crossed");
+    r.addr() - l.addr()
+  }
+
+  loop {
+    // We are done with partitioning block-by-block when `l` and `r` get very close. Then we do
+    // some patch-up work in order to partition the remaining elements in between.
+    let is_done = width(l, r) <= 2 * block;
+
+    if is_done {
+      // Number of remaining elements (still not compared to the pivot).
+      let mut rem = width(l, r);
+      if start_l < end_l || start_r < end_r {
+        rem -= block;
+      }
+
+      // Adjust block sizes so that the left and right block don't overlap, but get perfectly
+      // aligned to cover the whole remaining gap.
+      if start_l < end_l {
+        block_r = rem;
+      } else if start_r < end_r {
+        block_l = rem;
+      } else {
+        // There were the same number of elements to switch on both blocks during the last
+        // iteration, so there are no remaining elements on either block. Cover the remaining
+        // items with roughly equally-sized blocks.
+        block_l = rem / 2;
+        block_r = rem - block_l;
+      }
+      debug_assert!(block_l <= block && block_r <= block);
+      debug_assert!(width(l, r) == block_l + block_r);
+    }
+
+    if start_l == end_l {
+      // Trace `block_l` elements from the left side.
+      start_l = FakeUsizePtr::null_mut();
+      end_l = start_l;
+      let mut elem = l;
+
+      // for i in 0..block_l {
+      let mut i = 0;
+      while i < block_l {
+        // SAFETY: The unsafe operations below involve the usage of the `offset`.
+        //         According to the conditions required by the function, we satisfy them because:
+        //         1. `offsets_l` is stack-allocated, and thus considered separate allocated object.
+        //         2. The function `is_less` returns a `bool`.
+        //            Casting a `bool` will never overflow `isize`.
+        //         3. We have guaranteed that `block_l` will be `<= block`.
+        //            Plus, `end_l` was initially set to the begin pointer of `offsets_` which was declared on the stack.
+        //            Thus, we know that even in the worst case (all invocations of `is_less` returns false) we will only be at most 1 byte pass the end.
+        //        Another unsafe operation here is dereferencing `elem`.
+        //        However, `elem` was initially the begin pointer to the slice which is always valid.
+        {
+          // Branchless comparison.
+          offsets_l[end_l].write(i as u8);
+          end_l = end_l.add(!is_less(&v[elem], pivot) as usize);
+          elem = elem.add(1);
+        }
+        i += 1;
+      }
+    }
+
+    if start_r == end_r {
+      // Trace `block_r` elements from the right side.
+      start_r = FakeUsizePtr::null_mut();
+      end_r = start_r;
+      let mut elem = r;
+
+      // for i in 0..block_r {
+      let mut i = 0;
+      while i < block_r {
+        // SAFETY: The unsafe operations below involve the usage of the `offset`.
+        //         According to the conditions required by the function, we satisfy them because:
+        //         1. `offsets_r` is stack-allocated, and thus considered separate allocated object.
+        //         2. The function `is_less` returns a `bool`.
+        //            Casting a `bool` will never overflow `isize`.
+        //         3. We have guaranteed that `block_r` will be `<= block`.
+        //            Plus, `end_r` was initially set to the begin pointer of `offsets_` which was declared on the stack.
+        //            Thus, we know that even in the worst case (all invocations of `is_less` returns true) we will only be at most 1 byte pass the end.
+        //        Another unsafe operation here is dereferencing `elem`.
+        //        However, `elem` was initially `1 * sizeof(T)` past the end and we decrement it by `1 * sizeof(T)` before accessing it.
+        //        Plus, `block_r` was asserted to be less than `block` and `elem` will therefore at most be pointing to the beginning of the slice.
+        {
+          // Branchless comparison.
+          elem = elem.sub(1);
+          offsets_r[end_r].write(i as u8);
+          end_r = end_r.add(is_less(&v[elem], pivot) as usize);
+        }
+        i += 1;
+      }
+    }
+
+    // Number of out-of-order elements to swap between the left and right side.
+    let count = cmp::min(width(start_l, end_l), width(start_r, end_r));
+
+    if count > 0 {
+      macro_rules! left {
+        () => {
+          &mut v[l.add(usize::from(offsets_l[start_l].assume_init()))]
+        };
+      }
+      macro_rules! right {
+        () => {
+          &mut v[r.sub(usize::from(offsets_r[start_r].assume_init()) + 1)]
+        };
+      }
+
+      // Instead of swapping one pair at the time, it is more efficient to perform a cyclic
+      // permutation. This is not strictly equivalent to swapping, but produces a similar
+      // result using fewer memory operations.
+
+      // SAFETY: The use of `ptr::read` is valid because there is at least one element in
+      // both `offsets_l` and `offsets_r`, so `left!` is a valid pointer to read from.
+      //
+      // The uses of `left!` involve calls to `offset` on `l`, which points to the
+      // beginning of `v`. All the offsets pointed-to by `start_l` are at most `block_l`, so
+      // these `offset` calls are safe as all reads are within the block. The same argument
+      // applies for the uses of `right!`.
+      //
+      // The calls to `start_l.offset` are valid because there are at most `count-1` of them,
+      // plus the final one at the end of the unsafe block, where `count` is the minimum number
+      // of collected offsets in `offsets_l` and `offsets_r`, so there is no risk of there not
+      // being enough elements. The same reasoning applies to the calls to `start_r.offset`.
+      //
+      // The calls to `copy_nonoverlapping` are safe because `left!` and `right!` are guaranteed
+      // not to overlap, and are valid because of the reasoning above.
+      unsafe {
+        let tmp = ptr::read(left!());
+        ptr::copy_nonoverlapping(right!(), left!(), 1);
+
+        // for _ in 1..count {
+        let mut oi = 1;
+        while oi < count {
+          start_l = start_l.add(1);
+          ptr::copy_nonoverlapping(left!(), right!(), 1);
+          start_r = start_r.add(1);
+          ptr::copy_nonoverlapping(right!(), left!(), 1);
+          oi += 1;
+        }
+
+        ptr::copy_nonoverlapping(&tmp, right!(), 1);
+        mem::forget(tmp);
+        start_l = start_l.add(1);
+        start_r = start_r.add(1);
+      }
+    }
+
+    if start_l == end_l {
+      // All out-of-order elements in the left block were moved. Move to the next block.
+
+      // block-width-guarantee
+      // SAFETY: if `!is_done` then the slice width is guaranteed to be at least `2*block` wide. There
+      // are at most `block` elements in `offsets_l` because of its size, so the `offset` operation is
+      // safe. Otherwise, the debug assertions in the `is_done` case guarantee that
+      // `width(l, r) == block_l + block_r`, namely, that the block sizes have been adjusted to account
+      // for the smaller number of remaining elements.
+      l = l.add(block_l);
+    }
+
+    if start_r == end_r {
+      // All out-of-order elements in the right block were moved. Move to the previous block.
+
+      // SAFETY: Same argument as [block-width-guarantee]. Either this is a full block `2*block`-wide,
+      // or `block_r` has been adjusted for the last handful of elements.
+      r = r.sub(block_r);
+    }
+
+    if is_done {
+      break;
+    }
+  }
+
+  // All that remains now is at most one block (either the left or the right) with out-of-order
+  // elements that need to be moved. Such remaining elements can be simply shifted to the end
+  // within their block.
+
+  if start_l < end_l {
+    // The left block remains.
+    // Move its remaining out-of-order elements to the far right.
+    debug_assert!(width(l, r) == block_l);
+    while start_l < end_l {
+      // remaining-elements-safety
+      // SAFETY: while the loop condition holds there are still elements in `offsets_l`, so it
+      // is safe to point `end_l` to the previous element.
+      //
+      // The `ptr::swap` is safe if both its arguments are valid for reads and writes:
+      //  - Per the debug assert above, the distance between `l` and `r` is `block_l`
+      //    elements, so there can be at most `block_l` remaining offsets between `start_l`
+      //    and `end_l`. This means `r` will be moved at most `block_l` steps back, which
+      //    makes the `r.offset` calls valid (at that point `l == r`).
+      //  - `offsets_l` contains valid offsets into `v` collected during the partitioning of
+      //    the last block, so the `l.offset` calls are valid.
+      unsafe {
+        end_l = end_l.sub(1);
+        v.swap(
+          l.add(usize::from(offsets_l[end_l].assume_init())).addr(),
+          r.sub(1).addr(),
+        );
+        r = r.sub(1);
+      }
+    }
+    width(FakeUsizePtr::null_mut(), r)
+  } else if start_r < end_r {
+    // The right block remains.
+    // Move its remaining out-of-order elements to the far left.
+    debug_assert!(width(l, r) == block_r);
+    while start_r < end_r {
+      // SAFETY: See the reasoning in [remaining-elements-safety].
+      unsafe {
+        end_r = end_r.sub(1);
+        v.swap(
+          l.addr(),
+          r.sub(usize::from(offsets_r[end_r].assume_init()) + 1)
+            .addr(),
+        );
+        l = l.add(1);
+      }
+    }
+    width(FakeUsizePtr::null_mut(), l)
+  } else {
+    // Nothing else to do, we're done.
+    width(FakeUsizePtr::null_mut(), l)
+  }
+}
+
+/// Partitions `v` into elements smaller than `v[pivot]`, followed by elements greater than or
+/// equal to `v[pivot]`.
+///
+/// Returns a tuple of:
+///
+/// 1. Number of elements smaller than `v[pivot]`.
+/// 2. True if `v` was already partitioned.
+const fn partition<T, F>(v: &mut [T], pivot: usize, is_less: &mut F) -> (usize, bool)
+where
+  F: ~const FnMut(&T, &T) -> bool,
+{
+  let (mid, was_partitioned) = {
+    // Place the pivot at the beginning of slice.
+    v.swap(0, pivot);
+    let (pivot, v) = v.split_at_mut(1);
+    let pivot = &mut pivot[0];
+
+    // Read the pivot into a stack-allocated variable for efficiency. If a following comparison
+    // operation panics, the pivot will be automatically written back into the slice.
+
+    // SAFETY: `pivot` is a reference to the first element of `v`, so `ptr::read` is safe.
+    let tmp = mem::ManuallyDrop::new(unsafe { ptr::read(pivot) });
+    let _pivot_guard = CopyOnDrop {
+      src: &*tmp,
+      dest: pivot,
+    };
+    let pivot = &*tmp;
+
+    // Find the first pair of out-of-order elements.
+    let mut l = 0;
+    let mut r = v.len();
+
+    // SAFETY: The unsafe below involves indexing an array.
+    // For the first one: We already do the bounds checking here with `l < r`.
+    // For the second one: We initially have `l == 0` and `r == v.len()` and we checked that `l < r` at every indexing operation.
+    //                     From here we know that `r` must be at least `r == l` which was shown to be valid from the first one.
+    unsafe {
+      // Find the first element greater than or equal to the pivot.
+      while l < r && is_less(v.get_unchecked(l), pivot) {
+        l += 1;
+      }
+
+      // Find the last element smaller that the pivot.
+      while l < r && !is_less(v.get_unchecked(r - 1), pivot) {
+        r -= 1;
+      }
+    }
+
+    (
+      l + partition_in_blocks(&mut v[l..r], pivot, is_less),
+      l >= r,
+    )
+
+    // `_pivot_guard` goes out of scope and writes the pivot (which is a stack-allocated
+    // variable) back into the slice where it originally was. This step is critical in ensuring
+    // safety!
+  };
+
+  // Place the pivot between the two partitions.
+  v.swap(0, mid);
+
+  (mid, was_partitioned)
+}
+
+/// Partitions `v` into elements equal to `v[pivot]` followed by elements greater than `v[pivot]`.
+///
+/// Returns the number of elements equal to the pivot. It is assumed that `v` does not contain
+/// elements smaller than the pivot.
+const fn partition_equal<T, F>(v: &mut [T], pivot: usize, is_less: &mut F) -> usize
+where
+  F: ~const FnMut(&T, &T) -> bool,
+{
+  // Place the pivot at the beginning of slice.
+  v.swap(0, pivot);
+  let (pivot, v) = v.split_at_mut(1);
+  let pivot = &mut pivot[0];
+
+  // Read the pivot into a stack-allocated variable for efficiency. If a following comparison
+  // operation panics, the pivot will be automatically written back into the slice.
+  // SAFETY: The pointer here is valid because it is obtained from a reference to a slice.
+  let tmp = mem::ManuallyDrop::new(unsafe { ptr::read(pivot) });
+  let _pivot_guard = CopyOnDrop {
+    src: &*tmp,
+    dest: pivot,
+  };
+  let pivot = &*tmp;
+
+  // Now partition the slice.
+  let mut l = 0;
+  let mut r = v.len();
+  loop {
+    // SAFETY: The unsafe below involves indexing an array.
+    // For the first one: We already do the bounds checking here with `l < r`.
+    // For the second one: We initially have `l == 0` and `r == v.len()` and we checked that `l < r` at every indexing operation.
+    //                     From here we know that `r` must be at least `r == l` which was shown to be valid from the first one.
+    unsafe {
+      // Find the first element greater than the pivot.
+      while l < r && !is_less(pivot, v.get_unchecked(l)) {
+        l += 1;
+      }
+
+      // Find the last element equal to the pivot.
+      while l < r && is_less(pivot, v.get_unchecked(r - 1)) {
+        r -= 1;
+      }
+
+      // Are we done?
+      if l >= r {
+        break;
+      }
+
+      // Swap the found pair of out-of-order elements.
+      r -= 1;
+      let ptr = v.as_mut_ptr();
+      ptr::swap(ptr.add(l), ptr.add(r));
+      l += 1;
+    }
+  }
+
+  // We found `l` elements equal to the pivot. Add 1 to account for the pivot itself.
+  l + 1
+
+  // `_pivot_guard` goes out of scope and writes the pivot (which is a stack-allocated variable)
+  // back into the slice where it originally was. This step is critical in ensuring safety!
+}
+
+/// Scatters some elements around in an attempt to break patterns that might cause imbalanced
+/// partitions in quicksort.
+#[cold]
+const fn break_patterns<T>(v: &mut [T]) {
+  let len = v.len();
+  if len >= 8 {
+    // Pseudorandom number generator from the "Xorshift RNGs" paper by George Marsaglia.
+    let mut random = len as u32;
+    const fn gen_u32(random: &mut u32) -> u32 {
+      *random ^= *random << 13;
+      *random ^= *random >> 17;
+      *random ^= *random << 5;
+      *random
+    }
+    const fn gen_usize(random: &mut u32) -> usize {
+      if usize::BITS <= 32 {
+        gen_u32(random) as usize
+      } else {
+        (((gen_u32(random) as u64) << 32) | (gen_u32(random) as u64)) as usize
+      }
+    }
+
+    // Take random numbers modulo this number.
+    // The number fits into `usize` because `len` is not greater than `isize::MAX`.
+    let modulus = len.next_power_of_two();
+
+    // Some pivot candidates will be in the nearby of this index. Let's randomize them.
+    let pos = len / 4 * 2;
+
+    //for i in 0..3 {
+    let mut i = 0;
+    while i < 3 {
+      // Generate a random number modulo `len`. However, in order to avoid costly operations
+      // we first take it modulo a power of two, and then decrease by `len` until it fits
+      // into the range `[0, len - 1]`.
+      let mut other = gen_usize(&mut random) & (modulus - 1);
+
+      // `other` is guaranteed to be less than `2 * len`.
+      if other >= len {
+        other -= len;
+      }
+
+      v.swap(pos - 1 + i, other);
+      i += 1;
+    }
+  }
+}
+
+/// Chooses a pivot in `v` and returns the index and `true` if the slice is likely already sorted.
+///
+/// Elements in `v` might be reordered in the process.
+const fn choose_pivot<T, F>(v: &mut [T], is_less: &mut F) -> (usize, bool)
+where
+  F: ~const FnMut(&T, &T) -> bool,
+{
+  // Minimum length to choose the median-of-medians method.
+  // Shorter slices use the simple median-of-three method.
+  const SHORTEST_MEDIAN_OF_MEDIANS: usize = 50;
+  // Maximum number of swaps that can be performed in this function.
+  const MAX_SWAPS: usize = 4 * 3;
+
+  let len = v.len();
+
+  // Three indices near which we are going to choose a pivot.
+  let mut a = len / 4 * 1;
+  let mut b = len / 4 * 2;
+  let mut c = len / 4 * 3;
+
+  // Counts the total number of swaps we are about to perform while sorting indices.
+  let mut swaps = 0;
+
+  if len >= 8 {
+    // Swaps indices so that `v[a] <= v[b]`.
+    // SAFETY: `len >= 8` so there are at least two elements in the neighbourhoods of
+    // `a`, `b` and `c`. This means the three calls to `sort_adjacent` result in
+    // corresponding calls to `sort3` with valid 3-item neighbourhoods around each
+    // pointer, which in turn means the calls to `sort2` are done with valid
+    // references. Thus the `v.get_unchecked` calls are safe, as is the `ptr::swap`
+    // call.
+    const fn sort2<F, T>(
+      v: &mut [T],
+      is_less: &mut F,
+      swaps: &mut usize,
+      a: &mut usize,
+      b: &mut usize,
+    ) where
+      F: ~const FnMut(&T, &T) -> bool,
+    {
+      unsafe {
+        if is_less(v.get_unchecked(*b), v.get_unchecked(*a)) {
+          ptr::swap(a, b);
+          *swaps += 1;
+        }
+      }
+    }
+
+    // Swaps indices so that `v[a] <= v[b] <= v[c]`.
+    const fn sort3<F, T>(
+      v: &mut [T],
+      is_less: &mut F,
+      swaps: &mut usize,
+      a: &mut usize,
+      b: &mut usize,
+      c: &mut usize,
+    ) where
+      F: ~const FnMut(&T, &T) -> bool,
+    {
+      sort2(v, is_less, swaps, a, b);
+      sort2(v, is_less, swaps, b, c);
+      sort2(v, is_less, swaps, a, b);
+    }
+
+    if len >= SHORTEST_MEDIAN_OF_MEDIANS {
+      // Finds the median of `v[a - 1], v[a], v[a + 1]` and stores the index into `a`.
+      const fn sort_adjacent<T, F>(v: &mut [T], is_less: &mut F, swaps: &mut usize, a: &mut usize)
+      where
+        F: ~const FnMut(&T, &T) -> bool,
+      {
+        let tmp = *a;
+        sort3(v, is_less, swaps, &mut (tmp - 1), a, &mut (tmp + 1));
+      }
+
+      // Find medians in the neighbourhoods of `a`, `b`, and `c`.
+      sort_adjacent(v, is_less, &mut swaps, &mut a);
+      sort_adjacent(v, is_less, &mut swaps, &mut b);
+      sort_adjacent(v, is_less, &mut swaps, &mut c);
+    }
+
+    // Find the median among `a`, `b`, and `c`.
+    sort3(v, is_less, &mut swaps, &mut a, &mut b, &mut c);
+  }
+
+  if swaps < MAX_SWAPS {
+    (b, swaps == 0)
+  } else {
+    // The maximum number of swaps was performed. Chances are the slice is descending or mostly
+    // descending, so reversing will probably help sort it faster.
+    v.reverse();
+    (len - 1 - b, true)
+  }
+}
+
+/// Counts how often [`const_quicksort_instrumented`] took its slow paths.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SortStats {
+  /// Number of subslices sorted with heapsort after too many imbalanced partitions.
+  pub heapsort_fallbacks: usize,
+  /// Number of times elements were shuffled to break patterns after an imbalanced partition.
+  pub break_patterns: usize,
+}
+
+impl SortStats {
+  const fn new() -> Self {
+    Self {
+      heapsort_fallbacks: 0,
+      break_patterns: 0,
+    }
+  }
+}
+
+/// Describes how much work [`const_partition_at_index_instrumented`] needed to place an element.
+///
+/// With good pivots, every round shrinks the searched subslice by a constant factor, so both
+/// counts stay close to log2(*n*) for a slice of length *n*. Counts far above that indicate input
+/// that defeats the pivot selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SelectStats {
+  /// Number of times a subslice was partitioned around a pivot.
+  pub partition_rounds: usize,
+  /// Number of nested subslices the search descended into before the element was placed.
+  pub depth: usize,
+}
+
+impl SelectStats {
+  const fn new() -> Self {
+    Self {
+      partition_rounds: 0,
+      depth: 0,
+    }
+  }
+}
+
+/// Slices of up to this length get sorted using insertion sort by [`recurse`] and
+/// [`const_sort_each`].
+const MAX_INSERTION: usize = 20;
+
+/// Sorts `v` recursively.
+///
+/// If the slice had a predecessor in the original array, it is specified as `pred`.
+///
+/// `limit` is the number of allowed imbalanced partitions before switching to `heapsort`. If zero,
+/// this function will immediately switch to heapsort.
+///
+/// The slow paths taken are counted in `stats`, and the major decisions are recorded in `trace`
+/// with indices relative to `start`, the index of `v` in the original slice.
+const fn recurse<'a, T, F>(
+  mut v: &'a mut [T],
+  is_less: &mut F,
+  mut pred: Option<&'a T>,
+  mut limit: u32,
+  mut start: usize,
+  stats: &mut SortStats,
+  trace: &mut Trace<'_>,
+) where
+  F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+{
+  // True if the last partitioning was reasonably balanced.
+  let mut was_balanced = true;
+  // True if the last partitioning didn't shuffle elements (the slice was already partitioned).
+  let mut was_partitioned = true;
+
+  loop {
+    let len = v.len();
+
+    // Very short slices get sorted using insertion sort.
+    if len <= MAX_INSERTION {
+      insertion_sort(v, is_less);
+      return;
+    }
+
+    // If too many bad pivot choices were made, simply fall back to heapsort in order to
+    // guarantee `O(n * log(n))` worst-case.
+    if limit == 0 {
+      stats.heapsort_fallbacks += 1;
+      trace.record(TraceEvent::HeapsortFallback { start, len });
+      const_heapsort(v, is_less);
+      return;
+    }
+
+    // If the last partitioning was imbalanced, try breaking patterns in the slice by shuffling
+    // some elements around. Hopefully we'll choose a better pivot this time.
+    if !was_balanced {
+      stats.break_patterns += 1;
+      trace.record(TraceEvent::BreakPatterns { start, len });
+      break_patterns(v);
+      limit -= 1;
+    }
+
+    // Choose a pivot and try guessing whether the slice is already sorted.
+    let (pivot, likely_sorted) = choose_pivot(v, is_less);
+
+    // If the last partitioning was decently balanced and didn't shuffle elements, and if pivot
+    // selection predicts the slice is likely already sorted...
+    if was_balanced && was_partitioned && likely_sorted {
+      // Try identifying several out-of-order elements and shifting them to correct
+      // positions. If the slice ends up being completely sorted, we're done.
+      if partial_insertion_sort(v, is_less) {
+        trace.record(TraceEvent::Presorted { start, len });
+        return;
+      }
+    }
+
+    // If the chosen pivot is equal to the predecessor, then it's the smallest element in the
+    // slice. Partition the slice into elements equal to and elements greater than the pivot.
+    // This case is usually hit when the slice contains many duplicate elements.
+    if let Some(p) = pred {
+      if !is_less(p, &v[pivot]) {
+        let mid = partition_equal(v, pivot, is_less);
+        trace.record(TraceEvent::PartitionEqual { start, equal: mid });
+
+        // Continue sorting elements greater than the pivot.
+        v = &mut v[mid..];
+        start += mid;
+        continue;
+      }
+    }
+
+    // Partition the slice.
+    let (mid, was_p) = partition(v, pivot, is_less);
+    was_balanced = cmp::min(mid, len - mid) >= len / 8;
+    was_partitioned = was_p;
+    trace.record(TraceEvent::Partition {
+      pivot: start + mid,
+      left: mid,
+      right: len - mid - 1,
+    });
+
+    // Split the slice into `left`, `pivot`, and `right`.
+    let (left, right) = v.split_at_mut(mid);
+    let (pivot, right) = right.split_at_mut(1);
+    let pivot = &pivot[0];
+
+    // Recurse into the shorter side only in order to minimize the total number of recursive
+    // calls and consume less stack space. Then just continue with the longer side (this is
+    // akin to tail recursion).
+    if left.len() < right.len() {
+      recurse(left, is_less, pred, limit, start, stats, trace);
+      v = right;
+      pred = Some(pivot);
+      start += mid + 1;
+    } else {
+      recurse(
+        right,
+        is_less,
+        Some(pivot),
+        limit,
+        start + mid + 1,
+        stats,
+        trace,
+      );
+      v = left;
+    }
+  }
+}
+
+/// Sorts `v` using pattern-defeating quicksort, which is *O*(*n* \* log(*n*)) worst-case.
+///
+/// Constified version of `core::slice::quicksort`.
+///
+/// This is the algorithm behind all `const_sort_unstable*` functions. The `force-heapsort` and
+/// `force-insertion` features replace it by [`const_heapsort`] or
+/// [`const_binary_insertion_sort`], which shrinks binaries that also sort at runtime.
+///
+/// Note: Unstable sort.
+pub const fn const_quicksort<T, F>(v: &mut [T], is_less: F)
+where
+  F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+{
+  const_quicksort_instrumented(v, is_less);
+}
+
+/// Sorts `v` like [`const_quicksort`] and returns how often the slow paths were taken.
+///
+/// Many heapsort fallbacks or pattern breaks indicate input patterns that defeat the pivot
+/// selection, which makes const evaluation noticeably more expensive.
+///
+/// With the `force-heapsort` or `force-insertion` feature, `v` is sorted with [`const_heapsort`]
+/// or [`const_binary_insertion_sort`] instead, and the returned stats are always zero.
+///
+/// Note: Unstable sort.
+pub const fn const_quicksort_instrumented<T, F>(v: &mut [T], mut is_less: F) -> SortStats
+where
+  F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+{
+  let mut stats = SortStats::new();
+  quicksort(v, &mut is_less, &mut stats, &mut Trace::disabled());
+  stats
+}
+
+/// Sorts `v` with the algorithm selected by the features, counting the slow paths of
+/// pattern-defeating quicksort in `stats` and recording its major decisions in `trace`.
+const fn quicksort<T, F>(v: &mut [T], is_less: &mut F, stats: &mut SortStats, trace: &mut Trace<'_>)
+where
+  F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+{
+  if sorts_trivially::<T>() {
+    return;
+  }
+
+  #[cfg(feature = "force-heapsort")]
+  {
+    // The forced algorithms neither count slow paths nor record events.
+    let _ = (stats, trace);
+    const_heapsort(v, is_less);
+  }
+  #[cfg(feature = "force-insertion")]
+  {
+    // The forced algorithms neither count slow paths nor record events.
+    let _ = (stats, trace);
+    const_binary_insertion_sort(v, is_less);
+  }
+  #[cfg(not(any(feature = "force-heapsort", feature = "force-insertion")))]
+  {
+    // Limit the number of imbalanced partitions to `floor(log2(len)) + 1`.
+    let limit = usize::BITS - v.len().leading_zeros();
+
+    recurse(v, is_less, None, limit, 0, stats, trace);
+  }
+}
+
+/// Sorts `v` like [`const_quicksort`] and records its major decisions in `trace`.
+///
+/// The events are written to the front of `trace` in the order they happened, and the total
+/// number of events is returned. Events that do not fit into `trace` are counted but not written,
+/// so a returned count larger than `trace.len()` means the log was truncated. Pathological inputs
+/// show up as many [`TraceEvent::BreakPatterns`] and [`TraceEvent::HeapsortFallback`] events or
+/// [`TraceEvent::Partition`]s with one tiny side.
+///
+/// With the `force-heapsort` or `force-insertion` feature, no events are recorded.
+///
+/// Note: Unstable sort.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort_core::{const_quicksort_traced, TraceEvent};
+///
+/// const TRACE: ([Option<TraceEvent>; 8], usize) = {
+///   let mut v = [0u32; 64];
+///   let mut i = 0;
+///   while i < 64 {
+///     v[i] = (i as u32 * 37) % 64;
+///     i += 1;
+///   }
+///   let mut trace = [None; 8];
+///   let events = const_quicksort_traced(&mut v, PartialOrd::lt, &mut trace);
+///   (trace, events)
+/// };
+/// // The first partition splits the slice in half.
+/// assert_eq!(
+///   TRACE.0[0],
+///   Some(TraceEvent::Partition { pivot: 32, left: 32, right: 31 })
+/// );
+/// assert_eq!(TRACE.1, 5);
+/// ```
+#[cfg(feature = "trace")]
+pub const fn const_quicksort_traced<T, F>(
+  v: &mut [T],
+  mut is_less: F,
+  trace: &mut [Option<TraceEvent>],
+) -> usize
+where
+  F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+{
+  let mut trace = Trace::new(trace);
+  quicksort(v, &mut is_less, &mut SortStats::new(), &mut trace);
+  trace.events()
+}
+
+/// Identifies a sorting algorithm of this crate.
+///
+/// The results of unstable sorts depend on the algorithm, so a const item sorted with
+/// [`const_sort_with`] keeps its exact contents across crate upgrades and feature changes as long
+/// as it names the same algorithm. New algorithms may be added in future versions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Algorithm {
+  /// Pattern-defeating quicksort, i.e. [`const_quicksort`] without the `force-*` features.
+  Pdq,
+  /// [`const_heapsort`].
+  Heap,
+  /// [`const_binary_insertion_sort`].
+  Insertion,
+  /// [`const_selection_sort`].
+  Selection,
+  /// [`const_cocktail_sort`].
+  Cocktail,
+  /// [`const_merge_sort`].
+  Merge,
+  /// [`const_sort_adaptive`].
+  Adaptive,
+}
+
+impl Algorithm {
+  /// Returns the algorithm used by [`const_quicksort`] and therefore by all `const_sort_unstable*`
+  /// functions of `const_sort`, which depends on the `force-heapsort` and `force-insertion`
+  /// features.
+  #[must_use]
+  pub const fn current() -> Self {
+    if cfg!(feature = "force-heapsort") {
+      Self::Heap
+    } else if cfg!(feature = "force-insertion") {
+      Self::Insertion
+    } else {
+      Self::Pdq
+    }
+  }
+
+  /// Returns `true` if the algorithm keeps equal elements in their original order.
+  #[must_use]
+  pub const fn is_stable(self) -> bool {
+    matches!(
+      self,
+      Self::Insertion | Self::Cocktail | Self::Merge | Self::Adaptive
+    )
+  }
+
+  /// Returns an upper bound of the number of comparisons the algorithm makes to sort `len`
+  /// elements.
+  ///
+  /// The bound is derived from the worst case of the algorithm, so it is not tight, and saturates
+  /// at `usize::MAX`. Use it to budget the latency of a sort on a hard real-time path.
+  #[must_use]
+  pub const fn estimated_comparisons(self, len: usize) -> usize {
+    let n_log = len.saturating_mul(log2_len(len));
+    match self {
+      // Up to `log` imbalanced and about `6 * log` balanced partitioning levels, a heapsort
+      // fallback and insertion sorts of short subslices.
+      Self::Pdq => n_log
+        .saturating_mul(10)
+        .saturating_add(len.saturating_mul(10)),
+      Self::Heap => n_log.saturating_mul(3),
+      // One binary search of at most `log` comparisons per element.
+      Self::Insertion => n_log,
+      Self::Selection | Self::Cocktail => quadratic(len),
+      // Merges and run scans of `log` levels and insertion sorts of short runs.
+      Self::Merge | Self::Adaptive => n_log
+        .saturating_mul(3)
+        .saturating_add(len.saturating_mul(10)),
+    }
+  }
+
+  /// Returns an upper bound of the number of bytes the algorithm moves to sort `len` elements of
+  /// `size_of_t` bytes each.
+  ///
+  /// Swapping two elements counts as moving three. Like
+  /// [`estimated_comparisons`](Self::estimated_comparisons), the bound is derived from the worst
+  /// case of the algorithm, so it is not tight, and saturates at `usize::MAX`. Together they bound
+  /// the time a sort spends, e.g. in an interrupt-free section on a microcontroller.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use const_sort_core::Algorithm;
+  ///
+  /// // Selection sort swaps every position at most once.
+  /// assert_eq!(Algorithm::Selection.estimated_moves(100, 8), 3 * 99 * 8);
+  /// assert!(Algorithm::Heap.estimated_moves(100, 8) < Algorithm::Cocktail.estimated_moves(100, 8));
+  /// ```
+  #[must_use]
+  pub const fn estimated_moves(self, len: usize, size_of_t: usize) -> usize {
+    let log = log2_len(len);
+    let n_log = len.saturating_mul(log);
+    let moves = match self {
+      // Up to three moves per element and partitioning level, a heapsort fallback and insertion
+      // sorts of short subslices.
+      Self::Pdq => n_log
+        .saturating_mul(30)
+        .saturating_add(len.saturating_mul(12)),
+      Self::Heap => n_log.saturating_mul(5),
+      Self::Insertion => quadratic(len).saturating_add(len.saturating_mul(2)),
+      Self::Selection => len.saturating_sub(1).saturating_mul(3),
+      Self::Cocktail => quadratic(len).saturating_mul(3),
+      // Every merge level rotates every element up to `log` times.
+      Self::Merge | Self::Adaptive => n_log
+        .saturating_mul(log)
+        .saturating_mul(3)
+        .saturating_add(len.saturating_mul(14)),
+    };
+    moves.saturating_mul(size_of_t)
+  }
+}
+
+/// Returns the number of bits of `len`, i.e. `floor(log2(len)) + 1` for `len > 0`.
+const fn log2_len(len: usize) -> usize {
+  (usize::BITS - len.leading_zeros()) as usize
+}
+
+/// Returns the number of pairs of `len` elements, i.e. `len * (len - 1) / 2`, saturating at
+/// `usize::MAX`.
+const fn quadratic(len: usize) -> usize {
+  if len % 2 == 0 {
+    (len / 2).saturating_mul(len.saturating_sub(1))
+  } else {
+    len.saturating_mul((len - 1) / 2)
+  }
+}
+
+/// Sorts `v` with `algorithm`, regardless of the `force-heapsort` and `force-insertion`
+/// features.
+///
+/// Pin the algorithm of a sort whose output must be reproducible, e.g. an unstable sort of
+/// elements that compare equal but are distinguishable.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort_core::{const_sort_with, Algorithm};
+///
+/// const V: [u8; 5] = {
+///   let mut v = [5, 1, 4, 2, 3];
+///   const_sort_with(Algorithm::Heap, &mut v, PartialOrd::lt);
+///   v
+/// };
+/// assert_eq!(V, [1, 2, 3, 4, 5]);
+/// ```
+pub const fn const_sort_with<T, F>(algorithm: Algorithm, v: &mut [T], mut is_less: F)
+where
+  F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+{
+  match algorithm {
+    Algorithm::Pdq => {
+      if sorts_trivially::<T>() {
+        return;
+      }
+      let limit = usize::BITS - v.len().leading_zeros();
+      let mut stats = SortStats::new();
+      recurse(
+        v,
+        &mut is_less,
+        None,
+        limit,
+        0,
+        &mut stats,
+        &mut Trace::disabled(),
+      );
+    },
+    Algorithm::Heap => const_heapsort(v, is_less),
+    Algorithm::Insertion => const_binary_insertion_sort(v, is_less),
+    Algorithm::Selection => const_selection_sort(v, is_less),
+    Algorithm::Cocktail => const_cocktail_sort(v, is_less),
+    Algorithm::Merge => const_merge_sort(v, is_less),
+    Algorithm::Adaptive => const_sort_adaptive(v, is_less),
+  }
+}
+
+/// Sorts every array of `arrays`.
+///
+/// This is equivalent to calling [`const_quicksort`] on every array, but the algorithm is chosen
+/// once for the whole batch instead of once per array. Arrays of up to 20 elements, where
+/// [`const_quicksort`] would fall back to insertion sort anyway, are insertion sorted directly,
+/// which saves the dispatch overhead when sorting many small records.
+///
+/// Note: Unstable sort.
+pub const fn const_sort_each<T, F, const N: usize, const M: usize>(
+  arrays: &mut [[T; N]; M],
+  mut is_less: F,
+) where
+  F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+{
+  // Forcing heapsort must not pull in insertion sort.
+  #[cfg(not(feature = "force-heapsort"))]
+  if N <= MAX_INSERTION && !sorts_trivially::<T>() {
+    let mut i = 0;
+    while i < M {
+      insertion_sort(&mut arrays[i], &mut is_less);
+      i += 1;
+    }
+    return;
+  }
+
+  let mut i = 0;
+  while i < M {
+    const_quicksort(&mut arrays[i], &mut is_less);
+    i += 1;
+  }
+}
+
+const fn partition_at_index_loop<'a, T, F>(
+  mut v: &'a mut [T],
+  mut index: usize,
+  is_less: &mut F,
+  mut pred: Option<&'a T>,
+  stats: &mut SelectStats,
+) where
+  F: ~const FnMut(&T, &T) -> bool,
+{
+  loop {
+    // For slices of up to this length it's probably faster to simply sort them.
+    const MAX_INSERTION: usize = 10;
+    if v.len() <= MAX_INSERTION {
+      insertion_sort(v, is_less);
+      return;
+    }
+
+    // Choose a pivot
+    let (pivot, _) = choose_pivot(v, is_less);
+
+    // If the chosen pivot is equal to the predecessor, then it's the smallest element in the
+    // slice. Partition the slice into elements equal to and elements greater than the pivot.
+    // This case is usually hit when the slice contains many duplicate elements.
+    if let Some(p) = pred {
+      if !is_less(p, &v[pivot]) {
+        let mid = partition_equal(v, pivot, is_less);
+        stats.partition_rounds += 1;
+
+        // If we've passed our index, then we're good.
+        if mid > index {
+          return;
+        }
+
+        // Otherwise, continue sorting elements greater than the pivot.
+        v = &mut v[mid..];
+        index -= mid;
+        pred = None;
+        stats.depth += 1;
+        continue;
+      }
+    }
+
+    let (mid, _) = partition(v, pivot, is_less);
+    stats.partition_rounds += 1;
+
+    // Split the slice into `left`, `pivot`, and `right`.
+    let (left, right) = v.split_at_mut(mid);
+    let (pivot, right) = right.split_at_mut(1);
+    let pivot = &pivot[0];
+
+    if mid != index {
+      stats.depth += 1;
+    }
+    if mid < index {
+      v = right;
+      index = index - mid - 1;
+      pred = Some(pivot);
+    } else if mid > index {
+      v = left;
+    } else {
+      // If mid == index, then we're done, since partition() guaranteed that all elements
+      // after mid are greater than or equal to mid.
+      return;
+    }
+  }
+}
+
+/// Reorders `v` such that the element at `index` is at its final sorted position, where
+/// `is_less` returns whether the first argument is less than the second.
+///
+/// Returns the elements before `index`, the element at `index` and the elements after `index`.
+/// No element before `index` is greater than any element after it.
+///
+/// Constified version of `core::slice::select::partition_at_index`.
+///
+/// Note: Unstable, *O*(*n*) worst-case.
+///
+/// # Panics
+///
+/// Panics when `index >= v.len()`.
+pub const fn const_partition_at_index<T, F>(
+  v: &mut [T],
+  index: usize,
+  mut is_less: F,
+) -> (&mut [T], &mut T, &mut [T])
+where
+  F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+{
+  partition_at_index(v, index, &mut is_less, &mut SelectStats::new());
+
+  let (left, right) = v.split_at_mut(index);
+  let (pivot, right) = right.split_at_mut(1);
+  let pivot = &mut pivot[0];
+  (left, pivot, right)
+}
+
+/// Reorders `v` like [`const_partition_at_index`] and returns how many partition rounds were
+/// needed.
+///
+/// Selecting from adversarial input, e.g. generated data that happens to defeat the pivot
+/// selection, can take up to *O*(*n*) rounds instead of *O*(log(*n*)). If the returned stats show
+/// that, fully sorting with [`const_heapsort`], which is *O*(*n* \* log(*n*)) in the worst case,
+/// and indexing the result may be the cheaper choice for that input.
+///
+/// Note: Unstable.
+///
+/// # Panics
+///
+/// Panics when `index >= v.len()`.
+pub const fn const_partition_at_index_instrumented<T, F>(
+  v: &mut [T],
+  index: usize,
+  mut is_less: F,
+) -> SelectStats
+where
+  F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+{
+  let mut stats = SelectStats::new();
+  partition_at_index(v, index, &mut is_less, &mut stats);
+  stats
+}
+
+/// Places the element at `index` at its final sorted position, counting the work in `stats`.
+const fn partition_at_index<T, F>(
+  v: &mut [T],
+  index: usize,
+  is_less: &mut F,
+  stats: &mut SelectStats,
+) where
+  F: ~const FnMut(&T, &T) -> bool,
+{
+  if index >= v.len() {
+    const fn const_panic(_index: usize, _len: usize) {
+      panic!("partition_at_index index ? greater than length of slice ?")
+    }
+    fn rt_panic(index: usize, len: usize) {
+      panic!(
+        "partition_at_index index {} greater than length of slice {}",
+        index, len
+      )
+    }
+    unsafe { const_eval_select((index, v.len()), const_panic, rt_panic) }
+  }
+
+  if sorts_trivially::<T>() {
+    // Do nothing.
+  } else if index == v.len() - 1 {
+    // Find max element and place it in the last position of the array. We're free to use
+    // `unwrap()` here because we know v must not be empty.
+    let mut max_index = 0;
+    let mut i = 0;
+    while i < v.len() {
+      if is_less(&v[max_index], &v[i]) {
+        max_index = i;
+      }
+      i += 1;
+    }
+    v.swap(max_index, index);
+  } else if index == 0 {
+    // Find min element and place it in the first position of the array. We're free to use
+    // `unwrap()` here because we know v must not be empty.
+    let mut min_index = 0;
+    let mut i = 0;
+    while i < v.len() {
+      if is_less(&v[i], &v[min_index]) {
+        min_index = i;
+      }
+      i += 1;
+    }
+    v.swap(min_index, index);
+  } else {
+    partition_at_index_loop(v, index, is_less, None, stats);
+  }
+}
+
+/// Reorders `v` such that the element at `index` is at its final sorted position according to
+/// the comparator function `compare`.
+///
+/// See [`const_partition_at_index`] for the returned values.
+///
+/// Note: Unstable, *O*(*n*) worst-case.
+///
+/// # Panics
+///
+/// Panics when `index >= v.len()`.
+pub const fn const_partition_at_index_by<T, F>(
+  v: &mut [T],
+  index: usize,
+  mut compare: F,
+) -> (&mut [T], &mut T, &mut [T])
+where
+  F: ~const FnMut(&T, &T) -> cmp::Ordering + ~const Destruct,
+{
+  let mut is_less = const |a: &T, b: &T| compare(a, b) == cmp::Ordering::Less;
+  const_partition_at_index(v, index, &mut is_less)
+}
+
+/// Reorders `v` such that the element at `index` is at its final sorted position according to
+/// the keys extracted by `f`.
+///
+/// See [`const_partition_at_index`] for the returned values.
+///
+/// Note: Unstable, *O*(*n*) worst-case.
+///
+/// # Panics
+///
+/// Panics when `index >= v.len()`.
+pub const fn const_partition_at_index_by_key<T, K, F>(
+  v: &mut [T],
+  index: usize,
+  mut f: F,
+) -> (&mut [T], &mut T, &mut [T])
+where
+  F: ~const FnMut(&T) -> K + ~const Destruct,
+  K: Ord + ~const PartialOrd + ~const Destruct,
+{
+  let mut is_less = const |a: &T, b: &T| f(a).lt(&f(b));
+  const_partition_at_index(v, index, &mut is_less)
+}
+
+/// Returns the permutation of indices that sorts the parallel slices `keys1` and `keys2` by the
+/// pairs `(keys1[i], keys2[i])` in lexicographic order.
+///
+/// This avoids materializing an array of tuples, which may lack a const `Ord` implementation.
+/// Position `i` of the result holds the index of the `i`-th smallest pair. Indices of equal pairs
+/// keep their original relative order.
+///
+/// # Panics
+///
+/// Panics if `keys1` or `keys2` is not exactly `N` elements long.
+#[must_use]
+pub const fn const_argsort_lexicographic<A, B, const N: usize>(
+  keys1: &[A],
+  keys2: &[B],
+) -> [usize; N]
+where
+  A: Ord + ~const PartialOrd,
+  B: Ord + ~const PartialOrd,
+{
+  assert!(
+    keys1.len() == N && keys2.len() == N,
+    "argsort keys must have exactly N elements"
+  );
+
+  let mut indices = [0; N];
+  let mut i = 0;
+  while i < N {
+    indices[i] = i;
+    i += 1;
+  }
+
+  const_quicksort(&mut indices, const |a: &usize, b: &usize| {
+    let (a, b) = (*a, *b);
+    if keys1[a].lt(&keys1[b]) {
+      return true;
+    }
+    if keys1[b].lt(&keys1[a]) {
+      return false;
+    }
+    if keys2[a].lt(&keys2[b]) {
+      return true;
+    }
+    if keys2[b].lt(&keys2[a]) {
+      return false;
+    }
+    a < b
+  });
+  indices
+}
+
+/// Returns the permutation of row indices that sorts `rows` by the columns `key_cols` in
+/// lexicographic order.
+///
+/// Rows are compared by `key_cols[0]` first, ties are broken by `key_cols[1]` and so on. Position
+/// `i` of the result holds the index of the `i`-th smallest row. Rows with equal keys keep their
+/// original relative order. `rows` is left untouched.
+///
+/// # Panics
+///
+/// Panics if a key column is not less than `C`.
+#[must_use]
+pub const fn const_argsort_rows_by<T, const R: usize, const C: usize>(
+  rows: &[[T; C]; R],
+  key_cols: &[usize],
+) -> [usize; R]
+where
+  T: Ord + ~const PartialOrd,
+{
+  let mut key = 0;
+  while key < key_cols.len() {
+    assert!(key_cols[key] < C, "key column out of bounds");
+    key += 1;
+  }
+
+  let mut indices = [0; R];
+  let mut i = 0;
+  while i < R {
+    indices[i] = i;
+    i += 1;
+  }
+
+  const_quicksort(&mut indices, const |a: &usize, b: &usize| {
+    let (a, b) = (*a, *b);
+    let mut key = 0;
+    while key < key_cols.len() {
+      let col = key_cols[key];
+      if rows[a][col].lt(&rows[b][col]) {
+        return true;
+      }
+      if rows[b][col].lt(&rows[a][col]) {
+        return false;
+      }
+      key += 1;
+    }
+    a < b
+  });
+  indices
+}
+
+/// Merges the `K` sorted slices `shards` into `out` using a tournament tree of losers, which is
+/// *O*(*n* \* log(*k*)) worst-case.
+///
+/// Every output element costs *O*(log(*k*)) comparisons instead of the *O*(*k*) needed to scan the
+/// heads of all shards, which keeps the const evaluation of large tables assembled from many
+/// sorted fragments cheap. Elements are written to the front of `out` and their number is
+/// returned.
+///
+/// Note: Stable merge. Equal elements are taken from the shard with the lower index first.
+///
+/// # Panics
+///
+/// Panics if `out` is shorter than the total length of all shards.
+pub const fn const_kmerge_tree<T, F, const K: usize>(
+  shards: &[&[T]; K],
+  out: &mut [T],
+  mut is_less: F,
+) -> usize
+where
+  T: Copy,
+  F: ~const FnMut(&T, &T) -> bool + ~const Destruct,
+{
+  /// Returns whether the head of shard `a` is taken before the head of shard `b`.
+  /// Exhausted shards lose against all others.
+  const fn beats<T, F, const K: usize>(
+    shards: &[&[T]; K],
+    heads: &[usize; K],
+    a: usize,
+    b: usize,
+    is_less: &mut F,
+  ) -> bool
+  where
+    F: ~const FnMut(&T, &T) -> bool,
+  {
+    if heads[a] >= shards[a].len() {
+      return false;
+    }
+    if heads[b] >= shards[b].len() {
+      return true;
+    }
+    let (x, y) = (&shards[a][heads[a]], &shards[b][heads[b]]);
+    is_less(x, y) || (a < b && !is_less(y, x))
+  }
+
+  let mut total = 0;
+  let mut i = 0;
+  while i < K {
+    total += shards[i].len();
+    i += 1;
+  }
+  assert!(total <= out.len(), "merge output buffer is too short");
+  if K == 0 {
+    return 0;
+  }
+
+  let mut heads = [0; K];
+  // Node `0` holds the overall winner, the inner nodes `1..K` the loser of the match played there.
+  // The parent of the leaf for shard `i` is `(i + K) / 2`, the parent of node `n` is `n / 2`.
+  const EMPTY: usize = usize::MAX;
+  let mut tree = [EMPTY; K];
+
+  // Build the tree. The first shard reaching an inner node waits there for its opponent.
+  let mut i = 0;
+  while i < K {
+    let mut winner = i;
+    let mut node = (i + K) / 2;
+    while node > 0 {
+      if tree[node] == EMPTY {
+        tree[node] = winner;
+        winner = EMPTY;
+        break;
+      }
+      if beats(shards, &heads, tree[node], winner, &mut is_less) {
+        mem::swap(&mut tree[node], &mut winner);
+      }
+      node /= 2;
+    }
+    if winner != EMPTY {
+      tree[0] = winner;
+    }
+    i += 1;
+  }
+
+  let mut n = 0;
+  while n < total {
+    let winner_shard = tree[0];
+    out[n] = shards[winner_shard][heads[winner_shard]];
+    heads[winner_shard] += 1;
+    n += 1;
+
+    // Replay the matches on the path of the winner, which now presents its next element.
+    let mut winner = winner_shard;
+    let mut node = (winner_shard + K) / 2;
+    while node > 0 {
+      if beats(shards, &heads, tree[node], winner, &mut is_less) {
+        mem::swap(&mut tree[node], &mut winner);
+      }
+      node /= 2;
+    }
+    tree[0] = winner;
+  }
+  n
+}
+
+/// Sorts `v` by counting its `false` elements, which is *O*(*n*) worst-case.
+///
+/// Boolean flag tables only hold two distinct values, so they are sorted by writing the counted
+/// number of `false` elements followed by `true` elements instead of running a comparison sort.
+// Reached as `const_sort::const_sort::const_sort_bool`, next to the other `const_sort_*` entry
+// points, so the name keeps its prefix.
+#[allow(clippy::module_name_repetitions)]
+pub const fn const_sort_bool(v: &mut [bool]) {
+  let mut falses = 0;
+  let mut i = 0;
+  while i < v.len() {
+    if !v[i] {
+      falses += 1;
+    }
+    i += 1;
+  }
+
+  let mut i = 0;
+  while i < v.len() {
+    v[i] = i >= falses;
+    i += 1;
+  }
+}
+
+/// Sorts the fixed-length byte strings in `v` lexicographically, *O*(*n* \* log(*n*)) worst-case.
+///
+/// Hashes and digests are compared with a plain byte loop that stops at the first differing byte
+/// during const evaluation and with the `memcmp` based comparison of byte slices at runtime,
+/// instead of going through the generic array comparison.
+///
+/// Note: Unstable sort.
+pub const fn const_sort_byte_arrays<const K: usize>(v: &mut [[u8; K]]) {
+  const fn const_lt<const K: usize>(a: &[u8; K], b: &[u8; K]) -> bool {
+    let mut i = 0;
+    while i < K {
+      if a[i] != b[i] {
+        return a[i] < b[i];
+      }
+      i += 1;
+    }
+    false
+  }
+  fn rt_lt<const K: usize>(a: &[u8; K], b: &[u8; K]) -> bool {
+    a.as_slice() < b.as_slice()
+  }
+  let is_less = const |a: &[u8; K], b: &[u8; K]| {
+    // SAFETY: Both functions compare the arrays the same way.
+    unsafe { const_eval_select((a, b), const_lt, rt_lt) }
+  };
+  const_quicksort(v, is_less);
+}
+
+/// Returns the minimum number of swaps of two elements that sort `v`.
+///
+/// Every element is paired with its position in the stably sorted array. This permutation
+/// decomposes into cycles and a cycle of length *k* needs *k* - 1 swaps, so the result is `N`
+/// minus the number of cycles. It is exact if the elements are distinct; equal elements are
+/// assumed to keep their relative order, which may overestimate the minimum.
+///
+/// This function is *O*(*n* \* log(*n*)) and uses two arrays of length `N` as scratch space.
+#[must_use]
+pub const fn const_min_swaps_to_sort<T, const N: usize>(v: &[T; N]) -> usize
+where
+  T: Ord + ~const PartialOrd,
+{
+  let mut order = [0; N];
+  let mut i = 0;
+  while i < N {
+    order[i] = i;
+    i += 1;
+  }
+  const_quicksort(&mut order, const |a: &usize, b: &usize| {
+    v[*a].lt(&v[*b]) || (!v[*b].lt(&v[*a]) && a < b)
+  });
+
+  let mut visited = [false; N];
+  let mut cycles = 0;
+  let mut i = 0;
+  while i < N {
+    if !visited[i] {
+      cycles += 1;
+      let mut j = i;
+      while !visited[j] {
+        visited[j] = true;
+        j = order[j];
+      }
+    }
+    i += 1;
+  }
+  N - cycles
+}
+
+/// Returns the first index in `lo..hi` for which `pred` returns `false`, or `hi` if there is
+/// none.
+///
+/// `pred` must be monotone on `lo..hi`, i.e. return `true` up to some index and `false` from
+/// then on, otherwise the result is unspecified. Unlike a binary search of a slice, the values
+/// are computed on demand, e.g. to find the smallest table size or shift that satisfies a
+/// compile-time constraint.
+///
+/// This is *O*(log(`hi` - `lo`)) calls of `pred`.
+///
+/// # Panics
+///
+/// Panics if `lo > hi`.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// use const_sort_core::const_bisect;
+///
+/// const fn too_small(bits: usize) -> bool {
+///   (1u64 << bits) < 1000
+/// }
+///
+/// // The number of bits needed to index 1000 entries.
+/// const BITS: usize = const_bisect(0, 64, too_small);
+/// assert_eq!(BITS, 10);
+/// ```
+#[must_use]
+pub const fn const_bisect<P>(mut lo: usize, mut hi: usize, mut pred: P) -> usize
+where
+  P: ~const FnMut(usize) -> bool + ~const Destruct,
+{
+  assert!(lo <= hi, "lo must not be greater than hi");
+  while lo < hi {
+    let mid = lo + (hi - lo) / 2;
+    if pred(mid) {
+      lo = mid + 1;
+    } else {
+      hi = mid;
+    }
+  }
+  lo
+}
+
+/// Searches the matrix `m`, whose rows and columns are both sorted in ascending order, for `x`.
+///
+/// Returns the row and column of a matching element, or `None` if `m` does not contain `x`. If
+/// there are multiple matches, then any one of the matches could be returned.
+///
+/// This is a staircase search starting in the top right corner, which is *O*(*R* + *C*).
+#[must_use]
+pub const fn const_search_sorted_matrix<T, const R: usize, const C: usize>(
+  m: &[[T; C]; R],
+  x: &T,
+) -> Option<(usize, usize)>
+where
+  T: Ord + ~const PartialOrd,
+{
+  if C == 0 {
+    return None;
+  }
+
+  // Every element left of `col` in the rows above `row` is less than `x`, every element below
+  // `row` in the columns right of `col` is greater than `x`.
+  let mut row = 0;
+  let mut col = C - 1;
+  while row < R {
+    let elem = &m[row][col];
+    if elem.lt(x) {
+      row += 1;
+    } else if x.lt(elem) {
+      if col == 0 {
+        return None;
+      }
+      col -= 1;
+    } else {
+      return Some((row, col));
+    }
+  }
+  None
+}
+
+/// Checks that `compare` is a total order on the elements of `sample`.
+///
+/// Sorting with a comparator that is not a total order produces an unspecified order, so this
+/// helps to debug comparators on a small sample of problematic elements. Returns the indices
+/// `(i, j)` of the first pair of elements whose comparison is inconsistent, or `None` if
+/// `compare` is a total order on `sample`:
+///
+/// - Reflexivity: `compare(a, a)` must be `Equal`, otherwise `(i, i)` is returned.
+/// - Antisymmetry: `compare(a, b)` must be the reverse of `compare(b, a)`.
+/// - Transitivity: if `a <= b` and `b <= c`, then `a <= c` must hold and `a == c` if both
+///   `a == b` and `b == c`, otherwise the indices of `a` and `c` are returned.
+///
+/// This function is *O*(*n*^3), so it is only intended for small samples.
+#[must_use]
+pub const fn const_check_total_order<T, F>(sample: &[T], mut compare: F) -> Option<(usize, usize)>
+where
+  F: ~const FnMut(&T, &T) -> cmp::Ordering + ~const Destruct,
+{
+  use cmp::Ordering::{Equal, Greater, Less};
+
+  let n = sample.len();
+  let mut i = 0;
+  while i < n {
+    if !matches!(compare(&sample[i], &sample[i]), Equal) {
+      return Some((i, i));
+    }
+    let mut j = i + 1;
+    while j < n {
+      let consistent = matches!(
+        (
+          compare(&sample[i], &sample[j]),
+          compare(&sample[j], &sample[i])
+        ),
+        (Less, Greater) | (Equal, Equal) | (Greater, Less)
+      );
+      if !consistent {
+        return Some((i, j));
+      }
+      j += 1;
+    }
+    i += 1;
+  }
+
+  let mut i = 0;
+  while i < n {
+    let mut j = 0;
+    while j < n {
+      let ij = compare(&sample[i], &sample[j]);
+      if !matches!(ij, Greater) {
+        let mut k = 0;
+        while k < n {
+          let jk = compare(&sample[j], &sample[k]);
+          if !matches!(jk, Greater) {
+            let ik = compare(&sample[i], &sample[k]);
+            let consistent = if matches!((ij, jk), (Equal, Equal)) {
+              matches!(ik, Equal)
+            } else {
+              matches!(ik, Less)
+            };
+            if !consistent {
+              return Some((i, k));
+            }
+          }
+          k += 1;
+        }
+      }
+      j += 1;
+    }
+    i += 1;
+  }
+  None
+}
+
+/// Checks that the parallel tables `left` and `right` are ordered the same way by `cmp_left` and
+/// `cmp_right`.
+///
+/// Returns `true` if every pair of indices compares the same in both tables, i.e. a stable sort
+/// of either table moves the elements the same way. Use it in a const assertion that tables
+/// generated separately, e.g. names and their ids, were generated in sync. Returns `false` if the
+/// tables differ in length.
+///
+/// This function is *O*(*n*^2), as it compares every pair of indices.
+///
+/// # Examples
+///
+/// ```
+/// #![feature(const_mut_refs)]
+/// #![feature(const_trait_impl)]
+/// #![feature(const_cmp)]
+/// use core::cmp::Ordering;
+/// use const_sort_core::const_same_order;
+///
+/// const fn by_name(a: &&str, b: &&str) -> Ordering {
+///   let (a, b) = (a.as_bytes(), b.as_bytes());
+///   a[0].cmp(&b[0])
+/// }
+/// const fn by_id(a: &u16, b: &u16) -> Ordering {
+///   a.cmp(b)
+/// }
+///
+/// const NAMES: [&str; 3] = ["gamma", "alpha", "beta"];
+/// const IDS: [u16; 3] = [30, 10, 20];
+/// const _: () = assert!(const_same_order(&NAMES, &IDS, by_name, by_id));
+/// assert!(!const_same_order(&NAMES, &[30, 20, 10], by_name, by_id));
+/// ```
+#[must_use]
+pub const fn const_same_order<A, B, FA, FB>(
+  left: &[A],
+  right: &[B],
+  mut cmp_left: FA,
+  mut cmp_right: FB,
+) -> bool
+where
+  FA: ~const FnMut(&A, &A) -> cmp::Ordering + ~const Destruct,
+  FB: ~const FnMut(&B, &B) -> cmp::Ordering + ~const Destruct,
+{
+  if left.len() != right.len() {
+    return false;
+  }
+  let len = left.len();
+  let mut i = 0;
+  while i < len {
+    let mut j = i + 1;
+    while j < len {
+      if cmp_left(&left[i], &left[j]) != cmp_right(&right[i], &right[j]) {
+        return false;
+      }
+      j += 1;
+    }
+    i += 1;
+  }
+  true
+}